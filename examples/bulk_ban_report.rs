@@ -0,0 +1,54 @@
+//! Look up the summaries and bans of any number of profiles and print the banned ones as json.
+//!
+//! ```sh
+//! STEAM_API_KEY=<key> cargo run --example bulk_ban_report -- 76561197960287930 76561197960287931
+//! cat ids.txt | STEAM_API_KEY=<key> cargo run --example bulk_ban_report
+//! ```
+//!
+//! The ids are requested in chunks of 100, at most 10 requests per second.
+
+use std::error::Error;
+use std::io::BufRead;
+
+use steam_api_concurrent::rate_limit::RateSchedule;
+use steam_api_concurrent::{Client, SteamId};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY isn't set")?;
+
+    // ids from the arguments, or one per line from stdin
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() {
+        args = std::io::stdin().lock().lines().collect::<Result<_, _>>()?;
+    }
+    let ids = args
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<SteamId>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let client = Client::builder()
+        .api_key(key)
+        .rate_schedule(RateSchedule::new(10.0))
+        .build()
+        .await?;
+    let profiles = client.get_profiles_with_bans(&ids).await?;
+
+    let banned = profiles
+        .values()
+        .filter(|profile| profile.bans.as_ref().is_some_and(|bans| !bans.is_clean()))
+        .collect::<Vec<_>>();
+    for profile in &banned {
+        println!("{}", serde_json::to_string(profile)?);
+    }
+    eprintln!(
+        "{} of {} profiles found, {} banned",
+        profiles.len(),
+        ids.len(),
+        banned.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Walk the friend lists starting at one profile and print the edges as csv.
+//!
+//! ```sh
+//! STEAM_API_KEY=<key> cargo run --example friend_graph -- 76561197960287930 2 > edges.csv
+//! ```
+//!
+//! The second argument is how many steps away from the start profiles are visited,
+//! the number of requests grows quickly with it.
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use futures::{StreamExt, TryStreamExt};
+use steam_api_concurrent::governor::GovernorConfig;
+use steam_api_concurrent::{Client, Endpoint, SteamId};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY isn't set")?;
+
+    let mut args = std::env::args().skip(1);
+    let start: SteamId = args.next().ok_or("missing start steam id")?.parse()?;
+    let depth: usize = args.next().map_or(Ok(1), |depth| depth.parse())?;
+
+    // back off instead of burning the retries when Steam starts answering with 429
+    let client = Client::builder()
+        .api_key(key)
+        .governor(GovernorConfig::default())
+        .build()
+        .await?;
+
+    println!("source,target,friends_since");
+    let mut visited = HashSet::from([start]);
+    let mut layer = vec![start];
+    for _ in 0..depth {
+        let lists = futures::stream::iter(&layer)
+            .map(|&id| {
+                let client = &client;
+                async move { Ok::<_, Box<dyn Error>>((id, client.get_player_friends(id).await?)) }
+            })
+            .buffer_unordered(Endpoint::PlayerFriends.concurrent_requests())
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut next = Vec::new();
+        for (source, list) in lists {
+            // private friend lists are skipped
+            let Some(friends) = list.into_inner() else {
+                continue;
+            };
+            for (target, friend) in friends {
+                println!("{},{},{}", source, target, friend.friends_since.timestamp());
+                if visited.insert(target) {
+                    next.push(target);
+                }
+            }
+        }
+        layer = next;
+    }
+    eprintln!("{} profiles visited", visited.len());
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Resolve many vanity urls concurrently and print `name,steam_id` as csv.
+//!
+//! ```sh
+//! STEAM_API_KEY=<key> cargo run --example resolve_vanity -- gabelogannewell robinwalker
+//! cat names.txt | STEAM_API_KEY=<key> cargo run --example resolve_vanity
+//! ```
+//!
+//! Names that don't exist get an empty steam id, names Steam refused to resolve
+//! because of rate limits are printed to stderr.
+
+use std::error::Error;
+use std::io::BufRead;
+
+use futures::StreamExt;
+use steam_api_concurrent::api::VanityResolution;
+use steam_api_concurrent::{Client, Endpoint};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY isn't set")?;
+
+    let mut names = std::env::args().skip(1).collect::<Vec<_>>();
+    if names.is_empty() {
+        names = std::io::stdin().lock().lines().collect::<Result<_, _>>()?;
+    }
+    names.retain(|name| !name.trim().is_empty());
+
+    let client = Client::builder().api_key(key).build().await?;
+    let mut results = futures::stream::iter(&names)
+        .map(|name| {
+            let client = &client;
+            async move { (name, client.resolve_vanity_url(name.trim()).await) }
+        })
+        .buffered(Endpoint::ResolveVanityUrl.concurrent_requests());
+
+    println!("name,steam_id");
+    while let Some((name, result)) = results.next().await {
+        match result {
+            Ok(VanityResolution::Found(steam_id)) => println!("{},{}", name.trim(), steam_id),
+            Ok(VanityResolution::NotFound) => println!("{},", name.trim()),
+            Ok(VanityResolution::RateLimited) => eprintln!("rate limited: {}", name.trim()),
+            Err(err) => return Err(err.into()),
+        }
+    }
+    eprintln!("{} retries", client.total_retries());
+    Ok(())
+}
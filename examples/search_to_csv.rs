@@ -0,0 +1,57 @@
+//! Export the results of a community user search as csv.
+//!
+//! ```sh
+//! STEAM_API_KEY=<key> cargo run --example search_to_csv --features user_search -- sauce 3
+//! ```
+//!
+//! The second argument is the number of pages, with 20 results each.
+
+use std::error::Error;
+
+use steam_api_concurrent::Client;
+
+/// Quote `field` if it contains anything that would break the row
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY isn't set")?;
+
+    let mut args = std::env::args().skip(1);
+    let query = args.next().ok_or("missing search query")?;
+    let pages: usize = args.next().map_or(Ok(1), |pages| pages.parse())?;
+
+    let client = Client::builder()
+        .api_key(key)
+        .retry_on_empty(true)
+        .build()
+        .await?;
+
+    println!("steam_id,persona_name,profile_url,aliases");
+    for page in 1..=pages {
+        let results = client.get_search_page(&query, page).await?.results;
+        if results.is_empty() {
+            break;
+        }
+        for entry in results {
+            println!(
+                "{},{},{},{}",
+                entry
+                    .steam_id()
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+                csv_field(&entry.persona_name),
+                csv_field(&entry.profile_url),
+                csv_field(&entry.aliases.join(";")),
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,396 @@
+//! Re-record the files in `test_resources/` from the live endpoints.
+//!
+//! ```sh
+//! cargo run -p fixtures -- refresh --key <api-key>
+//! cargo run -p fixtures -- refresh --only player_bans.json
+//! ```
+//!
+//! Personal data is replaced by a hash of itself, so refreshing twice gives the same files
+//! and tests can still compare the redacted values. The tests assert on the recorded data,
+//! so check them after refreshing.
+//!
+//! The publisher endpoints and the group feeds aren't recorded, see [`SKIPPED`].
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use steam_api_concurrent::{Endpoint, Host};
+
+#[derive(Parser)]
+#[command(about = "Re-record the test resources from the live endpoints")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Re-record every fixture, or only the given ones
+    Refresh {
+        /// Steam Web API key
+        #[arg(long, env = "STEAM_API_KEY", hide_env_values = true)]
+        key: String,
+        /// Only record the fixture with this file name, e.g. `player_bans.json`
+        #[arg(long)]
+        only: Vec<String>,
+    },
+    /// Print the fixtures that are recorded and the ones that aren't
+    List,
+}
+
+/// A file in `test_resources/` and the request it's recorded from
+struct Fixture {
+    file: &'static str,
+    endpoint: Endpoint,
+    /// Appended to the url of the endpoint
+    sub_path: &'static str,
+    /// The api-key is added for [`Host::Api`] endpoints
+    query: &'static [(&'static str, &'static str)],
+    /// Arrays are cut down to this many elements, for endpoints with huge responses
+    truncate: Option<usize>,
+}
+
+impl Fixture {
+    const fn new(
+        file: &'static str,
+        endpoint: Endpoint,
+        query: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            file,
+            endpoint,
+            sub_path: "",
+            query,
+            truncate: None,
+        }
+    }
+}
+
+const FIXTURES: [Fixture; 24] = [
+    Fixture::new(
+        "vanity_url.json",
+        Endpoint::ResolveVanityUrl,
+        &[("vanityurl", "GabeLoganNewell")],
+    ),
+    Fixture::new(
+        "player_summaries.json",
+        Endpoint::PlayerSummaries,
+        &[("steamids", "76561198230177976,76561199159691884")],
+    ),
+    Fixture::new(
+        "player_bans.json",
+        Endpoint::PlayerBans,
+        &[("steamids", "76561198196615742,76561199159691884")],
+    ),
+    Fixture::new(
+        "player_friends_public.json",
+        Endpoint::PlayerFriends,
+        &[("steamid", "76561198089612262"), ("relationship", "friend")],
+    ),
+    Fixture::new(
+        "player_friends_all.json",
+        Endpoint::PlayerFriends,
+        &[("steamid", "76561198089612262"), ("relationship", "all")],
+    ),
+    Fixture::new(
+        "player_friends_private.json",
+        Endpoint::PlayerFriends,
+        &[("steamid", "76561198196615742"), ("relationship", "friend")],
+    ),
+    Fixture::new(
+        "steam_level.json",
+        Endpoint::PlayerSteamLevel,
+        &[("steamid", "76561198196615742")],
+    ),
+    Fixture::new(
+        "steam_level_deleted.json",
+        Endpoint::PlayerSteamLevel,
+        &[("steamid", "76561197992321696")],
+    ),
+    Fixture::new(
+        "owned_games.json",
+        Endpoint::OwnedGames,
+        &[
+            ("steamid", "76561198089612262"),
+            ("include_appinfo", "1"),
+            ("include_played_free_games", "1"),
+        ],
+    ),
+    Fixture::new(
+        "owned_games_private.json",
+        Endpoint::OwnedGames,
+        &[
+            ("steamid", "76561198196615742"),
+            ("include_appinfo", "1"),
+            ("include_played_free_games", "1"),
+        ],
+    ),
+    Fixture::new(
+        "user_stats.json",
+        Endpoint::UserStatsForGame,
+        &[("steamid", "76561198089612262"), ("appid", "440")],
+    ),
+    Fixture::new(
+        "profile_items_equipped.json",
+        Endpoint::ProfileItemsEquipped,
+        &[("steamid", "76561198089612262")],
+    ),
+    Fixture::new(
+        "published_file_details.json",
+        Endpoint::PublishedFileDetails,
+        &[
+            ("itemcount", "2"),
+            ("publishedfileids[0]", "3070290869"),
+            ("publishedfileids[1]", "1"),
+        ],
+    ),
+    Fixture::new("server_info.json", Endpoint::ServerInfo, &[]),
+    Fixture {
+        truncate: Some(3),
+        ..Fixture::new("supported_api_list.json", Endpoint::SupportedApiList, &[])
+    },
+    Fixture::new(
+        "servers_at_address.json",
+        Endpoint::ServersAtAddress,
+        &[("addr", "162.254.193.6")],
+    ),
+    Fixture {
+        truncate: Some(9),
+        ..Fixture::new("app_list.json", Endpoint::AppList, &[])
+    },
+    Fixture::new("cm_list.json", Endpoint::CmList, &[("cellid", "0")]),
+    Fixture::new(
+        "cm_list_for_connect.json",
+        Endpoint::CmListForConnect,
+        &[("cellid", "0"), ("cmtype", "websockets")],
+    ),
+    Fixture::new(
+        "item_def_meta.json",
+        Endpoint::ItemDefMeta,
+        &[("appid", "440")],
+    ),
+    Fixture {
+        // the account id of 76561198089612262
+        sub_path: "129346534/json",
+        ..Fixture::new("mini_profile.json", Endpoint::MiniProfile, &[])
+    },
+    Fixture::new(
+        "deck_compatibility.json",
+        Endpoint::DeckCompatibilityReport,
+        &[("nAppID", "620"), ("l", "english")],
+    ),
+    Fixture::new(
+        "user_search.json",
+        Endpoint::UserSearch,
+        &[("filter", "users"), ("text", "sauce"), ("page", "1")],
+    ),
+    Fixture::new(
+        "app_search.json",
+        Endpoint::UserSearch,
+        &[
+            ("filter", "apps"),
+            ("text", "counter-strike"),
+            ("page", "1"),
+        ],
+    ),
+];
+
+/// Fixtures that can't be recorded with an api-key, and why
+const SKIPPED: [(&str, &str); 4] = [
+    (
+        "app_betas.json, app_builds.json, asset_prices.json, cheating_reports.json, \
+         inventory.json, lobby_data.json, report_player_cheating.json, \
+         request_player_game_ban.json",
+        "need a publisher key with access to the app",
+    ),
+    (
+        "group_rss.xml, group_members.html, group_page.html, group_member_list.xml, \
+         group_member_list_2.xml, market_listings.html, profile.html, profile_animated.html, profile.xml",
+        "not json",
+    ),
+    (
+        "openid_check_authentication.txt",
+        "needs an assertion of a login that wasn't verified yet",
+    ),
+    (
+        "item_def_archive.json",
+        "depends on the digest of item_def_meta.json",
+    ),
+];
+
+/// Fields with personal data, their values are replaced by [`redact_value`]
+const REDACTED_FIELDS: [&str; 6] = [
+    "realname",
+    "loccountrycode",
+    "locstatecode",
+    "loccityid",
+    "gameserverip",
+    "gameserversteamid",
+];
+
+/// A stand-in for `value` that only depends on the field and the value
+fn redact_value(field: &str, value: &Value) -> Value {
+    let digest = Sha256::new()
+        .chain_update(field)
+        .chain_update(b":")
+        .chain_update(value.to_string())
+        .finalize();
+    let short = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    match value {
+        Value::Number(_) => Value::from(short),
+        Value::String(str) if str.is_empty() => Value::String(String::new()),
+        _ => Value::String(format!("redacted-{:08x}", short)),
+    }
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (field, value) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&field.as_str()) {
+                    *value = redact_value(field, value);
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn truncate(value: &mut Value, max: usize) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(|value| truncate(value, max)),
+        Value::Array(values) => {
+            values.truncate(max);
+            values.iter_mut().for_each(|value| truncate(value, max));
+        }
+        _ => {}
+    }
+}
+
+fn test_resources() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("test_resources")
+}
+
+async fn record(
+    client: &reqwest::Client,
+    key: &str,
+    session_id: &str,
+    fixture: &Fixture,
+) -> Result<Value, Box<dyn Error>> {
+    let url = format!("{}{}", fixture.endpoint.url(), fixture.sub_path);
+    let mut query = fixture.query.to_vec();
+    match fixture.endpoint.host() {
+        Host::Api => query.push(("key", key)),
+        // rejected without the session id, the client sends it as a cookie as well
+        Host::Community if fixture.endpoint == Endpoint::UserSearch => {
+            query.push(("sessionid", session_id))
+        }
+        _ => {}
+    }
+
+    let request = match fixture.endpoint.method() {
+        "POST" => client.post(&url).form(&query),
+        _ => client.get(&url).query(&query),
+    };
+    let mut value = request
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Value>()
+        .await?;
+
+    redact(&mut value);
+    if let Some(max) = fixture.truncate {
+        truncate(&mut value, max);
+    }
+    Ok(value)
+}
+
+async fn refresh(key: &str, only: &[String]) -> Result<(), Box<dyn Error>> {
+    let client = steam_api_concurrent::Client::builder()
+        .api_key(key.to_string())
+        .build()
+        .await?;
+    let http = client.clone_client();
+    let dir = test_resources();
+
+    for fixture in &FIXTURES {
+        if !only.is_empty() && !only.iter().any(|file| file == fixture.file) {
+            continue;
+        }
+        let value = record(&http, key, client.session_id(), fixture).await?;
+        let json = serde_json::to_string_pretty(&value)? + "\n";
+        std::fs::write(dir.join(fixture.file), json)?;
+        println!("recorded {}", fixture.file);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    match Cli::parse().command {
+        Command::Refresh { key, only } => refresh(&key, &only).await,
+        Command::List => {
+            for fixture in &FIXTURES {
+                println!("{}: {}", fixture.file, fixture.endpoint);
+            }
+            for (files, reason) in SKIPPED {
+                println!("skipped {}: {}", files, reason);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{redact, test_resources, truncate, FIXTURES};
+
+    #[test]
+    fn redacts_deterministically() {
+        let mut fst =
+            json!({"players": [{"realname": "Gabe", "loccityid": 3961, "personaname": "x"}]});
+        let mut snd = fst.clone();
+        redact(&mut fst);
+        redact(&mut snd);
+        assert_eq!(fst, snd);
+
+        let player = &fst["players"][0];
+        assert!(player["realname"]
+            .as_str()
+            .unwrap()
+            .starts_with("redacted-"));
+        assert_ne!(player["loccityid"], 3961);
+        assert!(player["loccityid"].is_u64());
+        assert_eq!(player["personaname"], "x");
+    }
+
+    #[test]
+    fn truncates_arrays() {
+        let mut value = json!({"applist": {"apps": [1, 2, 3, 4]}});
+        truncate(&mut value, 2);
+        assert_eq!(value, json!({"applist": {"apps": [1, 2]}}));
+    }
+
+    #[test]
+    fn fixtures_exist() {
+        for fixture in &FIXTURES {
+            assert!(
+                test_resources().join(fixture.file).is_file(),
+                "{}",
+                fixture.file
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Keep a record of every request the client makes, for research-usage policies
+//! that require documented data collection.
+//!
+//! Every attempt, including retries, is passed to the [`AuditSink`] configured with
+//! [`ClientBuilder::audit_sink`](crate::ClientBuilder::audit_sink). [`RotatingFile`]
+//! appends the records as json lines to a file and rotates it once it gets too big.
+//!
+//! The api key is never recorded, the query hash is of the [`CanonicalQuery`], which
+//! redacts it and doesn't depend on the order of the parameters.
+
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::query::CanonicalQuery;
+use crate::Endpoint;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    pub at: DateTime<Utc>,
+    pub endpoint: Endpoint,
+    /// Sha256 of the canonical query and form body, see [`query_hash`]
+    pub query_hash: String,
+    /// [`None`], if no response arrived
+    pub status: Option<u16>,
+    /// Sha256 of the response body, [`None`] for unsuccessful responses
+    pub response_digest: Option<String>,
+}
+
+/// Receives a record for every request attempt
+pub trait AuditSink: Debug + Send + Sync {
+    fn record(&self, record: &AuditRecord) -> io::Result<()>;
+}
+
+/// Lowercase hex sha256 of `data`
+pub fn digest(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Sha256 of the [`CanonicalQuery`] of the request, which includes a form body
+pub fn query_hash(request: &reqwest::Request) -> String {
+    digest(CanonicalQuery::from_request(request).as_str().as_bytes())
+}
+
+#[derive(Debug)]
+struct FileState {
+    file: File,
+    len: u64,
+}
+
+/// Appends records as json lines to `path`.
+///
+/// Once the file would grow beyond `max_bytes`, it's renamed to `path.1`, the previous
+/// `path.1` to `path.2` and so on, keeping at most `max_files` old files.
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    state: Mutex<FileState>,
+}
+
+impl RotatingFile {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let path = path.into();
+        let file = Self::open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            state: Mutex::new(FileState { file, len }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path of the `n`-th rotated file
+    pub fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", n));
+        path.into()
+    }
+
+    fn open(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for n in (1..self.max_files).rev() {
+                let from = self.rotated_path(n);
+                if from.exists() {
+                    fs::rename(from, self.rotated_path(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        state.file = Self::open(&self.path)?;
+        state.len = 0;
+        Ok(())
+    }
+}
+
+impl AuditSink for RotatingFile {
+    fn record(&self, record: &AuditRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut state = self.state.lock().expect("audit file lock poisoned");
+        if state.len > 0 && state.len + line.len() as u64 > self.max_bytes {
+            self.rotate(&mut state)?;
+        }
+        state.file.write_all(&line)?;
+        state.len += line.len() as u64;
+        drop(state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use chrono::Utc;
+
+    use super::{digest, query_hash, AuditRecord, AuditSink, RotatingFile};
+    use crate::Endpoint;
+
+    #[test]
+    fn hashes_without_key() {
+        let client = reqwest::Client::new();
+        let request = |key| {
+            client
+                .get("https://api.steampowered.com/")
+                .query(&[("key", key), ("steamids", "76561197960287930")])
+                .build()
+                .unwrap()
+        };
+        assert_eq!(query_hash(&request("a")), query_hash(&request("b")));
+        let reordered = client
+            .get("https://api.steampowered.com/")
+            .query(&[("steamids", "76561197960287930"), ("key", "c")])
+            .build()
+            .unwrap();
+        assert_eq!(query_hash(&request("a")), query_hash(&reordered));
+        assert_eq!(
+            digest(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn rotates() {
+        let dir = std::env::temp_dir().join(format!("steam_api_audit_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let sink = RotatingFile::new(dir.join("audit.log"), 300, 2).unwrap();
+
+        let record = AuditRecord {
+            at: Utc::now(),
+            endpoint: Endpoint::PlayerBans,
+            query_hash: digest(b"steamids=76561197960287930&"),
+            status: Some(200),
+            response_digest: Some(digest(b"{}")),
+        };
+        for _ in 0..4 {
+            sink.record(&record).unwrap();
+        }
+
+        let lines = |path: &Path| fs::read_to_string(path).unwrap().lines().count();
+        assert_eq!(lines(sink.path()), 1);
+        assert_eq!(lines(&sink.rotated_path(1)), 1);
+        assert_eq!(lines(&sink.rotated_path(2)), 1);
+        assert!(!sink.rotated_path(3).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn records_requests() {
+        use std::io;
+        use std::sync::{Arc, Mutex};
+
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        #[derive(Debug, Default)]
+        struct Memory(Mutex<Vec<AuditRecord>>);
+        impl AuditSink for Memory {
+            fn record(&self, record: &AuditRecord) -> io::Result<()> {
+                self.0.lock().unwrap().push(record.clone());
+                Ok(())
+            }
+        }
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 1).await;
+        let sink = Arc::new(Memory::default());
+        let client = steam
+            .client_builder()
+            .audit_sink(sink.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let ids = [SteamId(76561197960287930)];
+        let bans = client.get_player_bans(ids[..].into()).await.unwrap();
+        assert!(!bans.is_empty());
+
+        let records = sink.0.lock().unwrap().clone();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].status, Some(429));
+        assert_eq!(records[0].response_digest, None);
+        assert_eq!(records[1].status, Some(200));
+        assert_eq!(records[1].response_digest.as_ref().unwrap().len(), 64);
+        assert_eq!(records[0].query_hash, records[1].query_hash);
+    }
+}
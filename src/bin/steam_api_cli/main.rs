@@ -0,0 +1,132 @@
+//! Command line companion for the library, mostly useful for quick lookups.
+//!
+//! The api-key is read from `--key`, the `STEAM_API_KEY` environment variable or a `.env` file.
+
+mod output;
+
+use std::error::Error;
+
+use clap::{Parser, Subcommand};
+use futures::future::try_join_all;
+use output::Format;
+use serde::Serialize;
+use steam_api_concurrent::api::VanityResolution;
+use steam_api_concurrent::{convert, Client, Endpoint, SteamId};
+
+#[derive(Parser)]
+#[command(version, about = "Query the Steam API from the command line")]
+struct Cli {
+    /// Steam Web API key
+    #[arg(long, env = "STEAM_API_KEY", hide_env_values = true, global = true)]
+    key: Option<String>,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Resolve a vanity url (the part after `steamcommunity.com/id/`)
+    Resolve { vanity: String },
+    /// Get the profile summaries of the given ID64s
+    Summary {
+        #[arg(required = true)]
+        ids: Vec<SteamId>,
+    },
+    /// Get the bans of the given ID64s
+    Bans {
+        #[arg(required = true)]
+        ids: Vec<SteamId>,
+    },
+    /// Get the friends of the given ID64
+    Friends { id: SteamId },
+    /// Search for users by name
+    Search {
+        query: String,
+        #[arg(long, default_value_t = 1)]
+        page: usize,
+    },
+    /// Print an id in every representation
+    Convert { id: String },
+}
+
+#[derive(Serialize)]
+struct VanityRow<'a> {
+    vanity: &'a str,
+    /// Empty if nobody uses the vanity url
+    steam_id: Option<SteamId>,
+}
+
+async fn client(key: Option<String>) -> Result<Client, Box<dyn Error>> {
+    let key = key.ok_or("missing api-key, pass --key or set STEAM_API_KEY")?;
+    Ok(Client::builder().api_key(key).build().await?)
+}
+
+fn chunks(ids: &[SteamId], endpoint: Endpoint) -> std::slice::Chunks<'_, SteamId> {
+    ids.chunks(endpoint.max_ids_per_request().unwrap_or(1))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv::dotenv().ok();
+    let cli = Cli::parse();
+
+    let rendered = match cli.command {
+        Command::Convert { id } => output::render(&[convert(&id)?], cli.format)?,
+        Command::Resolve { vanity } => {
+            let client = client(cli.key).await?;
+            let steam_id = match client.resolve_vanity_url(&vanity).await? {
+                VanityResolution::Found(steam_id) => Some(steam_id),
+                VanityResolution::NotFound => None,
+                VanityResolution::RateLimited => return Err("rate limited by steam".into()),
+            };
+            let row = VanityRow {
+                vanity: &vanity,
+                steam_id,
+            };
+            output::render(&[row], cli.format)?
+        }
+        Command::Summary { ids } => {
+            let client = client(cli.key).await?;
+            let requests = chunks(&ids, Endpoint::PlayerSummaries)
+                .map(|chunk| client.get_player_summaries(chunk.into()));
+            let summaries = try_join_all(requests).await?;
+            let rows = summaries
+                .into_iter()
+                .flat_map(|chunk| chunk.into_inner().into_values())
+                .collect::<Vec<_>>();
+            output::render(&rows, cli.format)?
+        }
+        Command::Bans { ids } => {
+            let client = client(cli.key).await?;
+            let requests = chunks(&ids, Endpoint::PlayerBans)
+                .map(|chunk| client.get_player_bans(chunk.into()));
+            let bans = try_join_all(requests).await?;
+            let rows = bans
+                .into_iter()
+                .flat_map(|chunk| chunk.into_inner().into_values())
+                .collect::<Vec<_>>();
+            output::render(&rows, cli.format)?
+        }
+        Command::Friends { id } => {
+            let client = client(cli.key).await?;
+            let Some(friends) = client.get_player_friends(id).await?.into_inner() else {
+                return Err(format!("the friends list of {} is private", id).into());
+            };
+            let rows = friends.into_values().collect::<Vec<_>>();
+            output::render(&rows, cli.format)?
+        }
+        Command::Search { query, page } => {
+            let client = client(cli.key).await?;
+            let page = client.get_search_page(&query, page).await?;
+            output::render(&page.results, cli.format)?
+        }
+    };
+
+    print!("{}", rendered);
+    Ok(())
+}
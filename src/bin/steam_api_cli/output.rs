@@ -0,0 +1,109 @@
+//! Render rows of serializable records as a table, json or csv
+
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Each record is flattened into one row, the columns are the union of all keys
+pub fn render<T: Serialize>(records: &[T], format: Format) -> serde_json::Result<String> {
+    if format == Format::Json {
+        return serde_json::to_string_pretty(records).map(|json| json + "\n");
+    }
+
+    let rows = records
+        .iter()
+        .map(|record| match serde_json::to_value(record)? {
+            Value::Object(map) => Ok(map),
+            other => Ok(Map::from_iter([("value".to_string(), other)])),
+        })
+        .collect::<serde_json::Result<Vec<_>>>()?;
+
+    let mut columns: Vec<&str> = Vec::new();
+    for key in rows.iter().flat_map(|row| row.keys()) {
+        if !columns.contains(&key.as_str()) {
+            columns.push(key);
+        }
+    }
+
+    let cells = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|col| row.get(*col).map(cell).unwrap_or_default())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(match format {
+        Format::Table => table(&columns, &cells),
+        Format::Csv => csv(&columns, &cells),
+        Format::Json => unreachable!(),
+    })
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(str) => str.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths = columns
+        .iter()
+        .map(|col| col.chars().count())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut buf = String::new();
+    let mut push_row = |cells: &mut dyn Iterator<Item = &str>| {
+        let line = cells
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        writeln!(buf, "{}", line.trim_end()).unwrap();
+    };
+
+    push_row(&mut columns.iter().copied());
+    let separator = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    push_row(&mut separator.iter().map(String::as_str));
+    for row in rows {
+        push_row(&mut row.iter().map(String::as_str));
+    }
+    buf
+}
+
+fn csv(columns: &[&str], rows: &[Vec<String>]) -> String {
+    fn escape(field: &str) -> String {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
+    let mut buf = String::new();
+    let header = columns.iter().map(|col| escape(col)).collect::<Vec<_>>();
+    writeln!(buf, "{}", header.join(",")).unwrap();
+    for row in rows {
+        let row = row.iter().map(|cell| escape(cell)).collect::<Vec<_>>();
+        writeln!(buf, "{}", row.join(",")).unwrap();
+    }
+    buf
+}
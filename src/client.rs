@@ -1,25 +1,312 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use reqwest::cookie::Jar;
-use reqwest::header::{HeaderValue, SET_COOKIE};
+use reqwest::header::{HeaderValue, ACCEPT_LANGUAGE, SET_COOKIE};
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
-use crate::constants::USER_SEARCH_API;
+#[cfg(feature = "audit")]
+use crate::audit::{self, AuditRecord, AuditSink};
+use crate::concurrency::{ConcurrencyConfig, ConcurrencyConfigError, ConcurrencyController};
+use crate::constants::AVATAR_BASE_URL;
+use crate::dns::{IpVersion, IpVersionResolver, Resolve, SharedResolver};
+use crate::governor::{Governor, GovernorConfig};
+use crate::rate_limit::{RateCoordinator, RateLimiter, RateSchedule};
+#[cfg(feature = "schema_drift")]
+use crate::schema_drift::SchemaDrift;
+use crate::tenant::{Quota, Tenant};
+use crate::{Endpoint, Host, RateClass};
 
+/// The language tag of a language name Steam knows, for `Accept-Language`
+fn accept_language(language: &str) -> Option<&'static str> {
+    let code = match language {
+        "english" => "en",
+        "german" => "de",
+        "french" => "fr",
+        "italian" => "it",
+        "spanish" => "es",
+        "latam" => "es-419",
+        "portuguese" => "pt",
+        "brazilian" => "pt-BR",
+        "russian" => "ru",
+        "polish" => "pl",
+        "dutch" => "nl",
+        "swedish" => "sv",
+        "danish" => "da",
+        "finnish" => "fi",
+        "norwegian" => "no",
+        "czech" => "cs",
+        "hungarian" => "hu",
+        "romanian" => "ro",
+        "bulgarian" => "bg",
+        "greek" => "el",
+        "turkish" => "tr",
+        "ukrainian" => "uk",
+        "schinese" => "zh-CN",
+        "tchinese" => "zh-TW",
+        "japanese" => "ja",
+        "koreana" => "ko",
+        "thai" => "th",
+        "vietnamese" => "vi",
+        "indonesian" => "id",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Cheap to clone, clones share the configuration, connection pool and session
+/// but count their own retries, see [`Client::stats`] and [`Client::global_stats`]
 pub struct Client {
+    inner: Arc<Inner>,
+    /// Counters of this handle only
+    stats: ClientStats,
+    tenant: Option<Arc<Tenant>>,
+}
+
+/// The parts of a [`Client`] shared by all of its clones
+struct Inner {
     retry_timeout: Duration,
     max_retries: usize,
     dont_retry: Vec<StatusCode>,
     session_id: String,
+    /// When the session id was handed out
+    session_created: Instant,
     api_keys: Vec<String>,
-    client: reqwest::Client,
+    publisher_key: Option<String>,
+    api_base_url: String,
+    community_base_url: String,
+    partner_base_url: String,
+    store_base_url: String,
+    avatar_base_url: String,
+    avatar_cache_dir: Option<PathBuf>,
+    /// One per local address, they share the cookie with the session id
+    clients: Vec<reqwest::Client>,
+    next_client: AtomicUsize,
+    /// The most recent failed attempts, newest last
+    errors: Mutex<VecDeque<RecordedError>>,
+    error_history: usize,
+    /// How much of a body that fails to parse is kept, `0` keeps none
+    #[cfg(feature = "body_capture")]
+    capture_body: usize,
+    on_retry: Option<RetryObserver>,
+    #[cfg(feature = "user_search")]
+    retry_on_empty: bool,
+    governor: Option<Governor>,
+    concurrency: Option<ConcurrencyController>,
+    rate_limiter: Option<RateLimiter>,
+    minimal_fields: bool,
+    /// Sent as `l` and `Accept-Language` with community requests
+    language: String,
+    /// Stops the bulk requests, see [`Client::shutdown`]
+    cancel: CancellationToken,
+    #[cfg(feature = "audit")]
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    #[cfg(feature = "schema_drift")]
+    schema_drift: SchemaDrift,
+    /// Counters of every handle sharing this
+    stats: ClientStats,
+}
+
+/// Counters of the requests made by a [`Client`]
+#[derive(Debug)]
+pub struct ClientStats {
     total_retries: AtomicUsize,
+    endpoint_retries: [AtomicUsize; Endpoint::COUNT],
+    empty_results: AtomicUsize,
+    /// Responses per status code, attempts without a response are counted as `0`
+    statuses: Mutex<BTreeMap<u16, usize>>,
+    /// The most recent latencies per class, oldest first
+    latencies: Mutex<BTreeMap<RateClass, VecDeque<Duration>>>,
+    #[cfg(feature = "audit")]
+    audit_failures: AtomicUsize,
+    coordinator_failures: AtomicUsize,
+}
+
+/// Latencies of the most recent attempts of one [`RateClass`], see [`ClientStats::latencies`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LatencySummary {
+    /// How many attempts the summary is based on, at most [`ClientStats::LATENCY_WINDOW`]
+    pub samples: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    /// Summarize `latencies`, [`None`] if there are none
+    fn from_samples(latencies: impl IntoIterator<Item = Duration>) -> Option<Self> {
+        let mut sorted = latencies.into_iter().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let max = *sorted.last()?;
+        // nearest rank, so every percentile is a latency that was measured
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        Some(LatencySummary {
+            samples: sorted.len(),
+            p50: percentile(50),
+            p95: percentile(95),
+            max,
+        })
+    }
+}
+
+// `Default` isn't implemented for arrays of more than 32 elements
+impl Default for ClientStats {
+    fn default() -> Self {
+        Self {
+            total_retries: AtomicUsize::new(0),
+            endpoint_retries: std::array::from_fn(|_| AtomicUsize::new(0)),
+            empty_results: AtomicUsize::new(0),
+            statuses: Mutex::default(),
+            latencies: Mutex::default(),
+            #[cfg(feature = "audit")]
+            audit_failures: AtomicUsize::new(0),
+            coordinator_failures: AtomicUsize::new(0),
+        }
+    }
 }
 
+impl ClientStats {
+    /// How many of the most recent latencies are kept per [`RateClass`]
+    pub const LATENCY_WINDOW: usize = 1024;
+
+    pub fn total_retries(&self) -> usize {
+        self.total_retries.load(Ordering::SeqCst)
+    }
+    /// Retries of requests made to `endpoint`
+    pub fn endpoint_retries(&self, endpoint: Endpoint) -> usize {
+        self.endpoint_retries[endpoint.index()].load(Ordering::SeqCst)
+    }
+    /// Responses that succeeded but were empty, see [`ClientBuilder::retry_on_empty`]
+    pub fn empty_results(&self) -> usize {
+        self.empty_results.load(Ordering::SeqCst)
+    }
+    /// Records the audit sink failed to write
+    #[cfg(feature = "audit")]
+    pub fn audit_failures(&self) -> usize {
+        self.audit_failures.load(Ordering::SeqCst)
+    }
+    /// Slots the [`ClientBuilder::rate_coordinator`] failed to reserve, which were
+    /// taken from the limiter of this process instead
+    pub fn coordinator_failures(&self) -> usize {
+        self.coordinator_failures.load(Ordering::SeqCst)
+    }
+    /// How many attempts got each status code, retries included.
+    ///
+    /// Attempts that got no response, e.g. because of a timeout, are counted under `0`.
+    pub fn status_histogram(&self) -> BTreeMap<u16, usize> {
+        self.statuses.lock().expect("stats lock poisoned").clone()
+    }
+    /// Latency of the most recent attempts to endpoints of `class`, retries included,
+    /// [`None`] if there were none.
+    ///
+    /// Measured from sending the request until the headers of the response arrived,
+    /// time spent waiting for rate limits isn't included.
+    pub fn latency(&self, class: RateClass) -> Option<LatencySummary> {
+        let samples = self
+            .latencies
+            .lock()
+            .expect("stats lock poisoned")
+            .get(&class)?
+            .clone();
+        LatencySummary::from_samples(samples)
+    }
+    /// [`ClientStats::latency`] of every class that had requests.
+    ///
+    /// Community pages are a lot slower than the keyed api, so it's worth
+    /// tuning the concurrency of both classes separately.
+    pub fn latencies(&self) -> BTreeMap<RateClass, LatencySummary> {
+        let latencies = self.latencies.lock().expect("stats lock poisoned").clone();
+        latencies
+            .into_iter()
+            .filter_map(|(class, samples)| Some((class, LatencySummary::from_samples(samples)?)))
+            .collect()
+    }
+    /// Set every counter to zero
+    pub fn reset(&self) {
+        self.total_retries.store(0, Ordering::SeqCst);
+        for retries in &self.endpoint_retries {
+            retries.store(0, Ordering::SeqCst);
+        }
+        self.empty_results.store(0, Ordering::SeqCst);
+        self.statuses.lock().expect("stats lock poisoned").clear();
+        self.latencies.lock().expect("stats lock poisoned").clear();
+        #[cfg(feature = "audit")]
+        self.audit_failures.store(0, Ordering::SeqCst);
+        self.coordinator_failures.store(0, Ordering::SeqCst);
+    }
+    fn add_latency(&self, class: RateClass, latency: Duration) {
+        let mut latencies = self.latencies.lock().expect("stats lock poisoned");
+        let samples = latencies.entry(class).or_default();
+        if samples.len() == Self::LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+        drop(latencies);
+    }
+    fn add_status(&self, status: Option<StatusCode>) {
+        let mut statuses = self.statuses.lock().expect("stats lock poisoned");
+        *statuses
+            .entry(status.map_or(0, |status| status.as_u16()))
+            .or_default() += 1;
+    }
+    fn add_retries(&self, endpoint: Endpoint, retries: usize) {
+        self.total_retries.fetch_add(retries, Ordering::SeqCst);
+        self.endpoint_retries[endpoint.index()].fetch_add(retries, Ordering::SeqCst);
+    }
+}
+
+/// A failed attempt to send a request, see [`Client::last_errors`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordedError {
+    pub at: DateTime<Utc>,
+    pub endpoint: Endpoint,
+    /// [`None`] if there was no response, e.g. on a timeout
+    pub status: Option<u16>,
+    pub message: String,
+    /// The start of a json body that failed to parse, see [`ClientBuilder::capture_body`]
+    #[cfg(feature = "body_capture")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Whether Steam still accepts the session id, see [`Client::validate_session`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionStatus {
+    pub valid: bool,
+    /// How long ago the session id was handed out.
+    ///
+    /// The session cookie has no expiry, so this is the only hint how long it has left,
+    /// compare it with the age at which earlier sessions stopped being accepted.
+    pub age: Duration,
+    /// The status of the probe, `401` once the session is rejected
+    pub status: StatusCode,
+}
+
+/// A request that is about to be retried, see [`ClientBuilder::on_retry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryInfo {
+    pub endpoint: Endpoint,
+    /// The attempt that is about to be made, `2` for the first retry
+    pub attempt: usize,
+    /// The status of the failed attempt, [`None`] if there was no response,
+    /// e.g. on a timeout, and `200` for [empty results](ClientBuilder::retry_on_empty)
+    pub status: Option<StatusCode>,
+    /// How long the client waits before the retry
+    pub delay: Duration,
+}
+
+type RetryObserver = Arc<dyn Fn(&RetryInfo) + Send + Sync>;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("builder configuration is invalid: {0}")]
@@ -36,14 +323,99 @@ pub enum Error {
     SetCookieLen,
     #[error("builder is missing api-key")]
     ApiKey,
+    #[error("adaptive concurrency config is invalid: {0}")]
+    Concurrency(#[from] ConcurrencyConfigError),
+    #[error("builder has a rate coordinator but no rate schedule")]
+    RateSchedule,
 }
 type Result<T> = std::result::Result<T, Error>;
 
+/// Connection settings of the [`reqwest::Client`] built by [`ClientBuilder::build`],
+/// [`None`] keeps the default of reqwest
+#[derive(Debug, Clone, Default)]
+struct HttpConfig {
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    http2_keep_alive_interval: Option<Duration>,
+    http2_keep_alive_timeout: Option<Duration>,
+    http2_adaptive_window: Option<bool>,
+    /// Hosts pinned to addresses, see [`ClientBuilder::resolve`]
+    resolve: Vec<(String, Vec<SocketAddr>)>,
+    dns_resolver: Option<SharedResolver>,
+    ip_version: Option<IpVersion>,
+    local_addresses: Vec<IpAddr>,
+}
+
+impl HttpConfig {
+    fn apply(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        if let Some(adaptive) = self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(adaptive);
+        }
+        match (self.dns_resolver.clone(), self.ip_version) {
+            (Some(resolver), Some(version)) => {
+                builder =
+                    builder.dns_resolver(Arc::new(IpVersionResolver::new(Some(resolver), version)));
+            }
+            (Some(resolver), None) => builder = builder.dns_resolver(Arc::new(resolver)),
+            (None, Some(version)) => {
+                builder = builder.dns_resolver(Arc::new(IpVersionResolver::new(None, version)));
+            }
+            (None, None) => {}
+        }
+        for (host, addrs) in &self.resolve {
+            builder = builder.resolve_to_addrs(host, addrs);
+        }
+        builder
+    }
+}
+
 pub struct ClientBuilder {
     retry_timeout: Option<Duration>,
     max_retries: Option<usize>,
     api_keys: Vec<String>,
+    publisher_key: Option<String>,
     dont_retry: Vec<StatusCode>,
+    api_base_url: Option<String>,
+    community_base_url: Option<String>,
+    partner_base_url: Option<String>,
+    store_base_url: Option<String>,
+    avatar_base_url: Option<String>,
+    avatar_cache_dir: Option<PathBuf>,
+    reqwest_client: Option<reqwest::Client>,
+    http: HttpConfig,
+    governor: Option<GovernorConfig>,
+    concurrency: Option<ConcurrencyConfig>,
+    rate_schedule: Option<RateSchedule>,
+    rate_coordinator: Option<Arc<dyn RateCoordinator>>,
+    on_retry: Option<RetryObserver>,
+    error_history: usize,
+    #[cfg(feature = "body_capture")]
+    capture_body: usize,
+    #[cfg(feature = "user_search")]
+    retry_on_empty: bool,
+    minimal_fields: bool,
+    language: Option<String>,
+    cancel: Option<CancellationToken>,
+    #[cfg(feature = "audit")]
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl Default for ClientBuilder {
@@ -59,7 +431,31 @@ impl ClientBuilder {
             retry_timeout: None,
             max_retries: None,
             api_keys: Vec::new(),
+            publisher_key: None,
             dont_retry: Vec::new(),
+            api_base_url: None,
+            community_base_url: None,
+            partner_base_url: None,
+            store_base_url: None,
+            avatar_base_url: None,
+            avatar_cache_dir: None,
+            reqwest_client: None,
+            http: HttpConfig::default(),
+            governor: None,
+            concurrency: None,
+            rate_schedule: None,
+            rate_coordinator: None,
+            on_retry: None,
+            error_history: 32,
+            #[cfg(feature = "body_capture")]
+            capture_body: 0,
+            #[cfg(feature = "user_search")]
+            retry_on_empty: false,
+            minimal_fields: false,
+            language: None,
+            cancel: None,
+            #[cfg(feature = "audit")]
+            audit_sink: None,
         }
     }
 
@@ -96,13 +492,255 @@ impl ClientBuilder {
         self.api_keys.extend(keys);
         self
     }
+    /// The publisher key used for [`Host::Partner`] endpoints, e.g. [`Client::get_app_builds`]
+    pub fn publisher_key(&mut self, key: String) -> &mut Self {
+        self.publisher_key = Some(key);
+        self
+    }
+
+    /// Send requests for [`Host::Api`] to `url` instead of [`Host::base_url`],
+    /// e.g. `http://localhost:8080` for a mock server or a proxy in front of the api.
+    pub fn api_base_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.api_base_url = Some(url.into());
+        self
+    }
+    /// Send requests for [`Host::Community`] to `url` instead of [`Host::base_url`].
+    ///
+    /// This is also used to get the session id when building the client.
+    pub fn community_base_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.community_base_url = Some(url.into());
+        self
+    }
+    /// Send requests for [`Host::Partner`] to `url` instead of [`Host::base_url`].
+    pub fn partner_base_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.partner_base_url = Some(url.into());
+        self
+    }
+    /// Send requests for [`Host::Store`] to `url` instead of [`Host::base_url`].
+    pub fn store_base_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.store_base_url = Some(url.into());
+        self
+    }
+    pub fn base_url(&mut self, host: Host, url: impl Into<String>) -> &mut Self {
+        match host {
+            Host::Api => self.api_base_url(url),
+            Host::Community => self.community_base_url(url),
+            Host::Partner => self.partner_base_url(url),
+            Host::Store => self.store_base_url(url),
+        }
+    }
+    /// Download avatars from `url` instead of [`AVATAR_BASE_URL`]
+    pub fn avatar_base_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.avatar_base_url = Some(url.into());
+        self
+    }
+    /// Keep downloaded avatars in `dir`, see [`Client::download_avatar`]
+    pub fn avatar_cache_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.avatar_cache_dir = Some(dir.into());
+        self
+    }
 
-    fn reqwest_client_with_cookies() -> Result<reqwest::Client> {
-        let builder = reqwest::Client::builder().cookie_provider(Arc::new(Jar::default()));
-        let client = builder.build().map_err(Error::ClientConfig)?;
-        Ok(client)
+    /// Use `client` for all requests instead of building a new one.
+    ///
+    /// Useful to point the client at a local mock server with custom TLS settings,
+    /// while keeping the retry and parsing logic of this crate.
+    ///
+    /// The community endpoints only accept requests that send the session id as a cookie,
+    /// so `client` should be built with a cookie store for those to work.
+    pub fn reqwest_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.reqwest_client = Some(client);
+        self
     }
-    async fn get_session_id(client: &reqwest::Client) -> Result<String> {
+
+    /// Close pooled connections that have been idle for `timeout`, reqwest defaults to 90 seconds.
+    ///
+    /// This and the other connection settings are ignored with [`ClientBuilder::reqwest_client`].
+    pub const fn pool_idle_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http.pool_idle_timeout = Some(timeout);
+        self
+    }
+    /// Keep at most `max` idle connections per host, by default there's no limit
+    pub const fn pool_max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.http.pool_max_idle_per_host = Some(max);
+        self
+    }
+    /// Send TCP keep-alive probes every `interval`
+    pub const fn tcp_keepalive(&mut self, interval: Duration) -> &mut Self {
+        self.http.tcp_keepalive = Some(interval);
+        self
+    }
+    /// Ping HTTP/2 connections every `interval`, also while they're idle, and close them if
+    /// a ping isn't answered within `timeout`, so long crawls keep their connections open
+    pub const fn http2_keep_alive(&mut self, interval: Duration, timeout: Duration) -> &mut Self {
+        self.http.http2_keep_alive_interval = Some(interval);
+        self.http.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+    /// Size the HTTP/2 flow control windows by the measured bandwidth,
+    /// which helps many concurrent requests multiplexed over one connection
+    pub const fn http2_adaptive_window(&mut self, adaptive: bool) -> &mut Self {
+        self.http.http2_adaptive_window = Some(adaptive);
+        self
+    }
+
+    /// Connect to `addrs` for `host` instead of resolving it, see [`crate::dns`].
+    ///
+    /// The port of the url replaces the port of `addrs`, unless that's `0`.
+    pub fn resolve(&mut self, host: impl Into<String>, addrs: Vec<SocketAddr>) -> &mut Self {
+        self.http.resolve.push((host.into(), addrs));
+        self
+    }
+    /// Resolve hosts with `resolver` instead of the system resolver,
+    /// e.g. a [`CachingResolver`](crate::dns::CachingResolver).
+    ///
+    /// Hosts pinned with [`ClientBuilder::resolve`] are not passed to it.
+    pub fn dns_resolver(&mut self, resolver: Arc<dyn Resolve>) -> &mut Self {
+        self.http.dns_resolver = Some(SharedResolver(resolver));
+        self
+    }
+
+    /// Only connect to, or first try, addresses of one IP version, see [`IpVersion`]
+    pub const fn ip_version(&mut self, version: IpVersion) -> &mut Self {
+        self.http.ip_version = Some(version);
+        self
+    }
+    /// Send requests from `addr`, call it again to take turns between several addresses,
+    /// e.g. to spread a crawl across the addresses of a host.
+    ///
+    /// The addresses need the same IP version as the addresses they connect to,
+    /// see [`ClientBuilder::ip_version`].
+    pub fn local_address(&mut self, addr: IpAddr) -> &mut Self {
+        self.http.local_addresses.push(addr);
+        self
+    }
+
+    /// Slow requests down when the error rate gets too high, see [`Governor`]
+    pub const fn governor(&mut self, config: GovernorConfig) -> &mut Self {
+        self.governor = Some(config);
+        self
+    }
+
+    /// Adjust how many requests to each endpoint are in flight at once to what Steam
+    /// tolerates, see [`ConcurrencyController`]
+    pub const fn adaptive_concurrency(&mut self, config: ConcurrencyConfig) -> &mut Self {
+        self.concurrency = Some(config);
+        self
+    }
+
+    /// Limit how many requests are sent per second, depending on the time of day
+    pub fn rate_schedule(&mut self, schedule: RateSchedule) -> &mut Self {
+        self.rate_schedule = Some(schedule);
+        self
+    }
+    /// Share the [`ClientBuilder::rate_schedule`] with other processes using the same key,
+    /// see [`crate::rate_limit`]
+    ///
+    /// [`ClientBuilder::build`] fails without a schedule. Slots the coordinator fails to
+    /// reserve are counted in [`ClientStats::coordinator_failures`].
+    pub fn rate_coordinator(&mut self, coordinator: Arc<dyn RateCoordinator>) -> &mut Self {
+        self.rate_coordinator = Some(coordinator);
+        self
+    }
+
+    /// Retry community requests that succeed but come back without results,
+    /// which the user search does transiently.
+    ///
+    /// The retries share the budget of [`ClientBuilder::retries`] with the retries of failed
+    /// attempts of the same request, and are counted in
+    /// [`Client::endpoint_retries`], every empty result in [`Client::empty_results`].
+    #[cfg(feature = "user_search")]
+    pub const fn retry_on_empty(&mut self, retry: bool) -> &mut Self {
+        self.retry_on_empty = retry;
+        self
+    }
+
+    /// Strip personal fields like the real name, country and avatar from
+    /// parsed models, before they're handed out.
+    ///
+    /// Applies to player summaries, user search results, mini profiles and rich presence,
+    /// profile pages and xml profiles, see [`PlayerSummary::minimize`](crate::api::PlayerSummary::minimize)
+    pub const fn minimal_fields(&mut self, minimal: bool) -> &mut Self {
+        self.minimal_fields = minimal;
+        self
+    }
+
+    /// The language community pages are requested in, a name Steam knows like `german`,
+    /// defaults to `english`.
+    ///
+    /// The html parsers don't depend on it, but the text they hand out does.
+    pub fn language(&mut self, language: impl Into<String>) -> &mut Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Stop the bulk requests of the client once `token` is cancelled, e.g. a token that's
+    /// cancelled when the service shuts down, see [`Client::shutdown`]
+    pub fn cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Call `observer` before every retry, e.g. to log or alert on failing requests.
+    ///
+    /// It's called from the task making the request, so it should return quickly.
+    pub fn on_retry(&mut self, observer: impl Fn(&RetryInfo) + Send + Sync + 'static) -> &mut Self {
+        self.on_retry = Some(Arc::new(observer));
+        self
+    }
+
+    /// How many failed attempts [`Client::last_errors`] keeps, defaults to `32`
+    pub const fn error_history(&mut self, len: usize) -> &mut Self {
+        self.error_history = len;
+        self
+    }
+
+    /// Keep up to `limit` bytes of json bodies that fail to parse in [`Client::last_errors`],
+    /// to find out what changed without capturing the traffic. Off by default.
+    ///
+    /// The api-keys, the publisher key and the session id are redacted. Only the body of
+    /// the last attempt is parsed, the ones of attempts that were retried aren't kept.
+    #[cfg(feature = "body_capture")]
+    pub const fn capture_body(&mut self, limit: usize) -> &mut Self {
+        self.capture_body = limit;
+        self
+    }
+
+    /// Record every request attempt, see [`crate::audit`]
+    #[cfg(feature = "audit")]
+    pub fn audit_sink(&mut self, sink: Arc<dyn AuditSink>) -> &mut Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    fn base_url_or_default(url: Option<&String>, host: Host) -> String {
+        url.map_or_else(
+            || host.base_url().to_string(),
+            |url| url.trim_end_matches('/').to_string(),
+        )
+    }
+
+    /// A client per local address, or a single one if none were set
+    fn reqwest_clients_with_cookies(&self) -> Result<Vec<reqwest::Client>> {
+        let jar = Arc::new(Jar::default());
+        let build = |local_address: Option<IpAddr>| {
+            let builder = reqwest::Client::builder()
+                .cookie_provider(jar.clone())
+                .local_address(local_address);
+            self.http
+                .apply(builder)
+                .build()
+                .map_err(Error::ClientConfig)
+        };
+        if self.http.local_addresses.is_empty() {
+            return Ok(vec![build(None)?]);
+        }
+        self.http
+            .local_addresses
+            .iter()
+            .map(|&addr| build(Some(addr)))
+            .collect()
+    }
+    async fn get_session_id(client: &reqwest::Client, community_base_url: &str) -> Result<String> {
         fn find_cookie(v: &HeaderValue) -> Option<&str> {
             let str = v.to_str().ok()?;
             str.strip_prefix(SESSION_ID_PREFIX)?
@@ -114,12 +752,9 @@ impl ClientBuilder {
         // sessionid=a0a0a0a0a0a0a0a0a0a0a0a0; Path=/; Secure; SameSite=None
         const SESSION_ID_PREFIX: &str = "sessionid=";
 
-        // Using the USER_SEARCH_API URL because it returns very little data
-        let resp = client
-            .get(USER_SEARCH_API)
-            .send()
-            .await
-            .map_err(Error::Request)?;
+        // Using the user search URL because it returns very little data
+        let url = format!("{}{}", community_base_url, Endpoint::UserSearch.path());
+        let resp = client.get(url).send().await.map_err(Error::Request)?;
 
         // We expect this status code to be returned
         if resp.status() != StatusCode::UNAUTHORIZED {
@@ -140,74 +775,876 @@ impl ClientBuilder {
         if self.api_keys.is_empty() {
             return Err(Error::ApiKey);
         }
+        if let Some(concurrency) = self.concurrency.as_ref() {
+            concurrency.validate()?;
+        }
+        if self.rate_coordinator.is_some() && self.rate_schedule.is_none() {
+            return Err(Error::RateSchedule);
+        }
 
-        let client = Self::reqwest_client_with_cookies()?;
-        let session_id = Self::get_session_id(&client).await?;
+        let community_base_url =
+            Self::base_url_or_default(self.community_base_url.as_ref(), Host::Community);
+        let clients = match self.reqwest_client.as_ref() {
+            Some(client) => vec![client.clone()],
+            None => self.reqwest_clients_with_cookies()?,
+        };
+        let session_id = Self::get_session_id(&clients[0], &community_base_url).await?;
+        Ok(self.build_with_session(clients, session_id))
+    }
+
+    /// Everything of [`ClientBuilder::build`] after the session id was fetched
+    fn build_with_session(&self, clients: Vec<reqwest::Client>, session_id: String) -> Client {
+        let api_base_url = Self::base_url_or_default(self.api_base_url.as_ref(), Host::Api);
+        let community_base_url =
+            Self::base_url_or_default(self.community_base_url.as_ref(), Host::Community);
+        let partner_base_url =
+            Self::base_url_or_default(self.partner_base_url.as_ref(), Host::Partner);
+        let store_base_url = Self::base_url_or_default(self.store_base_url.as_ref(), Host::Store);
 
         let mut dont_retry = self.dont_retry.clone();
         dont_retry.sort_unstable();
         dont_retry.dedup();
 
-        Ok(Client {
+        let inner = Inner {
             retry_timeout: self.retry_timeout.unwrap_or(Duration::from_millis(1000)),
             max_retries: self.max_retries.unwrap_or(3),
             dont_retry,
             session_id,
+            session_created: Instant::now(),
             api_keys: self.api_keys.clone(),
-            client,
-            total_retries: AtomicUsize::new(0),
-        })
+            publisher_key: self.publisher_key.clone(),
+            api_base_url,
+            community_base_url,
+            partner_base_url,
+            store_base_url,
+            avatar_base_url: self.avatar_base_url.as_ref().map_or_else(
+                || AVATAR_BASE_URL.to_string(),
+                |url| url.trim_end_matches('/').to_string(),
+            ),
+            avatar_cache_dir: self.avatar_cache_dir.clone(),
+            clients,
+            next_client: AtomicUsize::new(0),
+            errors: Mutex::new(VecDeque::with_capacity(self.error_history)),
+            error_history: self.error_history,
+            #[cfg(feature = "body_capture")]
+            capture_body: self.capture_body,
+            on_retry: self.on_retry.clone(),
+            #[cfg(feature = "user_search")]
+            retry_on_empty: self.retry_on_empty,
+            governor: self.governor.clone().map(Governor::new),
+            concurrency: self.concurrency.clone().map(ConcurrencyController::new),
+            rate_limiter: self.rate_schedule.clone().map(|schedule| {
+                match self.rate_coordinator.clone() {
+                    Some(coordinator) => RateLimiter::with_coordinator(schedule, coordinator),
+                    None => RateLimiter::new(schedule),
+                }
+            }),
+            minimal_fields: self.minimal_fields,
+            language: self
+                .language
+                .clone()
+                .unwrap_or_else(|| "english".to_string()),
+            cancel: self.cancel.clone().unwrap_or_default(),
+            #[cfg(feature = "audit")]
+            audit_sink: self.audit_sink.clone(),
+            #[cfg(feature = "schema_drift")]
+            schema_drift: SchemaDrift::default(),
+            stats: ClientStats::default(),
+        };
+        Client {
+            inner: Arc::new(inner),
+            stats: ClientStats::default(),
+            tenant: None,
+        }
+    }
+}
+
+impl Clone for Client {
+    /// Shares everything with `self` except the counters of [`Client::stats`]
+    fn clone(&self) -> Self {
+        Client {
+            inner: self.inner.clone(),
+            stats: ClientStats::default(),
+            tenant: self.tenant.clone(),
+        }
     }
 }
 
 impl Client {
-    pub async fn get_json<T>(&self, url: &str, query: &[(&str, &str)]) -> reqwest::Result<T>
+    /// Make a `GET` request to `endpoint` and parse the response as json.
+    ///
+    /// Failed requests are retried according to the client configuration
+    /// and counted towards the retries of the endpoint.
+    ///
+    /// The request and the parsing are boxed, so the future of every method built on this
+    /// stays small, bulk methods hold many of them at once.
+    pub async fn get_json<T>(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, &str)],
+    ) -> reqwest::Result<T>
     where
         T: DeserializeOwned,
     {
+        let mut budget = self.inner.max_retries;
+        self.get_json_with_budget(endpoint, query, &mut budget)
+            .await
+    }
+    /// [`Client::get_json`] taking its retries from `budget`, for callers that retry
+    /// on their own too and share the budget with the http retries
+    pub(crate) async fn get_json_with_budget<T>(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, &str)],
+        budget: &mut usize,
+    ) -> reqwest::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.endpoint_url(endpoint);
+        let resp = Box::pin(
+            self.send_with_budget(endpoint, budget, || self.http().get(&url).query(query)),
+        )
+        .await?;
+        #[cfg(feature = "schema_drift")]
+        let resp = Box::pin(self.scan_schema::<T>(endpoint, resp)).await?;
+        Box::pin(self.parse_json(endpoint, resp)).await
+    }
+    /// Make a `POST` request with a form body to `endpoint` and parse the response as json.
+    ///
+    /// Retried like [`Client::get_json`].
+    pub async fn post_form_json<T>(
+        &self,
+        endpoint: Endpoint,
+        form: &[(&str, &str)],
+    ) -> reqwest::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.endpoint_url(endpoint);
+        let resp = Box::pin(self.send_with_retries(endpoint, || self.http().post(&url).form(form)))
+            .await?;
+        #[cfg(feature = "schema_drift")]
+        let resp = Box::pin(self.scan_schema::<T>(endpoint, resp)).await?;
+        Box::pin(self.parse_json(endpoint, resp)).await
+    }
+    /// Make a `POST` request with a form body to `endpoint` and parse the response as json,
+    /// without retrying it.
+    ///
+    /// For requests that change something, like granting an item. When the request fails
+    /// Steam may have applied it anyway, the [`reqwest::Error`] tells whether there was
+    /// a response, so the caller can check before sending it again.
+    pub async fn post_form_json_once<T>(
+        &self,
+        endpoint: Endpoint,
+        form: &[(&str, &str)],
+    ) -> reqwest::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.endpoint_url(endpoint);
+        let resp = Box::pin(self.send_once(endpoint, || self.http().post(&url).form(form))).await?;
+        #[cfg(feature = "schema_drift")]
+        let resp = Box::pin(self.scan_schema::<T>(endpoint, resp)).await?;
+        Box::pin(self.parse_json(endpoint, resp)).await
+    }
+    /// Parse the body of `resp` as json, the start of the body is kept in the
+    /// error history if that fails and [`ClientBuilder::capture_body`] is set
+    async fn parse_json<T>(&self, endpoint: Endpoint, resp: reqwest::Response) -> reqwest::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        #[cfg(feature = "body_capture")]
+        if self.inner.capture_body > 0 {
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?;
+            if let Ok(parsed) = serde_json::from_slice(&body) {
+                return Ok(parsed);
+            }
+            let captured = self.redacted_body(&body);
+
+            // parse again to hand back the same error as without capturing
+            let mut rebuilt = http::Response::new(body);
+            *rebuilt.status_mut() = status;
+            *rebuilt.headers_mut() = headers;
+            let result = reqwest::Response::from(rebuilt).json().await;
+            if let Err(err) = &result {
+                self.push_error(RecordedError {
+                    at: Utc::now(),
+                    endpoint,
+                    status: Some(status.as_u16()),
+                    message: err.to_string(),
+                    body: Some(captured),
+                });
+            }
+            return result;
+        }
+        #[cfg(not(feature = "body_capture"))]
+        let _ = endpoint;
+        resp.json().await
+    }
+    /// The start of `body` without the secrets of the client
+    #[cfg(feature = "body_capture")]
+    fn redacted_body(&self, body: &[u8]) -> String {
+        let mut text = String::from_utf8_lossy(body).into_owned();
+        let secrets = self
+            .inner
+            .api_keys
+            .iter()
+            .chain(&self.inner.publisher_key)
+            .chain(std::iter::once(&self.inner.session_id))
+            .filter(|secret| !secret.is_empty());
+        for secret in secrets {
+            text = text.replace(secret.as_str(), "[redacted]");
+        }
+        if text.len() > self.inner.capture_body {
+            let mut end = self.inner.capture_body;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            text.truncate(end);
+        }
+        text
+    }
+    /// Record the fields of the response `T` doesn't know about,
+    /// the response handed back is rebuilt like in [`Client::send`]
+    #[cfg(feature = "schema_drift")]
+    async fn scan_schema<T>(
+        &self,
+        endpoint: Endpoint,
+        resp: reqwest::Response,
+    ) -> reqwest::Result<reqwest::Response>
+    where
+        T: DeserializeOwned,
+    {
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?;
+        self.inner.schema_drift.scan::<T>(endpoint, &body);
+
+        let mut rebuilt = http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.headers_mut() = headers;
+        Ok(reqwest::Response::from(rebuilt))
+    }
+    /// Make a `GET` request to `endpoint` with `sub_path` appended and return the body as text.
+    ///
+    /// For endpoints whose url contains a parameter, e.g. `/groups/{name}/rss/`,
+    /// parameters from callers go through [`encode_path_segment`].
+    pub async fn get_text(
+        &self,
+        endpoint: Endpoint,
+        sub_path: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Result<String> {
+        let url = format!("{}{}", self.endpoint_url(endpoint), sub_path);
+        let resp =
+            Box::pin(self.send_with_retries(endpoint, || self.http().get(&url).query(query)))
+                .await?;
+        resp.text().await
+    }
+    /// Make a `POST` request with a form body to `endpoint` and return the body as text,
+    /// without retrying it, see [`Client::post_form_json_once`]
+    #[cfg(feature = "openid")]
+    pub(crate) async fn post_form_text_once(
+        &self,
+        endpoint: Endpoint,
+        form: &[(&str, &str)],
+    ) -> reqwest::Result<String> {
+        let url = self.endpoint_url(endpoint);
+        let resp = Box::pin(self.send_once(endpoint, || self.http().post(&url).form(form))).await?;
+        resp.text().await
+    }
+    /// Make a request to `endpoint` with `query`, as a form for `POST` endpoints,
+    /// and hand back the response for endpoints that don't answer with text
+    #[cfg(feature = "protobuf")]
+    pub(crate) async fn get_response(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, &str)],
+    ) -> reqwest::Result<reqwest::Response> {
+        let url = self.endpoint_url(endpoint);
+        Box::pin(
+            self.send_with_retries(endpoint, || match endpoint.method() {
+                "POST" => self.http().post(&url).form(query),
+                _ => self.http().get(&url).query(query),
+            }),
+        )
+        .await
+    }
+    #[cfg(not(feature = "audit"))]
+    async fn send(
+        &self,
+        _endpoint: Endpoint,
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+    }
+    /// Send the request and pass a record of it to the audit sink, if there is one.
+    ///
+    /// The body of successful responses is read to digest it, so the response
+    /// handed back is rebuilt from the status, headers and body.
+    #[cfg(feature = "audit")]
+    async fn send(
+        &self,
+        endpoint: Endpoint,
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        let Some(sink) = self.inner.audit_sink.as_deref() else {
+            return request
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+        };
+
+        let (client, request) = request.build_split();
+        let request = request?;
+        let mut record = AuditRecord {
+            at: chrono::Utc::now(),
+            endpoint,
+            query_hash: audit::query_hash(&request),
+            status: None,
+            response_digest: None,
+        };
+        let result = client
+            .execute(request)
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        let result = match result {
+            Ok(resp) => {
+                let status = resp.status();
+                let headers = resp.headers().clone();
+                record.status = Some(status.as_u16());
+                resp.bytes().await.map(|body| {
+                    record.response_digest = Some(audit::digest(&body));
+                    let mut rebuilt = http::Response::new(body);
+                    *rebuilt.status_mut() = status;
+                    *rebuilt.headers_mut() = headers;
+                    reqwest::Response::from(rebuilt)
+                })
+            }
+            Err(err) => {
+                record.status = err.status().map(|status| status.as_u16());
+                Err(err)
+            }
+        };
+
+        // a broken sink shouldn't break requests, failures are counted instead
+        if sink.record(&record).is_err() {
+            self.stats.audit_failures.fetch_add(1, Ordering::SeqCst);
+            self.inner
+                .stats
+                .audit_failures
+                .fetch_add(1, Ordering::SeqCst);
+        }
+        result
+    }
+    /// Send the request built by `request` until it succeeds or the retries are used up
+    async fn send_with_retries(
+        &self,
+        endpoint: Endpoint,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        let mut budget = self.inner.max_retries;
+        self.send_with_budget(endpoint, &mut budget, request).await
+    }
+    /// Send the request built by `request` a single time, for requests that change something
+    /// on Steam's side and would be applied twice if a response got lost and they were retried
+    async fn send_once(
+        &self,
+        endpoint: Endpoint,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        self.send_with_budget(endpoint, &mut 0, request).await
+    }
+    /// Send the request built by `request` until it succeeds or `budget` is used up,
+    /// every retry takes one from it
+    async fn send_with_budget(
+        &self,
+        endpoint: Endpoint,
+        budget: &mut usize,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
         let mut retries = 0_usize;
         let result = loop {
-            let err = match self.client.get(url).query(query).send().await {
-                Ok(resp) => match resp.error_for_status() {
-                    Ok(resp) => break Ok(resp.json().await?),
-                    Err(err) => err,
-                },
+            if let Some(tenant) = self.tenant.as_deref() {
+                tenant.acquire().await;
+            }
+            if let Some(rate_limiter) = self.inner.rate_limiter.as_ref() {
+                // the slot is taken from this process instead, failures are counted
+                if rate_limiter.acquire().await.is_err() {
+                    self.stats
+                        .coordinator_failures
+                        .fetch_add(1, Ordering::SeqCst);
+                    self.inner
+                        .stats
+                        .coordinator_failures
+                        .fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            if let Some(governor) = self.inner.governor.as_ref() {
+                let delay = governor.delay();
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+            let permit = match self.inner.concurrency.as_ref() {
+                Some(concurrency) => Some(concurrency.acquire(endpoint).await),
+                None => None,
+            };
+            let started = Instant::now();
+            let result = self
+                .send(endpoint, self.localize(endpoint, request()))
+                .await;
+            let latency = started.elapsed();
+            self.record_latency(endpoint, latency);
+            let status = result
+                .as_ref()
+                .map_or_else(reqwest::Error::status, |resp| Some(resp.status()));
+            if let Some(permit) = permit {
+                permit.record(status, latency);
+            }
+            if let Some(governor) = self.inner.governor.as_ref() {
+                governor.record(!Governor::is_failure(status));
+            }
+            self.record_status(status);
+            let err = match result {
+                Ok(resp) => break Ok(resp),
                 Err(err) => err,
             };
-            if retries == self.max_retries {
+            self.record_error(endpoint, &err);
+            if *budget == 0 {
                 break Err(err);
             }
             if let Some(status) = err.status() {
-                if self.dont_retry.contains(&status) {
+                if self.inner.dont_retry.contains(&status) {
                     break Err(err);
                 }
             }
+            *budget -= 1;
             retries += 1;
-            tokio::time::sleep(self.retry_timeout).await;
+            self.notify_retry(endpoint, self.inner.max_retries - *budget, err.status());
+            tokio::time::sleep(self.inner.retry_timeout).await;
         };
         if retries > 0 {
-            self.total_retries.fetch_add(retries, Ordering::SeqCst);
+            self.add_retries(endpoint, retries);
         }
         result
     }
+    /// Ask for community pages in [`Client::language`], their text depends on it
+    fn localize(
+        &self,
+        endpoint: Endpoint,
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::RequestBuilder {
+        if endpoint.host() != Host::Community {
+            return request;
+        }
+        let request = request.query(&[("l", self.language())]);
+        match accept_language(self.language()) {
+            Some(code) => request.header(ACCEPT_LANGUAGE, code),
+            None => request,
+        }
+    }
+    /// The base url requests to `host` are sent to
+    pub fn base_url(&self, host: Host) -> &str {
+        match host {
+            Host::Api => self.inner.api_base_url.as_str(),
+            Host::Community => self.inner.community_base_url.as_str(),
+            Host::Partner => self.inner.partner_base_url.as_str(),
+            Host::Store => self.inner.store_base_url.as_str(),
+        }
+    }
+    /// The url requests to `endpoint` are sent to, taking base url overrides into account
+    pub fn endpoint_url(&self, endpoint: Endpoint) -> String {
+        format!("{}{}", self.base_url(endpoint.host()), endpoint.path())
+    }
+    /// The base url avatars are downloaded from
+    pub fn avatar_base_url(&self) -> &str {
+        self.inner.avatar_base_url.as_str()
+    }
+    /// The directory downloaded avatars are kept in, if any
+    pub fn avatar_cache_dir(&self) -> Option<&Path> {
+        self.inner.avatar_cache_dir.as_deref()
+    }
     pub fn api_key(&self) -> &str {
-        self.api_keys[0].as_str()
+        self.inner.api_keys[0].as_str()
+    }
+    /// The publisher key, if one was set with [`ClientBuilder::publisher_key`]
+    pub fn publisher_key(&self) -> Option<&str> {
+        self.inner.publisher_key.as_deref()
     }
     pub fn session_id(&self) -> &str {
-        self.session_id.as_str()
+        self.inner.session_id.as_str()
+    }
+    /// Check whether the community still accepts the session id with a single search request,
+    /// so a service can build a new client before a burst of requests fails.
+    ///
+    /// Not retried and not counted in the stats, errors are only returned if there was
+    /// no response at all.
+    pub async fn validate_session(&self) -> reqwest::Result<SessionStatus> {
+        #[derive(Deserialize)]
+        struct Probe {
+            success: i32,
+        }
+
+        let query = [
+            ("filter", "users"),
+            ("text", "steam"),
+            ("sessionid", self.session_id()),
+            ("page", "1"),
+        ];
+        let resp = self
+            .http()
+            .get(self.endpoint_url(Endpoint::UserSearch))
+            .query(&query)
+            .send()
+            .await?;
+        let status = resp.status();
+        // the body is only json while the session is accepted
+        let valid = status.is_success()
+            && resp
+                .json::<Probe>()
+                .await
+                .is_ok_and(|probe| probe.success == 1);
+        Ok(SessionStatus {
+            valid,
+            age: self.inner.session_created.elapsed(),
+            status,
+        })
+    }
+    /// The governor, if one was configured with [`ClientBuilder::governor`]
+    pub fn governor(&self) -> Option<&Governor> {
+        self.inner.governor.as_ref()
+    }
+    /// The concurrency controller, if one was configured with
+    /// [`ClientBuilder::adaptive_concurrency`]
+    pub fn concurrency(&self) -> Option<&ConcurrencyController> {
+        self.inner.concurrency.as_ref()
     }
+    /// The rate limiter, if a schedule was set with [`ClientBuilder::rate_schedule`]
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.rate_limiter.as_ref()
+    }
+    /// Retries of this handle, see [`Client::stats`]
     pub fn total_retries(&self) -> usize {
-        self.total_retries.load(Ordering::SeqCst)
+        self.stats.total_retries()
     }
+    /// Retries of requests made to `endpoint` by this handle
+    pub fn endpoint_retries(&self, endpoint: Endpoint) -> usize {
+        self.stats.endpoint_retries(endpoint)
+    }
+    /// Reset the counters of this handle
     pub fn reset_total_retries(&self) {
-        self.total_retries.store(0, Ordering::SeqCst);
+        self.stats.reset();
+    }
+    /// Responses that succeeded but were empty, see [`ClientBuilder::retry_on_empty`]
+    pub fn empty_results(&self) -> usize {
+        self.stats.empty_results()
+    }
+    /// The counters of this handle, clones and [scoped](Client::scoped) clients start at zero
+    pub const fn stats(&self) -> &ClientStats {
+        &self.stats
+    }
+    /// The counters of this client and every clone of it
+    pub fn global_stats(&self) -> &ClientStats {
+        &self.inner.stats
+    }
+    /// Pass the `retries`th retry of a request to the observer, if there is one
+    fn notify_retry(&self, endpoint: Endpoint, retries: usize, status: Option<StatusCode>) {
+        if let Some(observer) = self.inner.on_retry.as_deref() {
+            observer(&RetryInfo {
+                endpoint,
+                attempt: retries + 1,
+                status,
+                delay: self.inner.retry_timeout,
+            });
+        }
+    }
+    fn record_status(&self, status: Option<StatusCode>) {
+        self.stats.add_status(status);
+        self.inner.stats.add_status(status);
+    }
+    fn record_latency(&self, endpoint: Endpoint, latency: Duration) {
+        self.stats.add_latency(endpoint.rate_class(), latency);
+        self.inner.stats.add_latency(endpoint.rate_class(), latency);
+    }
+    fn record_error(&self, endpoint: Endpoint, err: &reqwest::Error) {
+        self.push_error(RecordedError {
+            at: Utc::now(),
+            endpoint,
+            status: err.status().map(|status| status.as_u16()),
+            message: err.to_string(),
+            #[cfg(feature = "body_capture")]
+            body: None,
+        });
+    }
+    fn push_error(&self, error: RecordedError) {
+        if self.inner.error_history == 0 {
+            return;
+        }
+        let mut errors = self
+            .inner
+            .errors
+            .lock()
+            .expect("error history lock poisoned");
+        if errors.len() == self.inner.error_history {
+            errors.pop_front();
+        }
+        errors.push_back(error);
+    }
+    /// Up to `n` of the most recent failed attempts of this client and its clones, newest first.
+    ///
+    /// Every attempt counts, including the ones that were retried.
+    /// At most [`ClientBuilder::error_history`] are kept.
+    pub fn last_errors(&self, n: usize) -> Vec<RecordedError> {
+        let errors = self
+            .inner
+            .errors
+            .lock()
+            .expect("error history lock poisoned");
+        errors.iter().rev().take(n).cloned().collect()
+    }
+    fn add_retries(&self, endpoint: Endpoint, retries: usize) {
+        self.stats.add_retries(endpoint, retries);
+        self.inner.stats.add_retries(endpoint, retries);
+    }
+    /// The retries a request may use, see [`Client::get_json_with_budget`]
+    #[cfg(feature = "user_search")]
+    pub(crate) fn retry_budget(&self) -> usize {
+        self.inner.max_retries
+    }
+    /// Count an empty response to `endpoint` and decide whether to send the request again,
+    /// taking the retry from `budget` and waiting the retry timeout if so
+    #[cfg(feature = "user_search")]
+    pub(crate) async fn retry_empty(&self, endpoint: Endpoint, budget: &mut usize) -> bool {
+        self.stats.empty_results.fetch_add(1, Ordering::SeqCst);
+        self.inner
+            .stats
+            .empty_results
+            .fetch_add(1, Ordering::SeqCst);
+        if !self.inner.retry_on_empty || *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+        self.add_retries(endpoint, 1);
+        self.notify_retry(
+            endpoint,
+            self.inner.max_retries - *budget,
+            Some(StatusCode::OK),
+        );
+        tokio::time::sleep(self.inner.retry_timeout).await;
+        true
+    }
+    /// Records the audit sink failed to write
+    #[cfg(feature = "audit")]
+    pub fn audit_failures(&self) -> usize {
+        self.stats.audit_failures()
+    }
+    /// Slots the rate coordinator failed to reserve, see [`ClientStats::coordinator_failures`]
+    pub fn coordinator_failures(&self) -> usize {
+        self.stats.coordinator_failures()
+    }
+    /// The fields Steam sent that the models don't know about, see [`crate::schema_drift`]
+    #[cfg(feature = "schema_drift")]
+    pub fn schema_drift(&self) -> &SchemaDrift {
+        &self.inner.schema_drift
+    }
+    /// Whether personal fields are stripped, see [`ClientBuilder::minimal_fields`]
+    pub fn minimal_fields(&self) -> bool {
+        self.inner.minimal_fields
+    }
+    /// The language community pages are requested in, see [`ClientBuilder::language`]
+    pub fn language(&self) -> &str {
+        &self.inner.language
+    }
+    /// Stop the bulk requests of this client and every clone of it, e.g.
+    /// [`Client::get_profiles_with_bans`]. They return a `Cancelled` error and drop the
+    /// requests in flight, single requests aren't affected.
+    ///
+    /// Cancels the token of [`ClientBuilder::cancellation_token`], bulk requests started
+    /// afterwards stop right away.
+    pub fn shutdown(&self) {
+        self.inner.cancel.cancel();
+    }
+    /// Whether [`Client::shutdown`] was called or the token of
+    /// [`ClientBuilder::cancellation_token`] was cancelled
+    pub fn is_shut_down(&self) -> bool {
+        self.inner.cancel.is_cancelled()
+    }
+    /// Run `future` unless the client is [shut down](Client::shutdown) first,
+    /// every bulk request goes through this
+    pub(crate) async fn until_shutdown<F: Future>(&self, future: F) -> Option<F::Output> {
+        self.inner.cancel.run_until_cancelled(future).await
+    }
+    /// The tenant, if this client was created with [`Client::scoped`]
+    pub fn tenant(&self) -> Option<&Tenant> {
+        self.tenant.as_deref()
+    }
+    /// A client for one tenant of a service, limited to `quota` requests.
+    ///
+    /// It shares the connection pool, session, governor, concurrency controller and
+    /// rate limiter with `self`, but has its own retry counters.
+    /// Requests over the quota wait, see [`crate::tenant`].
+    pub fn scoped(&self, tenant_id: impl Into<String>, quota: Quota) -> Client {
+        Client {
+            inner: self.inner.clone(),
+            stats: ClientStats::default(),
+            tenant: Some(Arc::new(Tenant::new(tenant_id, quota))),
+        }
     }
     /// Clone the inner [`reqwest::Client`], which is just a call to `Arc::clone`
     /// to share the connection pool with other program parts that need one.
     pub fn clone_client(&self) -> reqwest::Client {
-        self.client.clone()
+        self.inner.clients[0].clone()
+    }
+    /// The client the next request is sent with, taking turns if there are
+    /// [several local addresses](ClientBuilder::local_address)
+    fn http(&self) -> &reqwest::Client {
+        let clients = &self.inner.clients;
+        if clients.len() == 1 {
+            return &clients[0];
+        }
+        &clients[self.inner.next_client.fetch_add(1, Ordering::Relaxed) % clients.len()]
     }
     pub fn builder() -> ClientBuilder {
         ClientBuilder::new()
     }
 }
+
+/// Percent-encode `segment` so it stays a single path segment of a url, `/`, `?`, `#`
+/// and `..` can't change which page is requested
+///
+/// Urls can't have a segment of only dots, even encoded ones are removed when the url is
+/// parsed, so `.` and `..` are requested as the literal text `%2E` and `%2E%2E`.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let segment = match segment {
+        "." => "%2E",
+        ".." => "%2E%2E",
+        segment => segment,
+    };
+    let mut url = reqwest::Url::parse("http://localhost/").expect("valid url");
+    url.path_segments_mut()
+        .expect("url has a path")
+        .pop_if_empty()
+        .push(segment);
+    url.path()[1..].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{encode_path_segment, Client, LatencySummary};
+    use crate::{Endpoint, SteamId};
+
+    /// A client that never made a request, the session id isn't fetched
+    fn offline_client() -> Client {
+        let mut builder = Client::builder();
+        builder.api_key("0".repeat(32));
+        let clients = vec![reqwest::Client::new()];
+        builder.build_with_session(clients, "0".repeat(24))
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_concurrency() {
+        use crate::concurrency::{ConcurrencyConfig, ConcurrencyConfigError};
+
+        let mut builder = Client::builder();
+        builder
+            .api_key("0".repeat(32))
+            .adaptive_concurrency(ConcurrencyConfig {
+                min: 8,
+                max: 4,
+                ..ConcurrencyConfig::default()
+            });
+        // checked before the session id is requested, so no server is needed
+        let Err(super::Error::Concurrency(err)) = builder.build().await else {
+            panic!("contradicting limits were accepted");
+        };
+        assert_eq!(err, ConcurrencyConfigError::MinAboveMax { min: 8, max: 4 });
+    }
+
+    #[tokio::test]
+    async fn rejects_coordinator_without_schedule() {
+        use std::sync::Arc;
+
+        use crate::rate_limit::FileCoordinator;
+
+        let mut builder = Client::builder();
+        builder
+            .api_key("0".repeat(32))
+            .rate_coordinator(Arc::new(FileCoordinator::new("rate")));
+        assert!(matches!(
+            builder.build().await,
+            Err(super::Error::RateSchedule)
+        ));
+    }
+
+    #[test]
+    fn futures_stay_small() {
+        // executors keep a future on the stack or in a task, either way a fan-out
+        // over many chunks shouldn't multiply kilobytes of reqwest state
+        const MAX_FUTURE_SIZE: usize = 1024;
+
+        let client = offline_client();
+        let ids = [SteamId(76561198230177976)];
+        let snapshot: crate::api::PlayerBans = serde_json::from_str("{}").unwrap();
+
+        let sizes = [
+            std::mem::size_of_val(&client.get_player_summaries(ids.as_slice().into())),
+            std::mem::size_of_val(&client.get_player_bans(ids.as_slice().into())),
+            std::mem::size_of_val(&client.get_profiles_with_bans(&ids)),
+            std::mem::size_of_val(&client.get_new_bans_since(&ids, &snapshot)),
+            std::mem::size_of_val(&client.get_text(Endpoint::ProfilePage, "", &[])),
+        ];
+        assert!(
+            sizes.iter().all(|&size| size <= MAX_FUTURE_SIZE),
+            "future sizes {sizes:?} exceed {MAX_FUTURE_SIZE} bytes"
+        );
+    }
+
+    #[test]
+    fn encodes_path_segments() {
+        assert_eq!(
+            encode_path_segment("AK-47 | Redline (Field-Tested)"),
+            "AK-47%20|%20Redline%20(Field-Tested)"
+        );
+        assert_eq!(encode_path_segment("1/2"), "1%2F2");
+        assert_eq!(encode_path_segment("a?b#c"), "a%3Fb%23c");
+        assert_eq!(encode_path_segment("%2F"), "%252F");
+        for (segment, encoded) in [(".", "%252E"), ("..", "%252E%252E"), ("../..", "..%2F..")] {
+            let url = format!(
+                "https://steamcommunity.com/groups/{}/rss/",
+                encode_path_segment(segment)
+            );
+            assert_eq!(
+                reqwest::Url::parse(&url).unwrap().path(),
+                format!("/groups/{}/rss/", encoded)
+            );
+        }
+        assert_eq!(encode_path_segment("steamuniverse"), "steamuniverse");
+    }
+
+    #[test]
+    fn summarizes_latencies() {
+        assert_eq!(LatencySummary::from_samples([]), None);
+
+        let summary = LatencySummary::from_samples((1..=100).rev().map(Duration::from_millis));
+        assert_eq!(
+            summary,
+            Some(LatencySummary {
+                samples: 100,
+                p50: Duration::from_millis(50),
+                p95: Duration::from_millis(95),
+                max: Duration::from_millis(100),
+            })
+        );
+        let single = LatencySummary::from_samples([Duration::from_millis(7)]).unwrap();
+        assert_eq!((single.p50, single.p95), (single.max, single.max));
+    }
+}
@@ -0,0 +1,206 @@
+use std::marker::PhantomData;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "audit")]
+use crate::audit::AuditSink;
+use crate::concurrency::ConcurrencyConfig;
+use crate::dns::{IpVersion, Resolve};
+use crate::governor::GovernorConfig;
+use crate::rate_limit::{RateCoordinator, RateSchedule};
+use crate::{Client, ClientBuilder, Error, Host, RetryInfo};
+
+/// [`ClientOptions`] without an api-key yet
+#[derive(Debug, Clone, Copy)]
+pub struct MissingKey;
+
+/// [`ClientOptions`] with at least one api-key
+#[derive(Debug, Clone, Copy)]
+pub struct WithKey;
+
+/// A by-value variant of [`ClientBuilder`] that can be built in one expression
+///
+/// [`ClientOptions::build`] only exists once an api-key was set,
+/// so forgetting it is a compile error instead of [`Error::ApiKey`].
+///
+/// ```no_run
+/// # async fn run() -> Result<(), steam_api_concurrent::Error> {
+/// use steam_api_concurrent::Client;
+///
+/// let client = Client::options()
+///     .retries(5)
+///     .api_key("key".to_string())
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # async fn run() {
+/// use steam_api_concurrent::Client;
+///
+/// let client = Client::options().retries(5).build().await;
+/// # }
+/// ```
+pub struct ClientOptions<K = MissingKey> {
+    builder: Box<ClientBuilder>,
+    key: PhantomData<K>,
+}
+
+/// Methods that take and return the options by value and forward to [`ClientBuilder`]
+macro_rules! by_value {
+    ($($(#[$attr:meta])* $name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("See [`ClientBuilder::", stringify!($name), "`]")]
+            #[must_use]
+            pub fn $name(mut self, $($arg: $ty),*) -> Self {
+                self.builder.$name($($arg),*);
+                self
+            }
+        )*
+    };
+}
+
+impl<K> ClientOptions<K> {
+    by_value! {
+        retries(retries: usize);
+        retry_timeout(dur: Duration);
+        retry_timeout_ms(ms: u64);
+        dont_retry(code: StatusCode);
+        dont_retries(codes: Vec<StatusCode>);
+        dont_retry_unauthorized();
+        publisher_key(key: String);
+        api_base_url(url: impl Into<String>);
+        community_base_url(url: impl Into<String>);
+        partner_base_url(url: impl Into<String>);
+        store_base_url(url: impl Into<String>);
+        base_url(host: Host, url: impl Into<String>);
+        avatar_base_url(url: impl Into<String>);
+        avatar_cache_dir(dir: impl Into<PathBuf>);
+        reqwest_client(client: reqwest::Client);
+        pool_idle_timeout(timeout: Duration);
+        pool_max_idle_per_host(max: usize);
+        tcp_keepalive(interval: Duration);
+        http2_keep_alive(interval: Duration, timeout: Duration);
+        http2_adaptive_window(adaptive: bool);
+        resolve(host: impl Into<String>, addrs: Vec<SocketAddr>);
+        dns_resolver(resolver: Arc<dyn Resolve>);
+        ip_version(version: IpVersion);
+        local_address(addr: IpAddr);
+        governor(config: GovernorConfig);
+        adaptive_concurrency(config: ConcurrencyConfig);
+        rate_schedule(schedule: RateSchedule);
+        rate_coordinator(coordinator: Arc<dyn RateCoordinator>);
+        #[cfg(feature = "user_search")]
+        retry_on_empty(retry: bool);
+        minimal_fields(minimal: bool);
+        language(language: impl Into<String>);
+        cancellation_token(token: CancellationToken);
+        error_history(len: usize);
+        #[cfg(feature = "body_capture")]
+        capture_body(limit: usize);
+        on_retry(observer: impl Fn(&RetryInfo) + Send + Sync + 'static);
+        #[cfg(feature = "audit")]
+        audit_sink(sink: Arc<dyn AuditSink>);
+    }
+
+    /// See [`ClientBuilder::api_key`]
+    #[must_use]
+    pub fn api_key(mut self, key: String) -> ClientOptions<WithKey> {
+        self.builder.api_key(key);
+        ClientOptions {
+            builder: self.builder,
+            key: PhantomData,
+        }
+    }
+    /// See [`ClientBuilder::api_keys`]
+    ///
+    /// An empty `keys` still fails with [`Error::ApiKey`] when building.
+    #[must_use]
+    pub fn api_keys(mut self, keys: Vec<String>) -> ClientOptions<WithKey> {
+        self.builder.api_keys(keys);
+        ClientOptions {
+            builder: self.builder,
+            key: PhantomData,
+        }
+    }
+    /// The [`ClientBuilder`] with the options set so far
+    pub fn into_builder(self) -> ClientBuilder {
+        *self.builder
+    }
+}
+
+impl ClientOptions<MissingKey> {
+    pub fn new() -> Self {
+        Self {
+            builder: Box::default(),
+            key: PhantomData,
+        }
+    }
+}
+
+impl Default for ClientOptions<MissingKey> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientOptions<WithKey> {
+    /// See [`ClientBuilder::build`]
+    pub async fn build(self) -> Result<Client, Error> {
+        self.builder.build().await
+    }
+}
+
+impl Client {
+    /// Options to build a client with in one expression, see [`ClientOptions`]
+    pub fn options() -> ClientOptions {
+        ClientOptions::new()
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::testing::MockSteam;
+    use crate::{Client, Host};
+
+    #[tokio::test]
+    async fn builds_in_one_expression() {
+        let steam = MockSteam::start().await;
+        let client = Client::options()
+            .retries(1)
+            .base_url(Host::Api, steam.uri())
+            .base_url(Host::Community, steam.uri())
+            .api_key("key".to_string())
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client.api_key(), "key");
+        assert_eq!(client.base_url(Host::Api), steam.uri());
+    }
+
+    #[tokio::test]
+    async fn builds_with_connection_settings() {
+        let steam = MockSteam::start().await;
+        let client = Client::options()
+            .base_url(Host::Community, steam.uri())
+            .pool_idle_timeout(Duration::from_secs(300))
+            .pool_max_idle_per_host(100)
+            .tcp_keepalive(Duration::from_secs(60))
+            .http2_keep_alive(Duration::from_secs(30), Duration::from_secs(10))
+            .http2_adaptive_window(true)
+            .api_key("key".to_string())
+            .build()
+            .await;
+        assert!(client.is_ok());
+    }
+}
@@ -0,0 +1,403 @@
+//! Find out how many concurrent requests Steam tolerates, instead of guessing a fixed limit.
+//!
+//! The [`ConcurrencyController`] keeps a limit of requests in flight per endpoint and adjusts
+//! it AIMD-style, like TCP congestion control: every [`ConcurrencyConfig::increase_after`]
+//! successes faster than the latency target raise the limit by one, while a `429`, a server
+//! error or an attempt without a response, e.g. a timeout, shrinks it by
+//! [`ConcurrencyConfig::backoff`]. Requests over the limit wait until a slot frees up.
+//!
+//! Failures of requests that were sent before the last decrease don't shrink the limit again,
+//! so a burst of `429`s only halves it once.
+//!
+//! With a controller, streams of requests can be buffered generously,
+//! e.g. with [`ConcurrencyConfig::max`], and the client sends as many as Steam allows.
+
+use std::pin::pin;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use thiserror::Error;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::governor::Governor;
+use crate::Endpoint;
+
+/// Why a [`ConcurrencyConfig`] can't be used, see [`ConcurrencyConfig::validate`]
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ConcurrencyConfigError {
+    #[error("min ({min}) is greater than max ({max})")]
+    MinAboveMax { min: usize, max: usize },
+
+    /// A backoff of `1.0` or more would grow the limit on failure
+    #[error("backoff ({0}) isn't in 0.0..1.0")]
+    Backoff(f64),
+}
+
+#[derive(Debug, Clone)]
+pub struct ConcurrencyConfig {
+    /// The limit every endpoint starts with, [`Endpoint::concurrent_requests`] if [`None`]
+    pub initial: Option<usize>,
+    /// The limit never shrinks below this
+    pub min: usize,
+    /// The limit never grows beyond this
+    pub max: usize,
+    /// Successes needed to raise the limit by one
+    pub increase_after: usize,
+    /// Successes slower than this don't raise the limit, [`None`] to ignore latency
+    pub latency_target: Option<Duration>,
+    /// Factor in `0.0..1.0` the limit is multiplied with on failure
+    pub backoff: f64,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            initial: None,
+            min: 1,
+            max: 256,
+            increase_after: 10,
+            latency_target: Some(Duration::from_secs(2)),
+            backoff: 0.5,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    limit: usize,
+    in_flight: usize,
+    /// Successes since the limit last changed
+    successes: usize,
+    decreased_at: Option<Instant>,
+}
+
+impl Slot {
+    /// Count another request in flight, if the limit allows it
+    const fn try_take(&mut self) -> bool {
+        let free = self.in_flight < self.limit;
+        if free {
+            self.in_flight += 1;
+        }
+        free
+    }
+}
+
+#[derive(Debug)]
+struct EndpointState {
+    slot: Mutex<Slot>,
+    /// Woken whenever a request finishes or the limit grows
+    freed: Notify,
+}
+
+#[derive(Debug)]
+pub struct ConcurrencyController {
+    config: ConcurrencyConfig,
+    /// Indexed by [`Endpoint::index`]
+    endpoints: Vec<EndpointState>,
+}
+
+impl ConcurrencyController {
+    pub fn new(config: ConcurrencyConfig) -> Self {
+        let endpoints = Endpoint::ALL
+            .into_iter()
+            .map(|endpoint| EndpointState {
+                slot: Mutex::new(Slot {
+                    limit: config.initial_limit(endpoint),
+                    in_flight: 0,
+                    successes: 0,
+                    decreased_at: None,
+                }),
+                freed: Notify::new(),
+            })
+            .collect();
+        Self { config, endpoints }
+    }
+
+    pub const fn config(&self) -> &ConcurrencyConfig {
+        &self.config
+    }
+
+    /// How many requests to `endpoint` may be in flight at once right now
+    pub fn limit(&self, endpoint: Endpoint) -> usize {
+        self.slot(endpoint).limit
+    }
+
+    /// How many requests to `endpoint` are in flight right now
+    pub fn in_flight(&self, endpoint: Endpoint) -> usize {
+        self.slot(endpoint).in_flight
+    }
+
+    /// Wait until another request to `endpoint` may be sent.
+    ///
+    /// The slot is freed when the permit is dropped or its outcome is recorded.
+    pub async fn acquire(&self, endpoint: Endpoint) -> ConcurrencyPermit<'_> {
+        let state = &self.endpoints[endpoint.index()];
+        loop {
+            // registered before checking, so a slot freed in between isn't missed
+            let mut freed = pin!(state.freed.notified());
+            freed.as_mut().enable();
+            if state
+                .slot
+                .lock()
+                .expect("concurrency lock poisoned")
+                .try_take()
+            {
+                break;
+            }
+            freed.await;
+        }
+        ConcurrencyPermit {
+            controller: self,
+            endpoint,
+            sent_at: Instant::now(),
+        }
+    }
+
+    /// Start over with the initial limits, requests in flight keep their slots
+    pub fn reset(&self) {
+        for (endpoint, state) in Endpoint::ALL.into_iter().zip(&self.endpoints) {
+            let mut slot = state.slot.lock().expect("concurrency lock poisoned");
+            slot.limit = self.config.initial_limit(endpoint);
+            slot.successes = 0;
+            slot.decreased_at = None;
+            drop(slot);
+            state.freed.notify_waiters();
+        }
+    }
+
+    fn slot(&self, endpoint: Endpoint) -> std::sync::MutexGuard<'_, Slot> {
+        self.endpoints[endpoint.index()]
+            .slot
+            .lock()
+            .expect("concurrency lock poisoned")
+    }
+
+    fn record(
+        &self,
+        permit: &ConcurrencyPermit<'_>,
+        status: Option<StatusCode>,
+        latency: Duration,
+    ) {
+        let config = &self.config;
+        let mut slot = self.slot(permit.endpoint);
+        if Governor::is_failure(status) {
+            // the limit already shrank because of requests sent at the same time
+            if slot.decreased_at.is_some_and(|at| permit.sent_at <= at) {
+                return;
+            }
+            let shrunk = (slot.limit as f64 * config.backoff) as usize;
+            slot.limit = shrunk.max(config.min.max(1));
+            slot.successes = 0;
+            slot.decreased_at = Some(Instant::now());
+            return;
+        }
+        let fast = config.latency_target.is_none_or(|target| latency <= target);
+        if !status.is_some_and(|status| status.is_success()) || !fast {
+            return;
+        }
+        slot.successes += 1;
+        if slot.successes >= config.increase_after.max(1) && slot.limit < config.max {
+            slot.limit += 1;
+            slot.successes = 0;
+            drop(slot);
+            self.endpoints[permit.endpoint.index()]
+                .freed
+                .notify_waiters();
+        }
+    }
+
+    fn release(&self, endpoint: Endpoint) {
+        let state = &self.endpoints[endpoint.index()];
+        state
+            .slot
+            .lock()
+            .expect("concurrency lock poisoned")
+            .in_flight -= 1;
+        state.freed.notify_waiters();
+    }
+}
+
+impl ConcurrencyConfig {
+    /// Check that the limits don't contradict each other and the backoff shrinks the limit,
+    /// done by [`ClientBuilder::build`](crate::ClientBuilder::build)
+    pub fn validate(&self) -> Result<(), ConcurrencyConfigError> {
+        if self.min > self.max {
+            return Err(ConcurrencyConfigError::MinAboveMax {
+                min: self.min,
+                max: self.max,
+            });
+        }
+        if !(0.0..1.0).contains(&self.backoff) {
+            return Err(ConcurrencyConfigError::Backoff(self.backoff));
+        }
+        Ok(())
+    }
+
+    fn initial_limit(&self, endpoint: Endpoint) -> usize {
+        // a contradicting config that wasn't validated shouldn't panic
+        let min = self.min.max(1);
+        self.initial
+            .unwrap_or_else(|| endpoint.concurrent_requests())
+            .clamp(min, self.max.max(min))
+    }
+}
+
+/// A slot for one request, see [`ConcurrencyController::acquire`]
+#[derive(Debug)]
+pub struct ConcurrencyPermit<'a> {
+    controller: &'a ConcurrencyController,
+    endpoint: Endpoint,
+    sent_at: Instant,
+}
+
+impl ConcurrencyPermit<'_> {
+    /// Adjust the limit to the outcome of the request and free the slot.
+    ///
+    /// `status` is [`None`] if there was no response.
+    pub fn record(self, status: Option<StatusCode>, latency: Duration) {
+        self.controller.record(&self, status, latency);
+    }
+}
+
+impl Drop for ConcurrencyPermit<'_> {
+    fn drop(&mut self) {
+        self.controller.release(self.endpoint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::{ConcurrencyConfig, ConcurrencyConfigError, ConcurrencyController};
+    use crate::Endpoint;
+
+    const FAST: Duration = Duration::from_millis(10);
+
+    fn controller() -> ConcurrencyController {
+        ConcurrencyController::new(ConcurrencyConfig {
+            initial: Some(4),
+            min: 1,
+            max: 6,
+            increase_after: 2,
+            latency_target: Some(Duration::from_secs(1)),
+            backoff: 0.5,
+        })
+    }
+
+    #[tokio::test]
+    async fn increases_additively_and_decreases_multiplicatively() {
+        let controller = controller();
+        let endpoint = Endpoint::PlayerBans;
+
+        for _ in 0..4 {
+            let permit = controller.acquire(endpoint).await;
+            permit.record(Some(StatusCode::OK), FAST);
+        }
+        assert_eq!(controller.limit(endpoint), 6);
+
+        // slow successes and client errors don't count
+        let permit = controller.acquire(endpoint).await;
+        permit.record(Some(StatusCode::OK), Duration::from_secs(5));
+        let permit = controller.acquire(endpoint).await;
+        permit.record(Some(StatusCode::UNAUTHORIZED), FAST);
+        assert_eq!(controller.limit(endpoint), 6);
+
+        // capped at the maximum
+        for _ in 0..4 {
+            let permit = controller.acquire(endpoint).await;
+            permit.record(Some(StatusCode::OK), FAST);
+        }
+        assert_eq!(controller.limit(endpoint), 6);
+
+        let permit = controller.acquire(endpoint).await;
+        permit.record(Some(StatusCode::TOO_MANY_REQUESTS), FAST);
+        assert_eq!(controller.limit(endpoint), 3);
+        let permit = controller.acquire(endpoint).await;
+        permit.record(None, FAST);
+        assert_eq!(controller.limit(endpoint), 1);
+        let permit = controller.acquire(endpoint).await;
+        permit.record(Some(StatusCode::BAD_GATEWAY), FAST);
+        assert_eq!(controller.limit(endpoint), 1);
+
+        // other endpoints aren't affected
+        assert_eq!(controller.limit(Endpoint::PlayerSummaries), 4);
+        controller.reset();
+        assert_eq!(controller.limit(endpoint), 4);
+    }
+
+    #[test]
+    fn validates() {
+        assert_eq!(ConcurrencyConfig::default().validate(), Ok(()));
+
+        let config = ConcurrencyConfig {
+            min: 8,
+            max: 4,
+            ..ConcurrencyConfig::default()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConcurrencyConfigError::MinAboveMax { min: 8, max: 4 })
+        );
+        // used anyway, the minimum wins instead of panicking
+        let controller = ConcurrencyController::new(config);
+        assert_eq!(controller.limit(Endpoint::PlayerBans), 8);
+
+        for backoff in [1.0, 1.5, -0.5, f64::NAN] {
+            let config = ConcurrencyConfig {
+                backoff,
+                ..ConcurrencyConfig::default()
+            };
+            assert!(matches!(
+                config.validate(),
+                Err(ConcurrencyConfigError::Backoff(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn decreases_once_per_burst() {
+        let controller = controller();
+        let endpoint = Endpoint::PlayerBans;
+
+        let permits = [
+            controller.acquire(endpoint).await,
+            controller.acquire(endpoint).await,
+            controller.acquire(endpoint).await,
+        ];
+        assert_eq!(controller.in_flight(endpoint), 3);
+        for permit in permits {
+            permit.record(Some(StatusCode::TOO_MANY_REQUESTS), FAST);
+        }
+        assert_eq!(controller.limit(endpoint), 2);
+        assert_eq!(controller.in_flight(endpoint), 0);
+    }
+
+    #[tokio::test]
+    async fn waits_for_free_slots() {
+        let controller = ConcurrencyController::new(ConcurrencyConfig {
+            initial: Some(1),
+            ..ConcurrencyConfig::default()
+        });
+        let endpoint = Endpoint::PlayerBans;
+
+        let permit = controller.acquire(endpoint).await;
+        let waiting = tokio::time::timeout(FAST, controller.acquire(endpoint)).await;
+        assert!(waiting.is_err());
+
+        let (_, second) = tokio::join!(
+            async {
+                tokio::time::sleep(FAST).await;
+                drop(permit);
+            },
+            controller.acquire(endpoint)
+        );
+        assert_eq!(controller.in_flight(endpoint), 1);
+        drop(second);
+        assert_eq!(controller.in_flight(endpoint), 0);
+    }
+}
@@ -0,0 +1,232 @@
+//! Embeds of summaries and bans for Discord bots.
+//!
+//! [`Embed`] serializes to the [embed object](https://discord.com/developers/docs/resources/message#embed-object)
+//! of the Discord api, so it works with any library without this crate depending on one.
+//! serenity and twilight can both deserialize their embed types from it:
+//!
+//! ```ignore
+//! let embed = steam_api_concurrent::discord::profile_embed(&summary, Some(&bans));
+//! let embed: twilight_model::channel::message::Embed =
+//!     serde_json::from_value(serde_json::to_value(&embed)?)?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{PlayerBan, PlayerSummary};
+use crate::{CommunityVisibilityState, EconomyBan, PersonaState};
+
+/// Sidebar color of profiles that are online
+pub const COLOR_ONLINE: u32 = 0x57_CB_DE;
+/// Sidebar color of profiles that are offline
+pub const COLOR_OFFLINE: u32 = 0x89_8A_8C;
+/// Sidebar color of profiles with any ban
+pub const COLOR_BANNED: u32 = 0xE7_4C_3C;
+/// Sidebar color of a bans embed without any ban
+pub const COLOR_CLEAN: u32 = 0x2E_CC_71;
+
+/// Discord rejects embeds with longer titles
+const MAX_TITLE: usize = 256;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Embed {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<EmbedImage>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<EmbedField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<EmbedFooter>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EmbedImage {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EmbedField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EmbedFooter {
+    pub text: String,
+}
+
+impl Embed {
+    fn field(&mut self, name: &str, value: impl Into<String>) {
+        self.fields.push(EmbedField {
+            name: name.to_string(),
+            value: value.into(),
+            inline: true,
+        });
+    }
+}
+
+/// Cut `text` to at most `max` characters, Discord counts characters, not bytes
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+const fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+impl From<&PlayerSummary> for Embed {
+    fn from(summary: &PlayerSummary) -> Self {
+        let online = summary.persona_state() != PersonaState::Offline;
+        let mut embed = Embed {
+            title: Some(truncate(summary.persona_name(), MAX_TITLE)),
+            url: Some(summary.profile_url().to_string()),
+            color: Some(if online { COLOR_ONLINE } else { COLOR_OFFLINE }),
+            thumbnail: (!summary.avatar_full().is_empty()).then(|| EmbedImage {
+                url: summary.avatar_full().to_string(),
+            }),
+            footer: Some(EmbedFooter {
+                text: summary.steam_id().to_string(),
+            }),
+            ..Embed::default()
+        };
+        let visibility = match summary.community_visibility_state() {
+            CommunityVisibilityState::Public => "public",
+            CommunityVisibilityState::FriendsOnly => "friends only",
+            CommunityVisibilityState::Private => "private",
+        };
+        embed.field("Profile", visibility);
+        if let Some(country) = summary.local_country_code() {
+            embed.field("Country", country);
+        }
+        if let Some(created) = summary.time_created() {
+            // rendered by the client in the timezone of the reader
+            embed.field("Created", format!("<t:{}:D>", created.timestamp()));
+        }
+        embed
+    }
+}
+
+impl From<&PlayerBan> for Embed {
+    fn from(ban: &PlayerBan) -> Self {
+        let mut embed = Embed {
+            title: Some("Bans".to_string()),
+            footer: Some(EmbedFooter {
+                text: ban.steam_id.steam_id().to_string(),
+            }),
+            ..Embed::default()
+        };
+        add_ban_fields(&mut embed, ban);
+        embed.color = Some(if ban.is_clean() {
+            COLOR_CLEAN
+        } else {
+            COLOR_BANNED
+        });
+        embed
+    }
+}
+
+fn add_ban_fields(embed: &mut Embed, ban: &PlayerBan) {
+    embed.field("VAC bans", ban.number_of_vac_bans.to_string());
+    embed.field("Game bans", ban.number_of_game_bans.to_string());
+    if !ban.is_clean() {
+        embed.field("Days since last ban", ban.days_since_last_ban.to_string());
+    }
+    embed.field("Community banned", yes_no(ban.community_banned));
+    let trade = match ban.economy_ban {
+        EconomyBan::None => "none",
+        EconomyBan::Probation => "probation",
+        EconomyBan::Banned => "banned",
+    };
+    embed.field("Trade ban", trade);
+}
+
+/// The embed of the summary with the fields of the bans, if there are any,
+/// colored as banned if the profile has any ban
+pub fn profile_embed(summary: &PlayerSummary, ban: Option<&PlayerBan>) -> Embed {
+    let mut embed = Embed::from(summary);
+    if let Some(ban) = ban {
+        add_ban_fields(&mut embed, ban);
+        if !ban.is_clean() {
+            embed.color = Some(COLOR_BANNED);
+        }
+    }
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{profile_embed, Embed, COLOR_BANNED, COLOR_CLEAN, COLOR_OFFLINE};
+    use crate::api::{PlayerBan, PlayerSummary};
+    use crate::test_util::{player_ban, player_summary};
+
+    fn summary() -> PlayerSummary {
+        player_summary(json!({
+            "personaname": "Rabscuttle",
+            "profileurl": "https://steamcommunity.com/id/gabelogannewell/",
+            "avatarfull": "https://avatars.steamstatic.com/c5d56249ee5d28a07db4ac9f7f60af961fab5426_full.jpg",
+            "avatarhash": "c5d56249ee5d28a07db4ac9f7f60af961fab5426",
+            "timecreated": 1063407589,
+            "loccountrycode": "US"
+        }))
+    }
+
+    fn ban(vac_bans: i32) -> PlayerBan {
+        player_ban(json!({
+            "VACBanned": vac_bans > 0,
+            "NumberOfVACBans": vac_bans,
+            "DaysSinceLastBan": 12
+        }))
+    }
+
+    #[test]
+    fn embeds_summaries() {
+        let embed = Embed::from(&summary());
+        assert_eq!(embed.title.as_deref(), Some("Rabscuttle"));
+        assert_eq!(embed.color, Some(COLOR_OFFLINE));
+        assert_eq!(embed.footer.unwrap().text, "76561197960287930");
+        let fields = embed
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("Profile", "public"),
+                ("Country", "US"),
+                ("Created", "<t:1063407589:D>")
+            ]
+        );
+    }
+
+    #[test]
+    fn embeds_bans() {
+        assert_eq!(Embed::from(&ban(0)).color, Some(COLOR_CLEAN));
+        let embed = profile_embed(&summary(), Some(&ban(1)));
+        assert_eq!(embed.color, Some(COLOR_BANNED));
+        assert_eq!(embed.fields.len(), 3 + 5);
+    }
+
+    #[test]
+    fn serializes_like_discord() {
+        let value = serde_json::to_value(Embed::from(&ban(0))).unwrap();
+        assert_eq!(value["color"], COLOR_CLEAN);
+        assert_eq!(value["fields"][0]["inline"], true);
+        assert_eq!(value["footer"]["text"], "76561197960287930");
+        assert!(value.get("thumbnail").is_none());
+    }
+}
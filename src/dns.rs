@@ -0,0 +1,213 @@
+//! Control how the hosts of the endpoints are resolved.
+//!
+//! Hosts can be pinned to fixed addresses with [`ClientBuilder::resolve`](crate::ClientBuilder::resolve),
+//! e.g. to point `api.steampowered.com` at a mock server in tests, or every lookup can go
+//! through a custom [`Resolve`] with [`ClientBuilder::dns_resolver`](crate::ClientBuilder::dns_resolver).
+//!
+//! [`IpVersion`] filters or orders the resolved addresses by IP version,
+//! see [`ClientBuilder::ip_version`](crate::ClientBuilder::ip_version).
+//!
+//! [`CachingResolver`] keeps the addresses from the system resolver for a while,
+//! so crawlers with flaky DNS only depend on it once per TTL.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tokio::time::Instant;
+
+/// Resolve `host` with the system resolver
+fn system_lookup(host: String) -> Resolving {
+    Box::pin(async move {
+        // the port is replaced by the one of the url
+        let addrs = tokio::net::lookup_host((host, 0)).await?;
+        Ok(Box::new(addrs) as Addrs)
+    })
+}
+
+/// The addresses of each host and when they expire
+type Cache = HashMap<String, (Instant, Vec<SocketAddr>)>;
+
+/// Resolves with the system resolver and caches the addresses for a fixed time
+#[derive(Debug)]
+pub struct CachingResolver {
+    ttl: Duration,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl CachingResolver {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cache: Arc::default(),
+        }
+    }
+
+    /// The cached addresses of `host`, if they haven't expired
+    pub fn cached(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let cache = self.cache.lock().expect("dns cache lock poisoned");
+        cache
+            .get(host)
+            .filter(|(expires, _)| *expires > Instant::now())
+            .map(|(_, addrs)| addrs.clone())
+    }
+
+    pub fn clear(&self) {
+        self.cache.lock().expect("dns cache lock poisoned").clear();
+    }
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let ttl = self.ttl;
+        let cached = self.cached(&host);
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            if let Some(addrs) = cached {
+                return Ok(Box::new(addrs.into_iter()) as Addrs);
+            }
+            let addrs = system_lookup(host.clone()).await?.collect::<Vec<_>>();
+            cache
+                .lock()
+                .expect("dns cache lock poisoned")
+                .insert(host, (Instant::now() + ttl, addrs.clone()));
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Which IP versions to connect with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpVersion {
+    V4Only,
+    V6Only,
+    /// Try IPv4 addresses first, fall back to IPv6
+    PreferV4,
+    /// Try IPv6 addresses first, fall back to IPv4
+    PreferV6,
+}
+
+impl IpVersion {
+    /// Drop or reorder `addrs` according to `self`, keeping the order within each version
+    pub fn apply(self, addrs: impl IntoIterator<Item = SocketAddr>) -> Vec<SocketAddr> {
+        let (v4, v6): (Vec<_>, Vec<_>) = addrs.into_iter().partition(SocketAddr::is_ipv4);
+        match self {
+            IpVersion::V4Only => v4,
+            IpVersion::V6Only => v6,
+            IpVersion::PreferV4 => v4.into_iter().chain(v6).collect(),
+            IpVersion::PreferV6 => v6.into_iter().chain(v4).collect(),
+        }
+    }
+}
+
+/// Applies an [`IpVersion`] to the addresses of another resolver, or of the system resolver
+#[derive(Debug)]
+pub(crate) struct IpVersionResolver {
+    inner: Option<SharedResolver>,
+    version: IpVersion,
+}
+
+impl IpVersionResolver {
+    pub(crate) const fn new(inner: Option<SharedResolver>, version: IpVersion) -> Self {
+        Self { inner, version }
+    }
+}
+
+impl Resolve for IpVersionResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let version = self.version;
+        let resolving = match self.inner.as_ref() {
+            Some(inner) => inner.resolve(name),
+            None => system_lookup(name.as_str().to_string()),
+        };
+        Box::pin(async move {
+            let addrs = resolving.await?;
+            Ok(Box::new(version.apply(addrs).into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Lets a resolver behind a trait object be passed to reqwest, which wants a sized one
+#[derive(Clone)]
+pub(crate) struct SharedResolver(pub(crate) Arc<dyn Resolve>);
+
+impl fmt::Debug for SharedResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedResolver").finish_non_exhaustive()
+    }
+}
+
+impl Resolve for SharedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        self.0.resolve(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CachingResolver, IpVersion, Resolve};
+
+    #[tokio::test]
+    async fn caches_lookups() {
+        let resolver = CachingResolver::new(Duration::from_secs(60));
+        assert!(resolver.cached("localhost").is_none());
+
+        let addrs = resolver
+            .resolve("localhost".parse().unwrap())
+            .await
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert!(!addrs.is_empty());
+        assert_eq!(resolver.cached("localhost"), Some(addrs));
+
+        resolver.clear();
+        assert!(resolver.cached("localhost").is_none());
+    }
+
+    #[test]
+    fn orders_by_ip_version() {
+        let addrs: [std::net::SocketAddr; 3] = [
+            "[::1]:0".parse().unwrap(),
+            "127.0.0.1:0".parse().unwrap(),
+            "10.0.0.1:0".parse().unwrap(),
+        ];
+        assert_eq!(IpVersion::V4Only.apply(addrs), addrs[1..]);
+        assert_eq!(IpVersion::V6Only.apply(addrs), addrs[..1]);
+        assert_eq!(
+            IpVersion::PreferV4.apply(addrs),
+            [addrs[1], addrs[2], addrs[0]]
+        );
+        assert_eq!(IpVersion::PreferV6.apply(addrs), addrs);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn pins_hosts() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let steam = MockSteam::start().await;
+        let addr = steam.server().address();
+        // a host that doesn't exist, requests only reach the mock because it's pinned
+        let url = format!("http://steam.invalid:{}", addr.port());
+        let client = steam
+            .client_builder()
+            .api_base_url(&url)
+            .community_base_url(&url)
+            .resolve("steam.invalid", vec![*addr])
+            .build()
+            .await
+            .unwrap();
+        let lvl = client
+            .get_player_steam_level(SteamId(76561198196615742))
+            .await
+            .unwrap();
+        assert_eq!(lvl.lvl(), Some(135));
+    }
+}
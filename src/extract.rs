@@ -0,0 +1,411 @@
+//! Accept ids in web services in any format a user might paste.
+//!
+//! [`SteamIdParam`] parses with [`SteamId::parse_any`], so it works with the path and query
+//! extractors of any framework that deserializes parameters with serde, e.g.
+//! `axum::extract::Path<SteamIdParam>` or `actix_web::web::Query<SteamIdParam>`.
+//!
+//! With the `axum` or `actix` feature it's also an extractor of its own, which reads the
+//! [`PARAM`] path parameter, or the query parameter of the same name if the route has none:
+//!
+//! ```ignore
+//! use steam_api_concurrent::extract::SteamIdParam;
+//!
+//! // GET /profiles/{steamid} or GET /profile?steamid=...
+//! async fn profile(SteamIdParam(id): SteamIdParam) -> String {
+//!     id.to_string()
+//! }
+//! ```
+//!
+//! Both respond with `400 Bad Request` if the parameter is missing or not an id, vanity urls
+//! are rejected too since they can only be resolved with a request.
+//!
+//! Together with the `openid` feature, [`OpenIdLogin`] takes the query Steam redirects users
+//! back to the `return_to` of [`login_url`](crate::openid::login_url) with:
+//!
+//! ```ignore
+//! use steam_api_concurrent::extract::OpenIdLogin;
+//! use steam_api_concurrent::openid::OpenIdError;
+//!
+//! // GET /auth/steam?openid.mode=id_res&...
+//! async fn login(State(client): State<Client>, login: OpenIdLogin) -> Result<String, OpenIdError> {
+//!     let id = login.verify(&client, "https://example.com/auth/steam").await?;
+//!     Ok(id.to_string())
+//! }
+//! ```
+//!
+//! [`OpenIdError`](crate::openid::OpenIdError) responds with `401 Unauthorized` if Steam
+//! rejected the login, `502 Bad Gateway` if Steam couldn't be asked and `400 Bad Request`
+//! for anything else.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use thiserror::Error;
+
+use crate::{ConvertError, SteamId};
+
+/// The name of the parameter the extractors read, the same as in Steam's own api
+pub const PARAM: &str = "steamid";
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SteamIdRejection {
+    #[error("missing the `steamid` path or query parameter")]
+    Missing,
+
+    #[error(transparent)]
+    Invalid(#[from] ConvertError),
+}
+
+/// A [`SteamId`] that parses from any format of [`IdReport`](crate::IdReport)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SteamIdParam(pub SteamId);
+
+impl From<SteamIdParam> for SteamId {
+    fn from(value: SteamIdParam) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for SteamIdParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for SteamIdParam {
+    type Err = ConvertError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SteamId::parse_any(s).map(SteamIdParam)
+    }
+}
+
+impl<'de> Deserialize<'de> for SteamIdParam {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let input = String::deserialize(deserializer)?;
+        input.parse().map_err(de::Error::custom)
+    }
+}
+
+impl SteamIdParam {
+    /// The path parameter if the route has one, the query parameter otherwise
+    #[cfg(any(feature = "axum", feature = "actix"))]
+    fn from_request(path: Option<&str>, query: Option<&str>) -> Result<Self, SteamIdRejection> {
+        let from_query = || {
+            serde_urlencoded::from_str::<Vec<(String, String)>>(query?)
+                .ok()?
+                .into_iter()
+                .find_map(|(key, value)| (key == PARAM).then_some(value))
+        };
+        let input = path
+            .map(str::to_string)
+            .or_else(from_query)
+            .ok_or(SteamIdRejection::Missing)?;
+        Ok(input.parse()?)
+    }
+}
+
+/// The query of a request to the `return_to` of [`login_url`](crate::openid::login_url)
+///
+/// Extracting it never fails, the query is only checked by [`OpenIdLogin::verify`].
+#[cfg(all(feature = "openid", any(feature = "axum", feature = "actix")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenIdLogin(pub String);
+
+#[cfg(all(feature = "openid", any(feature = "axum", feature = "actix")))]
+impl OpenIdLogin {
+    /// [`Client::verify_openid`](crate::Client::verify_openid) with the query
+    pub async fn verify(
+        &self,
+        client: &crate::Client,
+        return_to: &str,
+    ) -> Result<SteamId, crate::openid::OpenIdError> {
+        client.verify_openid(&self.0, return_to).await
+    }
+}
+
+/// The status code to respond with when a login couldn't be verified
+#[cfg(all(feature = "openid", any(feature = "axum", feature = "actix")))]
+const fn openid_status(err: &crate::openid::OpenIdError) -> u16 {
+    use crate::openid::OpenIdError;
+    match err {
+        OpenIdError::Rejected => 401,
+        OpenIdError::Reqwest(_) => 502,
+        _ => 400,
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+    use axum::extract::{FromRequestParts, RawPathParams};
+    use axum::http::request::Parts;
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+
+    use super::{SteamIdParam, SteamIdRejection, PARAM};
+
+    impl<S> FromRequestParts<S> for SteamIdParam
+    where
+        S: Send + Sync,
+    {
+        type Rejection = SteamIdRejection;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let params = RawPathParams::from_request_parts(parts, state).await.ok();
+            let path = params
+                .as_ref()
+                .and_then(|params| params.iter().find(|(key, _)| *key == PARAM))
+                .map(|(_, value)| value);
+            SteamIdParam::from_request(path, parts.uri.query())
+        }
+    }
+
+    impl IntoResponse for SteamIdRejection {
+        fn into_response(self) -> Response {
+            (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+        }
+    }
+
+    #[cfg(feature = "openid")]
+    mod openid {
+        use std::convert::Infallible;
+
+        use axum::extract::FromRequestParts;
+        use axum::http::request::Parts;
+        use axum::http::StatusCode;
+        use axum::response::{IntoResponse, Response};
+
+        use super::super::{openid_status, OpenIdLogin};
+        use crate::openid::OpenIdError;
+
+        impl<S> FromRequestParts<S> for OpenIdLogin
+        where
+            S: Send + Sync,
+        {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+                Ok(OpenIdLogin(
+                    parts.uri.query().unwrap_or_default().to_string(),
+                ))
+            }
+        }
+
+        impl IntoResponse for OpenIdError {
+            fn into_response(self) -> Response {
+                let status = StatusCode::from_u16(openid_status(&self)).expect("valid status");
+                (status, self.to_string()).into_response()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix_impl {
+    use std::future::{ready, Ready};
+
+    use actix_web::dev::Payload;
+    use actix_web::http::StatusCode;
+    use actix_web::{FromRequest, HttpRequest, ResponseError};
+
+    use super::{SteamIdParam, SteamIdRejection, PARAM};
+
+    impl FromRequest for SteamIdParam {
+        type Error = SteamIdRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+            let query = Some(req.query_string()).filter(|query| !query.is_empty());
+            ready(SteamIdParam::from_request(
+                req.match_info().get(PARAM),
+                query,
+            ))
+        }
+    }
+
+    impl ResponseError for SteamIdRejection {
+        fn status_code(&self) -> StatusCode {
+            StatusCode::BAD_REQUEST
+        }
+    }
+
+    #[cfg(feature = "openid")]
+    mod openid {
+        use std::convert::Infallible;
+        use std::future::{ready, Ready};
+
+        use actix_web::dev::Payload;
+        use actix_web::http::StatusCode;
+        use actix_web::{FromRequest, HttpRequest, ResponseError};
+
+        use super::super::{openid_status, OpenIdLogin};
+        use crate::openid::OpenIdError;
+
+        impl FromRequest for OpenIdLogin {
+            type Error = Infallible;
+            type Future = Ready<Result<Self, Self::Error>>;
+
+            fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+                ready(Ok(OpenIdLogin(req.query_string().to_string())))
+            }
+        }
+
+        impl ResponseError for OpenIdError {
+            fn status_code(&self) -> StatusCode {
+                StatusCode::from_u16(openid_status(self)).expect("valid status")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SteamIdParam;
+    use crate::{ConvertError, SteamId};
+
+    const ID: SteamId = SteamId(76561197960287930);
+
+    #[test]
+    fn parses_any_format() {
+        #[derive(serde::Deserialize)]
+        struct Query {
+            steamid: SteamIdParam,
+        }
+
+        assert_eq!("STEAM_1:0:11101".parse(), Ok(SteamIdParam(ID)));
+        assert_eq!(
+            "https://steamcommunity.com/profiles/76561197960287930/".parse(),
+            Ok(SteamIdParam(ID))
+        );
+        assert_eq!(
+            "https://steamcommunity.com/id/gabelogannewell".parse::<SteamIdParam>(),
+            Err(ConvertError::Vanity("gabelogannewell".to_string()))
+        );
+
+        let query = serde_urlencoded::from_str::<Query>("steamid=%5BU%3A1%3A22202%5D").unwrap();
+        assert_eq!(query.steamid, SteamIdParam(ID));
+        assert!(serde_urlencoded::from_str::<Query>("steamid=nope%20nope").is_err());
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn extracts_with_axum() {
+        use axum::extract::FromRequestParts;
+
+        use super::SteamIdRejection;
+
+        let extract = |uri: &'static str| async move {
+            let (mut parts, ()) = axum::http::Request::builder()
+                .uri(uri)
+                .body(())
+                .unwrap()
+                .into_parts();
+            SteamIdParam::from_request_parts(&mut parts, &()).await
+        };
+        assert_eq!(
+            extract("/?steamid=STEAM_1:0:11101").await,
+            Ok(SteamIdParam(ID))
+        );
+        assert_eq!(extract("/?other=1").await, Err(SteamIdRejection::Missing));
+        assert!(matches!(
+            extract("/?steamid=nope%20nope").await,
+            Err(SteamIdRejection::Invalid(_))
+        ));
+    }
+
+    #[cfg(feature = "actix")]
+    #[tokio::test]
+    async fn extracts_with_actix() {
+        use actix_web::test::TestRequest;
+        use actix_web::FromRequest;
+
+        use super::SteamIdRejection;
+
+        let req = TestRequest::with_uri("/profiles/22202")
+            .param("steamid", "22202")
+            .to_http_request();
+        assert_eq!(SteamIdParam::extract(&req).await, Ok(SteamIdParam(ID)));
+
+        let req = TestRequest::with_uri("/profile?steamid=%5BU%3A1%3A22202%5D").to_http_request();
+        assert_eq!(SteamIdParam::extract(&req).await, Ok(SteamIdParam(ID)));
+
+        let req = TestRequest::with_uri("/profile").to_http_request();
+        assert_eq!(
+            SteamIdParam::extract(&req).await,
+            Err(SteamIdRejection::Missing)
+        );
+    }
+
+    #[cfg(all(feature = "axum", feature = "openid", feature = "testing"))]
+    #[tokio::test]
+    async fn verifies_logins_with_axum() {
+        use axum::extract::FromRequestParts;
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
+
+        use super::OpenIdLogin;
+        use crate::test_util::openid_query;
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let extract = |query: String| async move {
+            let (mut parts, ()) = axum::http::Request::builder()
+                .uri(format!("/auth/steam?{}", query))
+                .body(())
+                .unwrap()
+                .into_parts();
+            OpenIdLogin::from_request_parts(&mut parts, &())
+                .await
+                .unwrap()
+        };
+
+        let login = extract(openid_query(&[])).await;
+        assert_eq!(
+            login
+                .verify(&client, "https://example.com/auth/steam")
+                .await
+                .unwrap(),
+            ID
+        );
+        let err = extract(openid_query(&[("openid.mode", "cancel")]))
+            .await
+            .verify(&client, "https://example.com/auth/steam")
+            .await
+            .unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(all(feature = "actix", feature = "openid", feature = "testing"))]
+    #[tokio::test]
+    async fn verifies_logins_with_actix() {
+        use actix_web::http::StatusCode;
+        use actix_web::test::TestRequest;
+        use actix_web::{FromRequest, ResponseError};
+
+        use super::OpenIdLogin;
+        use crate::openid::OpenIdError;
+        use crate::test_util::openid_query;
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let req =
+            TestRequest::with_uri(&format!("/auth/steam?{}", openid_query(&[]))).to_http_request();
+        let login = OpenIdLogin::extract(&req).await.unwrap();
+        assert_eq!(
+            login
+                .verify(&client, "https://example.com/auth/steam")
+                .await
+                .unwrap(),
+            ID
+        );
+        assert_eq!(
+            OpenIdError::Rejected.status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
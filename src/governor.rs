@@ -0,0 +1,197 @@
+//! Slow the client down when Steam keeps failing, instead of hammering it with retries.
+//!
+//! The [`Governor`] watches the outcome of every request the client makes. When the error
+//! rate in the rolling window exceeds the threshold, every request is delayed and the delay
+//! doubles with each further failure, up to a maximum. Once the error rate drops again,
+//! the delay halves with every success until requests go out immediately.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+#[derive(Debug, Clone)]
+pub struct GovernorConfig {
+    /// How far back outcomes are taken into account
+    pub window: Duration,
+    /// Error rate in `0.0..=1.0` above which requests are slowed down
+    pub threshold: f64,
+    /// Outcomes needed in the window before the error rate is trusted
+    pub min_samples: usize,
+    /// Delay when the threshold is first exceeded
+    pub base_delay: Duration,
+    /// The delay never grows beyond this, which effectively pauses bulk requests
+    pub max_delay: Duration,
+}
+
+impl Default for GovernorConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            threshold: 0.25,
+            min_samples: 20,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    outcomes: VecDeque<(Instant, bool)>,
+    delay: Duration,
+}
+
+#[derive(Debug)]
+pub struct Governor {
+    config: GovernorConfig,
+    state: Mutex<State>,
+}
+
+impl Governor {
+    pub const fn new(config: GovernorConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State {
+                outcomes: VecDeque::new(),
+                delay: Duration::ZERO,
+            }),
+        }
+    }
+
+    pub const fn config(&self) -> &GovernorConfig {
+        &self.config
+    }
+
+    /// Whether a response counts as a failure of Steam rather than of the request
+    pub fn is_failure(status: Option<StatusCode>) -> bool {
+        status.is_none_or(|status| {
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        })
+    }
+
+    /// How long the next request should wait before it's sent
+    pub fn delay(&self) -> Duration {
+        self.state.lock().expect("governor lock poisoned").delay
+    }
+
+    /// The error rate in the current window, [`None`] if there are too few samples
+    pub fn error_rate(&self) -> Option<f64> {
+        let mut state = self.state.lock().expect("governor lock poisoned");
+        self.prune(&mut state, Instant::now());
+        self.rate(&state)
+    }
+
+    /// Record the outcome of a request and adjust the delay
+    pub fn record(&self, success: bool) {
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("governor lock poisoned");
+        state.outcomes.push_back((now, success));
+        self.prune(&mut state, now);
+
+        let over_threshold = self
+            .rate(&state)
+            .is_some_and(|rate| rate > self.config.threshold);
+        state.delay = if over_threshold && !success {
+            (state.delay * 2)
+                .max(self.config.base_delay)
+                .min(self.config.max_delay)
+        } else if over_threshold {
+            state.delay
+        } else {
+            let halved = state.delay / 2;
+            if halved < self.config.base_delay {
+                Duration::ZERO
+            } else {
+                halved
+            }
+        };
+    }
+
+    /// Forget every outcome and stop delaying requests
+    pub fn reset(&self) {
+        let mut state = self.state.lock().expect("governor lock poisoned");
+        state.outcomes.clear();
+        state.delay = Duration::ZERO;
+    }
+
+    fn prune(&self, state: &mut State, now: Instant) {
+        while let Some((at, _)) = state.outcomes.front() {
+            if now.duration_since(*at) <= self.config.window {
+                break;
+            }
+            state.outcomes.pop_front();
+        }
+    }
+
+    fn rate(&self, state: &State) -> Option<f64> {
+        let total = state.outcomes.len();
+        if total < self.config.min_samples.max(1) {
+            return None;
+        }
+        let failures = state.outcomes.iter().filter(|(_, ok)| !ok).count();
+        Some(failures as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::{Governor, GovernorConfig};
+
+    fn governor() -> Governor {
+        Governor::new(GovernorConfig {
+            window: Duration::from_secs(3600),
+            threshold: 0.5,
+            min_samples: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(400),
+        })
+    }
+
+    #[test]
+    fn slows_down_and_recovers() {
+        let governor = governor();
+        for _ in 0..3 {
+            governor.record(false);
+        }
+        // not enough samples yet
+        assert_eq!(governor.delay(), Duration::ZERO);
+
+        governor.record(false);
+        assert_eq!(governor.delay(), Duration::from_millis(100));
+        governor.record(false);
+        assert_eq!(governor.delay(), Duration::from_millis(200));
+        for _ in 0..3 {
+            governor.record(false);
+        }
+        assert_eq!(governor.delay(), Duration::from_millis(400));
+        assert_eq!(governor.error_rate(), Some(1.0));
+
+        // still over the threshold, so the delay is kept
+        governor.record(true);
+        assert_eq!(governor.delay(), Duration::from_millis(400));
+
+        for _ in 0..8 {
+            governor.record(true);
+        }
+        assert!(governor.delay() < Duration::from_millis(400));
+        for _ in 0..3 {
+            governor.record(true);
+        }
+        assert_eq!(governor.delay(), Duration::ZERO);
+    }
+
+    #[test]
+    fn classifies_failures() {
+        assert!(Governor::is_failure(None));
+        assert!(Governor::is_failure(Some(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(Governor::is_failure(Some(StatusCode::BAD_GATEWAY)));
+        assert!(!Governor::is_failure(Some(StatusCode::UNAUTHORIZED)));
+        assert!(!Governor::is_failure(Some(StatusCode::OK)));
+    }
+}
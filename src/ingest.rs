@@ -0,0 +1,262 @@
+//! Turn user-provided lists of profiles into ids for the bulk requests.
+//!
+//! Every line of a text file, or one column of a csv file, is classified with
+//! [`SteamId::parse_any`]. Lines that already name an id need no request, vanity urls and bare
+//! names that look like one have to be resolved first, see [`Client::resolve_plan`].
+//!
+//! ```no_run
+//! # async fn run(client: steam_api_concurrent::Client) -> std::io::Result<()> {
+//! use steam_api_concurrent::ingest::ResolutionPlan;
+//!
+//! let file = std::io::BufReader::new(std::fs::File::open("profiles.txt")?);
+//! let plan = ResolutionPlan::from_reader(file)?;
+//! for entry in plan.invalid() {
+//!     eprintln!("line {}: {:?}", entry.line, entry.input);
+//! }
+//! let resolved = client.resolve_plan(&plan).await;
+//! let profiles = client.get_profiles_with_bans(&resolved.ids).await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use crate::api::VanityProfileError;
+use crate::{Client, ConvertError, SteamId};
+
+/// What a line of the input names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanTarget {
+    /// The line is an id in one of the formats of [`IdReport`](crate::IdReport)
+    Id(SteamId),
+    /// The line is a vanity url or a bare name, which has to be resolved
+    Vanity(String),
+    Invalid(ConvertError),
+}
+
+/// A single non-empty line of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanEntry {
+    /// `1`-based, to point users at the line
+    pub line: usize,
+    /// The line, or its column, without surrounding whitespace and quotes
+    pub input: String,
+    pub target: PlanTarget,
+}
+
+/// The classified lines of an input list, see the [module docs](self)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolutionPlan {
+    pub entries: Vec<PlanEntry>,
+}
+
+/// Whether `input` could be the part after `steamcommunity.com/id/`
+fn is_vanity_name(input: &str) -> bool {
+    (2..=32).contains(&input.len())
+        && input
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+fn classify(input: &str) -> PlanTarget {
+    match SteamId::parse_any(input) {
+        Ok(id) => PlanTarget::Id(id),
+        Err(ConvertError::Vanity(name)) => PlanTarget::Vanity(name),
+        Err(ConvertError::Unrecognized(_)) if is_vanity_name(input) => {
+            PlanTarget::Vanity(input.to_string())
+        }
+        Err(err) => PlanTarget::Invalid(err),
+    }
+}
+
+impl ResolutionPlan {
+    /// Classify every line, empty lines and lines starting with `#` are skipped
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        Self::from_fields(lines.into_iter().map(Some))
+    }
+
+    /// Classify the `column`th field of every line of a csv file, `0`-based.
+    ///
+    /// Fields are split at commas and may be quoted, quotes inside fields aren't supported.
+    /// A header line is classified like any other, so it usually ends up as a vanity or
+    /// as invalid, skip it with [`Iterator::skip`] if needed.
+    pub fn from_csv_lines<'a>(lines: impl IntoIterator<Item = &'a str>, column: usize) -> Self {
+        Self::from_fields(lines.into_iter().map(|line| line.split(',').nth(column)))
+    }
+
+    /// [`ResolutionPlan::from_lines`] of everything `reader` reads
+    pub fn from_reader(reader: impl BufRead) -> std::io::Result<Self> {
+        let lines = reader.lines().collect::<std::io::Result<Vec<_>>>()?;
+        Ok(Self::from_lines(lines.iter().map(String::as_str)))
+    }
+
+    fn from_fields<'a>(fields: impl Iterator<Item = Option<&'a str>>) -> Self {
+        let entries = fields
+            .enumerate()
+            .filter_map(|(i, field)| {
+                let input = field?.trim().trim_matches('"').trim();
+                if input.is_empty() || input.starts_with('#') {
+                    return None;
+                }
+                Some(PlanEntry {
+                    line: i + 1,
+                    input: input.to_string(),
+                    target: classify(input),
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// The ids that need no request, sorted and without duplicates
+    pub fn ids(&self) -> Vec<SteamId> {
+        let mut ids = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.target {
+                PlanTarget::Id(id) => Some(id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// The names that have to be resolved, sorted and without duplicates
+    pub fn vanities(&self) -> Vec<&str> {
+        let mut names = self
+            .entries
+            .iter()
+            .filter_map(|entry| match &entry.target {
+                PlanTarget::Vanity(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// The lines that name neither an id nor a vanity url
+    pub fn invalid(&self) -> impl Iterator<Item = &PlanEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.target, PlanTarget::Invalid(_)))
+    }
+}
+
+/// See [`Client::resolve_plan`]
+#[derive(Debug, Default)]
+pub struct ResolvedPlan {
+    /// Every id of the plan, sorted and without duplicates
+    pub ids: Vec<SteamId>,
+    /// The names that couldn't be resolved and why
+    pub unresolved: HashMap<String, VanityProfileError>,
+}
+
+impl Client {
+    /// Resolve the vanities of `plan` and merge them with its ids, ready for the bulk
+    /// requests, e.g. [`Client::get_profiles_with_bans`]
+    ///
+    /// Once the client is [shut down](Client::shutdown) every name is
+    /// [`VanityProfileError::Cancelled`].
+    ///
+    /// Uses [`Endpoint::ResolveVanityUrl`](crate::Endpoint::ResolveVanityUrl)
+    pub async fn resolve_plan(&self, plan: &ResolutionPlan) -> ResolvedPlan {
+        let mut resolved = ResolvedPlan {
+            ids: plan.ids(),
+            unresolved: HashMap::new(),
+        };
+        for (name, resolution) in self.resolve_vanities(&plan.vanities()).await {
+            match resolution {
+                Ok(id) => resolved.ids.push(id),
+                Err(err) => {
+                    resolved.unresolved.insert(name.to_string(), err);
+                }
+            }
+        }
+        resolved.ids.sort_unstable();
+        resolved.ids.dedup();
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlanTarget, ResolutionPlan};
+    use crate::SteamId;
+
+    const ID: SteamId = SteamId(76561197960287930);
+
+    #[test]
+    fn classifies_lines() {
+        let input = "\
+76561197960287930
+# comment
+
+https://steamcommunity.com/id/gabelogannewell/
+  STEAM_1:0:11101
+gabelogannewell
+not a vanity
+";
+        let plan = ResolutionPlan::from_lines(input.lines());
+        assert_eq!(plan.entries.len(), 5);
+        assert_eq!(plan.entries[0].target, PlanTarget::Id(ID));
+        assert_eq!(plan.entries[2].line, 5);
+        assert_eq!(plan.entries[2].input, "STEAM_1:0:11101");
+        assert_eq!(plan.ids(), [ID]);
+        assert_eq!(plan.vanities(), ["gabelogannewell"]);
+        let invalid = plan.invalid().collect::<Vec<_>>();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].line, 7);
+    }
+
+    #[test]
+    fn reads_csv_columns() {
+        let input = "name,profile\nGabe,\"[U:1:22202]\"\nRobin,robinwalker\nEmpty,";
+        let plan = ResolutionPlan::from_csv_lines(input.lines().skip(1), 1);
+        assert_eq!(plan.entries.len(), 2);
+        assert_eq!(plan.ids(), [ID]);
+        assert_eq!(plan.vanities(), ["robinwalker"]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn resolves_plans() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let plan = ResolutionPlan::from_lines(["76561197960287930", "gabelogannewell", "gaben"]);
+        let resolved = client.resolve_plan(&plan).await;
+        // both names resolve to the id of the fixture, which is also the first line
+        assert_eq!(resolved.ids, [ID]);
+        assert!(resolved.unresolved.is_empty());
+        assert_eq!(steam.received(Endpoint::ResolveVanityUrl).await, 2);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn stops_when_shut_down() {
+        use crate::api::VanityProfileError;
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        client.shutdown();
+        let plan = ResolutionPlan::from_lines(["76561197960287930", "gabelogannewell"]);
+        let resolved = client.resolve_plan(&plan).await;
+        assert_eq!(resolved.ids, [ID]);
+        assert!(matches!(
+            resolved.unresolved["gabelogannewell"],
+            VanityProfileError::Cancelled
+        ));
+        assert_eq!(steam.received(Endpoint::ResolveVanityUrl).await, 0);
+    }
+}
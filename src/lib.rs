@@ -70,12 +70,44 @@
 //! # Current state
 //!
 //! Currently provides abstractions for the following endpoints:
-//! - [X] [`api.steampowered.com/ISteamUser/ResolveVanityURL/v1/`][constants::VANITY_API]
-//! - [X] [`api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/`][constants::PLAYER_SUMMARIES_API]
-//! - [X] [`api.steampowered.com/ISteamUser/GetFriendList/v1/`][constants::PLAYER_FRIENDS_API]
-//! - [X] [`api.steampowered.com/ISteamUser/GetPlayerBans/v1/`][constants::PLAYER_BANS_API]
-//! - [X] [`api.steampowered.com/IPlayerService/GetSteamLevel/v1/`][constants::PLAYER_STEAM_LEVEL_API]
-//! - [X] [`steamcommunity.com/search/SearchCommunityAjax/`][constants::USER_SEARCH_API]
+//! - [X] [`api.steampowered.com/ISteamUser/ResolveVanityURL/v1/`][Endpoint::ResolveVanityUrl]
+//! - [X] [`api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/`][Endpoint::PlayerSummaries]
+//! - [X] [`api.steampowered.com/ISteamUser/GetFriendList/v1/`][Endpoint::PlayerFriends]
+//! - [X] [`api.steampowered.com/ISteamUser/GetPlayerBans/v1/`][Endpoint::PlayerBans]
+//! - [X] [`api.steampowered.com/IPlayerService/GetSteamLevel/v1/`][Endpoint::PlayerSteamLevel]
+//! - [X] [`api.steampowered.com/IPlayerService/GetOwnedGames/v1/`][Endpoint::OwnedGames]
+//! - [X] [`api.steampowered.com/IPlayerService/GetProfileItemsEquipped/v1/`][Endpoint::ProfileItemsEquipped]
+//! - [X] [`api.steampowered.com/ISteamRemoteStorage/GetPublishedFileDetails/v1/`][Endpoint::PublishedFileDetails]
+//! - [X] [`api.steampowered.com/ISteamWebAPIUtil/GetServerInfo/v1/`][Endpoint::ServerInfo]
+//! - [X] [`api.steampowered.com/ISteamWebAPIUtil/GetSupportedAPIList/v1/`][Endpoint::SupportedApiList]
+//! - [X] [`api.steampowered.com/ISteamApps/GetAppList/v2/`][Endpoint::AppList]
+//! - [X] [`api.steampowered.com/ISteamDirectory/GetCMList/v1/`][Endpoint::CmList]
+//! - [X] [`api.steampowered.com/ISteamDirectory/GetCMListForConnect/v1/`][Endpoint::CmListForConnect]
+//! - [X] [`partner.steam-api.com/ISteamApps/GetAppBetas/v1/`][Endpoint::AppBetas]
+//! - [X] [`partner.steam-api.com/ISteamApps/GetAppBuilds/v1/`][Endpoint::AppBuilds]
+//! - [X] [`partner.steam-api.com/ICheatReportingService/ReportPlayerCheating/v1/`][Endpoint::ReportPlayerCheating]
+//! - [X] [`partner.steam-api.com/ICheatReportingService/RequestPlayerGameBan/v1/`][Endpoint::RequestPlayerGameBan]
+//! - [X] [`partner.steam-api.com/ICheatReportingService/GetCheatingReports/v1/`][Endpoint::CheatingReports]
+//! - [X] [`partner.steam-api.com/IInventoryService/GetInventory/v1/`][Endpoint::Inventory]
+//! - [X] [`partner.steam-api.com/IInventoryService/AddItem/v1/`][Endpoint::AddItem]
+//! - [X] [`partner.steam-api.com/IInventoryService/ConsumeItem/v1/`][Endpoint::ConsumeItem]
+//! - [X] [`api.steampowered.com/IInventoryService/GetItemDefMeta/v1/`][Endpoint::ItemDefMeta]
+//! - [X] [`api.steampowered.com/IGameInventory/GetItemDefArchive/v0001/`][Endpoint::ItemDefArchive]
+//! - [X] [`api.steampowered.com/ISteamUserStats/GetUserStatsForGame/v2/`][Endpoint::UserStatsForGame]
+//! - [X] [`api.steampowered.com/ISteamApps/GetServersAtAddress/v1/`][Endpoint::ServersAtAddress]
+//! - [X] [`partner.steam-api.com/ISteamEconomy/GetAssetPrices/v1/`][Endpoint::AssetPrices]
+//! - [X] [`partner.steam-api.com/ILobbyMatchmakingService/GetLobbyData/v1/`][Endpoint::LobbyData]
+//! - [X] [`steamcommunity.com/search/SearchCommunityAjax/`][Endpoint::UserSearch]
+//! - [X] [`steamcommunity.com/groups/{name}/rss/`][Endpoint::GroupAnnouncements]
+//! - [X] [`steamcommunity.com/groups/{name}/members/`][Endpoint::GroupMembers]
+//! - [X] [`steamcommunity.com/gid/{group_id}/memberslistxml/`][Endpoint::GroupMemberList]
+//! - [X] [`steamcommunity.com/groups/{name}/`][Endpoint::GroupPage]
+//! - [X] [`steamcommunity.com/miniprofile/{account_id}/json`][Endpoint::MiniProfile]
+//! - [X] [`steamcommunity.com/profiles/{steam_id}/`][Endpoint::ProfilePage]
+//! - [X] [`steamcommunity.com/profiles/{steam_id}/?xml=1`][Endpoint::ProfilePage]
+//! - [X] [`steamcommunity.com/market/listings/{app_id}/{market_hash_name}`][Endpoint::MarketListings]
+//! - [X] [`steamcommunity.com/openid/login`][Endpoint::OpenIdLogin]
+//! - [X] [`store.steampowered.com/saleaction/ajaxgetdeckappcompatibilityreport`][Endpoint::DeckCompatibilityReport]
 //!
 //! # Other
 //!
@@ -90,9 +122,36 @@
 mod test_util;
 
 mod model;
-pub use model::{api, html, *};
+pub use model::{api, html, xml, *};
 
 pub mod util;
 
 mod client;
 pub use client::*;
+mod client_options;
+pub use client_options::*;
+
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod concurrency;
+#[cfg(feature = "discord")]
+pub mod discord;
+pub mod dns;
+pub mod extract;
+pub mod governor;
+pub mod ingest;
+#[cfg(feature = "openid")]
+pub mod openid;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+pub mod rate_limit;
+pub mod report;
+#[cfg(feature = "schema_drift")]
+pub mod schema_drift;
+pub mod simple;
+pub mod tenant;
+#[cfg(feature = "vdf")]
+pub mod vdf;
+
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::{
+    PlayerBan, PlayerBanError, PlayerSummaryError, SteamLevel, SteamLevelError,
+};
+use crate::SteamId;
+
+#[derive(Error, Debug)]
+pub enum AccountStatusError {
+    #[error(transparent)]
+    Summaries(#[from] PlayerSummaryError),
+
+    #[error(transparent)]
+    Bans(#[from] PlayerBanError),
+
+    #[error(transparent)]
+    SteamLevel(#[from] SteamLevelError),
+}
+type Result<T> = std::result::Result<T, AccountStatusError>;
+
+/// Whether an account exists, see [`Client::probe_account_status`]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountStatus {
+    /// The account has a summary
+    Active,
+    /// The account has a summary, but has been banned from the community
+    CommunityBanned,
+    /// The account has no summary, but Steam still knows about it
+    Deleted,
+    /// Steam knows nothing about the account
+    NeverExisted,
+}
+
+impl AccountStatus {
+    /// Combine the signals of the three endpoints
+    ///
+    /// - A summary exists → [`AccountStatus::CommunityBanned`], if the `ban` says so,
+    ///   else [`AccountStatus::Active`]
+    /// - No summary, but a `ban` entry or a `level` → [`AccountStatus::Deleted`],
+    ///   because bans and levels are kept after an account is deleted
+    /// - None of them → [`AccountStatus::NeverExisted`]
+    pub const fn from_signals(
+        has_summary: bool,
+        ban: Option<&PlayerBan>,
+        level: SteamLevel,
+    ) -> Self {
+        match (has_summary, ban) {
+            (true, Some(ban)) if ban.community_banned => AccountStatus::CommunityBanned,
+            (true, _) => AccountStatus::Active,
+            (false, Some(_)) => AccountStatus::Deleted,
+            (false, None) if level.lvl().is_some() => AccountStatus::Deleted,
+            (false, None) => AccountStatus::NeverExisted,
+        }
+    }
+}
+
+impl Client {
+    /// Find out whether the account with the given [`SteamId`] exists,
+    /// see [`AccountStatus::from_signals`] for the heuristics
+    ///
+    /// Uses [`Endpoint::PlayerSummaries`](crate::Endpoint::PlayerSummaries),
+    /// [`Endpoint::PlayerBans`](crate::Endpoint::PlayerBans) and
+    /// [`Endpoint::PlayerSteamLevel`](crate::Endpoint::PlayerSteamLevel)
+    pub async fn probe_account_status(&self, steam_id: SteamId) -> Result<AccountStatus> {
+        let ids = [steam_id];
+        let (summaries, bans, level) = futures::join!(
+            self.get_player_summaries(ids[..].into()),
+            self.get_player_bans(ids[..].into()),
+            self.get_player_steam_level(steam_id),
+        );
+        let (summaries, bans, level) = (summaries?, bans?, level?);
+
+        Ok(AccountStatus::from_signals(
+            summaries.contains_key(&steam_id),
+            bans.get(&steam_id),
+            level,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::AccountStatus;
+    use crate::api::{PlayerBan, SteamLevel};
+    use crate::test_util::player_ban;
+
+    fn ban(community_banned: bool) -> PlayerBan {
+        player_ban(json!({ "CommunityBanned": community_banned }))
+    }
+
+    #[test]
+    fn from_signals() {
+        let lvl: SteamLevel = serde_json::from_value(json!(10)).unwrap();
+        let no_lvl: SteamLevel = serde_json::from_value(json!(null)).unwrap();
+
+        let cases = [
+            (true, Some(ban(true)), lvl, AccountStatus::CommunityBanned),
+            (true, Some(ban(false)), lvl, AccountStatus::Active),
+            (true, None, no_lvl, AccountStatus::Active),
+            (false, Some(ban(false)), no_lvl, AccountStatus::Deleted),
+            (false, None, lvl, AccountStatus::Deleted),
+            (false, None, no_lvl, AccountStatus::NeverExisted),
+        ];
+        for (has_summary, ban, level, expected) in cases {
+            let status = AccountStatus::from_signals(has_summary, ban.as_ref(), level);
+            assert_eq!(status, expected);
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn probes() {
+        use crate::testing::{fixtures, MockSteam};
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start_empty().await;
+        steam
+            .mount_fixture(Endpoint::PlayerSummaries, fixtures::PLAYER_SUMMARIES)
+            .await;
+        steam
+            .mount_fixture(Endpoint::PlayerBans, fixtures::PLAYER_BANS)
+            .await;
+        steam
+            .mount_fixture(Endpoint::PlayerSteamLevel, fixtures::STEAM_LEVEL_DELETED)
+            .await;
+        let client = steam.client().await;
+
+        let status = |id| client.probe_account_status(SteamId(id));
+        assert_eq!(
+            status(76561199159691884).await.unwrap(),
+            AccountStatus::Active
+        );
+        assert_eq!(
+            status(76561198196615742).await.unwrap(),
+            AccountStatus::Deleted
+        );
+        assert_eq!(
+            status(76561197960287930).await.unwrap(),
+            AccountStatus::NeverExisted
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamTime};
+
+#[derive(Error, Debug)]
+pub enum AppBuildsError {
+    /// See [`ClientBuilder::publisher_key`](crate::ClientBuilder::publisher_key)
+    #[error("client has no publisher key")]
+    PublisherKey,
+
+    /// Steam answered with a result other than `1`, e.g. if the key can't access the app
+    #[error("request failed with result {0}")]
+    Failed(u32),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, AppBuildsError>;
+
+/// A branch of an app, see [`Client::get_app_betas`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppBeta {
+    /// The name of the branch, `public` is the default one
+    #[serde(default)]
+    pub name: String,
+    /// The build that is live on the branch
+    #[serde(rename(deserialize = "BuildID"))]
+    pub build_id: u32,
+    #[serde(rename(deserialize = "Description"))]
+    pub description: String,
+    #[serde(rename(deserialize = "ReqPassword"))]
+    pub requires_password: bool,
+    #[serde(rename(deserialize = "ReqLocalCS"))]
+    pub requires_local_cs: bool,
+}
+
+/// An uploaded build of an app, see [`Client::get_app_builds`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppBuild {
+    #[serde(rename(deserialize = "BuildID"))]
+    pub build_id: u32,
+    #[serde(rename(deserialize = "Description"))]
+    pub description: String,
+    #[serde(rename(deserialize = "CreationTime"))]
+    pub created: SteamTime,
+    /// Account id of the user that uploaded the build
+    #[serde(rename(deserialize = "AccountIDCreator"))]
+    pub creator_account_id: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct BetasInner {
+    result: u32,
+    #[serde(default)]
+    betas: HashMap<String, AppBeta>,
+}
+
+type BetasResponse = Envelope<BetasInner>;
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct BuildsInner {
+    result: u32,
+    #[serde(default)]
+    builds: HashMap<String, AppBuild>,
+}
+
+type BuildsResponse = Envelope<BuildsInner>;
+
+impl TryFrom<BetasResponse> for Vec<AppBeta> {
+    type Error = AppBuildsError;
+    fn try_from(value: BetasResponse) -> Result<Self> {
+        let inner = value.into_inner();
+        if inner.result != 1 {
+            return Err(AppBuildsError::Failed(inner.result));
+        }
+        let mut betas = inner
+            .betas
+            .into_iter()
+            .map(|(name, beta)| AppBeta { name, ..beta })
+            .collect::<Vec<_>>();
+        betas.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        Ok(betas)
+    }
+}
+
+impl TryFrom<BuildsResponse> for Vec<AppBuild> {
+    type Error = AppBuildsError;
+    fn try_from(value: BuildsResponse) -> Result<Self> {
+        let inner = value.into_inner();
+        if inner.result != 1 {
+            return Err(AppBuildsError::Failed(inner.result));
+        }
+        let mut builds = inner.builds.into_values().collect::<Vec<_>>();
+        builds.sort_unstable_by_key(|build| Reverse(build.build_id));
+        Ok(builds)
+    }
+}
+
+impl Client {
+    fn require_publisher_key(&self) -> Result<&str> {
+        self.publisher_key().ok_or(AppBuildsError::PublisherKey)
+    }
+
+    /// Get the branches of an app, sorted by name
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Uses [`Endpoint::AppBetas`]
+    pub async fn get_app_betas(&self, app_id: u32) -> Result<Vec<AppBeta>> {
+        let key = self.require_publisher_key()?;
+        let query = [("key", key), ("appid", &app_id.to_string())];
+
+        self.get_json::<BetasResponse>(Endpoint::AppBetas, &query)
+            .await?
+            .try_into()
+    }
+
+    /// Get the most recent `count` builds of an app, newest first
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Uses [`Endpoint::AppBuilds`]
+    pub async fn get_app_builds(&self, app_id: u32, count: u32) -> Result<Vec<AppBuild>> {
+        let key = self.require_publisher_key()?;
+        let query = [
+            ("key", key),
+            ("appid", &app_id.to_string()),
+            ("count", &count.to_string()),
+        ];
+
+        self.get_json::<BuildsResponse>(Endpoint::AppBuilds, &query)
+            .await?
+            .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppBeta, AppBuild, BetasResponse, BuildsResponse};
+
+    #[test]
+    fn parses_betas() {
+        let json: BetasResponse = load_test_json!("app_betas.json");
+        let betas = Vec::<AppBeta>::try_from(json).unwrap();
+        assert_eq!(betas.len(), 2);
+        assert_eq!(betas[0].name, "public");
+        assert_eq!(betas[0].build_id, 5738281);
+        assert!(betas[1].requires_password);
+    }
+
+    #[test]
+    fn parses_builds() {
+        let json: BuildsResponse = load_test_json!("app_builds.json");
+        let builds = Vec::<AppBuild>::try_from(json).unwrap();
+        assert_eq!(builds.len(), 2);
+        assert_eq!(builds[0].build_id, 5740112);
+        assert_eq!(builds[0].description, "1.0.4 rc1");
+        assert_eq!(builds[0].created.timestamp(), 1697210511);
+        assert_eq!(builds[1].creator_account_id, 20873);
+    }
+
+    #[test]
+    fn fails() {
+        let json: BuildsResponse = serde_json::from_str(r#"{"response":{"result":15}}"#).unwrap();
+        assert!(Vec::<AppBuild>::try_from(json).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requires_publisher_key() {
+        use super::AppBuildsError;
+        use crate::testing::{MockSteam, API_KEY, PUBLISHER_KEY};
+        use crate::{ClientBuilder, Host};
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        assert_eq!(client.publisher_key(), Some(PUBLISHER_KEY));
+        assert_eq!(client.get_app_builds(480, 10).await.unwrap().len(), 2);
+        assert_eq!(client.get_app_betas(480).await.unwrap().len(), 2);
+
+        let client = ClientBuilder::new()
+            .api_key(API_KEY.to_string())
+            .base_url(Host::Api, steam.uri())
+            .base_url(Host::Community, steam.uri())
+            .base_url(Host::Partner, steam.uri())
+            .build()
+            .await
+            .unwrap();
+        assert!(matches!(
+            client.get_app_builds(480, 10).await,
+            Err(AppBuildsError::PublisherKey)
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::Endpoint;
+
+#[derive(Error, Debug)]
+pub enum AppListError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, AppListError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct App {
+    /// Also accepts `app_id`, so the list can be read back after serializing it
+    #[serde(rename(deserialize = "appid"), alias = "app_id")]
+    pub app_id: u32,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ResponseInner {
+    apps: Vec<App>,
+}
+
+type Response = Envelope<ResponseInner>;
+
+impl Client {
+    /// Get every app on Steam, which are a lot (> 200k)
+    ///
+    /// Apps without a name are left out.
+    ///
+    /// Uses [`Endpoint::AppList`]
+    pub async fn get_app_list(&self) -> Result<Vec<App>> {
+        let resp = self.get_json::<Response>(Endpoint::AppList, &[]).await?;
+        let mut apps = resp.into_inner().apps;
+        apps.retain(|app| !app.name.is_empty());
+        Ok(apps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("app_list.json");
+        let apps = json.into_inner().apps;
+        assert_eq!(apps.len(), 9);
+        assert_eq!(apps[2].app_id, 730);
+        assert_eq!(apps[2].name, "Counter-Strike 2");
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::serde_util::from_str_or_number;
+use crate::model::Endpoint;
+
+#[derive(Error, Debug)]
+pub enum AssetPricesError {
+    /// See [`ClientBuilder::publisher_key`](crate::ClientBuilder::publisher_key)
+    #[error("client has no publisher key")]
+    PublisherKey,
+
+    /// Steam answered with `success: false`, e.g. if the app has no item store
+    #[error("request failed")]
+    Failed,
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, AssetPricesError>;
+
+/// Provides the rates prices are converted with, see [`AssetPrice::price_in`].
+///
+/// This crate has no rates of its own, implement this on top of whatever source
+/// the rates should come from. Closures taking `(from, to)` implement it as well.
+///
+/// ```
+/// use steam_api_concurrent::api::ExchangeRates;
+///
+/// // the value of one unit of each currency in euros
+/// let in_eur = |currency: &str| match currency {
+///     "EUR" => Some(1.0),
+///     "USD" => Some(0.9),
+///     _ => None,
+/// };
+/// let rates = |from: &str, to: &str| Some(in_eur(from)? / in_eur(to)?);
+/// assert_eq!(rates.rate("USD", "EUR"), Some(0.9));
+/// assert_eq!(rates.rate("GBP", "EUR"), None);
+/// ```
+pub trait ExchangeRates {
+    /// How many units of `to` one unit of `from` is worth, [`None`] if the rate is unknown.
+    ///
+    /// Currencies are ISO 4217 codes, e.g. `USD`.
+    fn rate(&self, from: &str, to: &str) -> Option<f64>;
+}
+
+impl<F> ExchangeRates for F
+where
+    F: Fn(&str, &str) -> Option<f64>,
+{
+    fn rate(&self, from: &str, to: &str) -> Option<f64> {
+        self(from, to)
+    }
+}
+
+/// The prices of an item in the in-game store of an app, see [`Client::get_asset_prices`]
+///
+/// Prices are in hundredths of the currency, keyed by ISO 4217 code, e.g. `USD: 199` is `$1.99`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AssetPrice {
+    #[serde(rename(deserialize = "classid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub class_id: u64,
+    pub name: String,
+    /// The date the price was set, e.g. `2023/10/13`
+    #[serde(default)]
+    pub date: Option<String>,
+    pub prices: HashMap<String, u64>,
+    /// The prices before a sale, empty if the item isn't on sale
+    #[serde(default)]
+    pub original_prices: HashMap<String, u64>,
+}
+
+impl AssetPrice {
+    /// Whether the item is currently on sale
+    pub fn is_on_sale(&self) -> bool {
+        !self.original_prices.is_empty()
+    }
+
+    /// The price in `currency`, converted from another currency if the item has no price in it.
+    ///
+    /// Converts from the first currency in alphabetical order `rates` has a rate for,
+    /// so the result doesn't depend on the order of the prices.
+    pub fn price_in(&self, currency: &str, rates: &impl ExchangeRates) -> Option<f64> {
+        if let Some(&price) = self.prices.get(currency) {
+            return Some(price as f64);
+        }
+        let mut prices = self.prices.iter().collect::<Vec<_>>();
+        prices.sort_unstable_by_key(|(code, _)| *code);
+        prices
+            .into_iter()
+            .find_map(|(code, &price)| Some(price as f64 * rates.rate(code, currency)?))
+    }
+
+    /// Every regional price converted to `currency`, to compare prices across regions.
+    ///
+    /// Currencies `rates` has no rate for are left out.
+    pub fn normalized_prices(
+        &self,
+        currency: &str,
+        rates: &impl ExchangeRates,
+    ) -> HashMap<String, f64> {
+        self.prices
+            .iter()
+            .filter_map(|(code, &price)| {
+                let rate = if code == currency {
+                    1.0
+                } else {
+                    rates.rate(code, currency)?
+                };
+                Some((code.clone(), price as f64 * rate))
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct Inner {
+    success: bool,
+    #[serde(default)]
+    assets: Vec<AssetPrice>,
+}
+
+type Response = Envelope<Inner>;
+
+impl TryFrom<Response> for Vec<AssetPrice> {
+    type Error = AssetPricesError;
+    fn try_from(value: Response) -> Result<Self> {
+        if !value.success {
+            return Err(AssetPricesError::Failed);
+        }
+        Ok(value.into_inner().assets)
+    }
+}
+
+impl Client {
+    /// Get the prices of the items in the in-game store of an app in every currency,
+    /// or only in `currency`, e.g. `USD`
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Uses [`Endpoint::AssetPrices`]
+    pub async fn get_asset_prices(
+        &self,
+        app_id: u32,
+        currency: Option<&str>,
+    ) -> Result<Vec<AssetPrice>> {
+        let key = self.publisher_key().ok_or(AssetPricesError::PublisherKey)?;
+        let app_id = app_id.to_string();
+        let mut query = vec![("key", key), ("appid", app_id.as_str())];
+        if let Some(currency) = currency {
+            query.push(("currency", currency));
+        }
+
+        self.get_json::<Response>(Endpoint::AssetPrices, &query)
+            .await?
+            .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetPrice, Response};
+
+    fn rates(from: &str, to: &str) -> Option<f64> {
+        match (from, to) {
+            ("EUR", "GBP") => Some(0.5),
+            ("USD", "GBP") => Some(0.25),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("asset_prices.json");
+        let assets = Vec::<AssetPrice>::try_from(json).unwrap();
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0].class_id, 100);
+        assert_eq!(assets[0].prices["USD"], 199);
+        assert!(assets[0].is_on_sale());
+        assert!(!assets[1].is_on_sale());
+    }
+
+    #[test]
+    fn converts() {
+        let json: Response = load_test_json!("asset_prices.json");
+        let assets = Vec::<AssetPrice>::try_from(json).unwrap();
+
+        assert_eq!(assets[0].price_in("GBP", &rates), Some(159.0));
+        // converts from EUR, which comes before USD
+        assert_eq!(assets[1].price_in("GBP", &rates), Some(224.5));
+        assert_eq!(assets[1].price_in("JPY", &rates), None);
+
+        let normalized = assets[0].normalized_prices("GBP", &rates);
+        assert_eq!(normalized.len(), 3);
+        assert_eq!(normalized["GBP"], 159.0);
+        assert_eq!(normalized["EUR"], 89.5);
+        assert_eq!(normalized["USD"], 49.75);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+
+        let client = MockSteam::start().await.client().await;
+        assert_eq!(
+            client
+                .get_asset_prices(480, Some("USD"))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
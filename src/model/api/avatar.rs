@@ -0,0 +1,237 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::constants::DEFAULT_AVATAR_HASHES;
+
+#[derive(Error, Debug)]
+pub enum AvatarError {
+    /// Neither a hash nor an avatar url that contains one
+    #[error("not an avatar hash or url: {0}")]
+    InvalidHash(String),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+type Result<T> = std::result::Result<T, AvatarError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AvatarSize {
+    /// 32x32
+    Small,
+    /// 64x64
+    Medium,
+    /// 184x184
+    Full,
+}
+
+impl AvatarSize {
+    /// What's appended to the hash in the file name
+    pub const fn suffix(&self) -> &'static str {
+        match self {
+            AvatarSize::Small => "",
+            AvatarSize::Medium => "_medium",
+            AvatarSize::Full => "_full",
+        }
+    }
+}
+
+fn is_avatar_hash(hash: &str) -> bool {
+    hash.len() == 40 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// The hash of an avatar, either given directly or taken from an avatar url like
+/// `https://avatars.steamstatic.com/fef49e7fa7e1997310d705b2a6158ff8dc1cdfeb_full.jpg`
+pub fn avatar_hash(hash_or_url: &str) -> Option<String> {
+    let file_name = hash_or_url.rsplit('/').next()?;
+    let hash = file_name.get(..40)?;
+    is_avatar_hash(hash).then(|| hash.to_ascii_lowercase())
+}
+
+/// Whether the avatar is one of the [`DEFAULT_AVATAR_HASHES`], [`None`] if `hash_or_url`
+/// is neither a hash nor an avatar url
+pub fn is_default_avatar(hash_or_url: &str) -> Option<bool> {
+    let hash = avatar_hash(hash_or_url)?;
+    Some(DEFAULT_AVATAR_HASHES.contains(&hash.as_str()))
+}
+
+impl Client {
+    /// The url of the avatar with the given hash in the given size
+    pub fn avatar_url(&self, hash: &str, size: AvatarSize) -> String {
+        format!("{}/{}{}.jpg", self.avatar_base_url(), hash, size.suffix())
+    }
+
+    /// Download an avatar, see [`PlayerSummary`](crate::api::PlayerSummary) for the hash.
+    ///
+    /// Uses the connection pool of the client. If a cache directory was set with
+    /// [`ClientBuilder::avatar_cache_dir`](crate::ClientBuilder::avatar_cache_dir),
+    /// avatars are read from there if they were downloaded before, since their
+    /// hash changes with the image.
+    pub async fn download_avatar(&self, hash_or_url: &str, size: AvatarSize) -> Result<Vec<u8>> {
+        let hash = avatar_hash(hash_or_url)
+            .ok_or_else(|| AvatarError::InvalidHash(hash_or_url.to_string()))?;
+
+        let cached = self
+            .avatar_cache_dir()
+            .map(|dir| dir.join(format!("{}{}.jpg", hash, size.suffix())));
+        if let Some(cached) = &cached {
+            match tokio::fs::read(cached).await {
+                Ok(bytes) => return Ok(bytes),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let bytes = self
+            .clone_client()
+            .get(self.avatar_url(&hash, size))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec();
+
+        if let Some(cached) = cached {
+            // write to a temporary file first, so a concurrent read never sees half an image,
+            // named uniquely so concurrent downloads of the same avatar don't share it
+            static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let tmp = cached.with_extension(format!(
+                "jpg.{}-{}.part",
+                std::process::id(),
+                TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            if let Some(dir) = cached.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&tmp, &bytes).await?;
+            tokio::fs::rename(&tmp, &cached).await?;
+        }
+        Ok(bytes)
+    }
+
+    /// Download an avatar like [`Client::download_avatar`] and write it to `path`
+    pub async fn download_avatar_to(
+        &self,
+        hash_or_url: &str,
+        size: AvatarSize,
+        path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let bytes = self.download_avatar(hash_or_url, size).await?;
+        tokio::fs::write(path, bytes).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{avatar_hash, is_default_avatar};
+
+    const HASH: &str = "fef49e7fa7e1997310d705b2a6158ff8dc1cdfeb";
+
+    #[test]
+    fn parses_hash() {
+        assert_eq!(avatar_hash(HASH).as_deref(), Some(HASH));
+        let url = format!("https://avatars.steamstatic.com/{}_full.jpg", HASH);
+        assert_eq!(avatar_hash(&url).as_deref(), Some(HASH));
+        assert_eq!(avatar_hash("https://avatars.steamstatic.com/"), None);
+        assert_eq!(avatar_hash("not a hash"), None);
+    }
+
+    #[test]
+    fn detects_default() {
+        assert_eq!(is_default_avatar(HASH), Some(true));
+        let url = format!(
+            "https://avatars.steamstatic.com/{}_medium.jpg",
+            HASH.to_uppercase()
+        );
+        assert_eq!(is_default_avatar(&url), Some(true));
+        assert_eq!(
+            is_default_avatar("3cc9dd106fc8b72237056f37c4e9597d27637c6a"),
+            Some(false)
+        );
+        assert_eq!(is_default_avatar(""), None);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn downloads_and_caches() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use super::AvatarSize;
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}_full.jpg", HASH)))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg".to_vec()))
+            .expect(1)
+            .mount(steam.server())
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("steam_api_avatars_{}", std::process::id()));
+        let client = steam
+            .client_builder()
+            .avatar_cache_dir(&dir)
+            .build()
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let bytes = client
+                .download_avatar(HASH, AvatarSize::Full)
+                .await
+                .unwrap();
+            assert_eq!(bytes, b"jpeg");
+        }
+        assert!(dir.join(format!("{}_full.jpg", HASH)).exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn caches_concurrent_downloads() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use super::AvatarSize;
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}_medium.jpg", HASH)))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"jpeg".to_vec()))
+            .mount(steam.server())
+            .await;
+
+        let dir = std::env::temp_dir().join(format!(
+            "steam_api_avatars_concurrent_{}",
+            std::process::id()
+        ));
+        let client = steam
+            .client_builder()
+            .avatar_cache_dir(&dir)
+            .build()
+            .await
+            .unwrap();
+
+        // every download misses the cache and writes the file
+        let downloads = (0..8).map(|_| client.download_avatar(HASH, AvatarSize::Medium));
+        for bytes in futures::future::join_all(downloads).await {
+            assert_eq!(bytes.unwrap(), b"jpeg");
+        }
+        let files = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files, [format!("{}_medium.jpg", HASH).as_str()]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
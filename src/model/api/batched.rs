@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+
+use crate::model::api::{PlayerBans, PlayerSummaries};
+use crate::model::{Endpoint, SteamId};
+
+/// The result of an endpoint that takes a list of [`SteamId`]s,
+/// of which at most [`Batched::MAX_IDS`] fit in one request
+///
+/// [`Endpoint::max_ids_per_request`] reads the limit from here.
+pub trait Batched {
+    const ENDPOINT: Endpoint;
+    const MAX_IDS: usize;
+
+    /// Split `ids` into chunks that fit in one request each
+    fn chunks(ids: &[SteamId]) -> std::slice::Chunks<'_, SteamId> {
+        ids.chunks(Self::MAX_IDS)
+    }
+
+    /// Sort and deduplicate `ids`, [`None`] if there are still too many for one request
+    fn prepare_ids(ids: Cow<'_, [SteamId]>) -> Option<Vec<SteamId>> {
+        let mut ids = ids.into_owned();
+        ids.sort_unstable();
+        ids.dedup();
+        (ids.len() <= Self::MAX_IDS).then_some(ids)
+    }
+}
+
+impl Batched for PlayerSummaries {
+    const ENDPOINT: Endpoint = Endpoint::PlayerSummaries;
+    const MAX_IDS: usize = 100;
+}
+
+impl Batched for PlayerBans {
+    const ENDPOINT: Endpoint = Endpoint::PlayerBans;
+    const MAX_IDS: usize = 100;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Batched;
+    use crate::model::api::{PlayerBans, PlayerSummaries};
+    use crate::SteamId;
+
+    fn limit<B: Batched>() {
+        assert_eq!(B::ENDPOINT.max_ids_per_request(), Some(B::MAX_IDS));
+
+        let ids = (0..B::MAX_IDS as u64 + 1)
+            .map(|id| SteamId(76561197960265728 + id))
+            .collect::<Vec<_>>();
+        assert_eq!(B::chunks(&ids).count(), 2);
+        assert!(B::prepare_ids(ids.as_slice().into()).is_none());
+        assert_eq!(
+            B::prepare_ids(ids[..2].repeat(2).into()),
+            Some(ids[..2].to_vec())
+        );
+    }
+
+    #[test]
+    fn limits_ids() {
+        limit::<PlayerSummaries>();
+        limit::<PlayerBans>();
+    }
+}
@@ -0,0 +1,336 @@
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::serde_util::from_str_or_number;
+use crate::model::{Endpoint, SteamId, SteamIdStr, SteamTime};
+
+#[derive(Error, Debug)]
+pub enum CheatReportingError {
+    /// See [`ClientBuilder::publisher_key`](crate::ClientBuilder::publisher_key)
+    #[error("client has no publisher key")]
+    PublisherKey,
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, CheatReportingError>;
+
+/// A report for [`Client::report_player_cheating`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheatReport {
+    pub steam_id: SteamId,
+    pub app_id: u32,
+    /// The player that reported, if it's a player report
+    pub reporter: Option<SteamId>,
+    /// Found by a heuristic of the game
+    pub heuristic: bool,
+    /// Found by an anti-cheat detection
+    pub detection: bool,
+    /// Reported by another player
+    pub player_report: bool,
+    pub game_mode: Option<u32>,
+    /// Game defined, higher is more severe
+    pub severity: Option<u32>,
+    /// Game defined data that's kept with the report
+    pub app_data: Option<u64>,
+}
+
+impl CheatReport {
+    pub const fn new(steam_id: SteamId, app_id: u32) -> Self {
+        Self {
+            steam_id,
+            app_id,
+            reporter: None,
+            heuristic: false,
+            detection: false,
+            player_report: false,
+            game_mode: None,
+            severity: None,
+            app_data: None,
+        }
+    }
+}
+
+/// A ban request for [`Client::request_player_game_ban`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GameBanRequest {
+    pub steam_id: SteamId,
+    pub app_id: u32,
+    /// The report the ban is based on, see [`Client::report_player_cheating`]
+    pub report_id: u64,
+    /// Shown to the player
+    pub description: String,
+    /// Length of the ban in seconds, [`None`] for a permanent ban
+    pub duration: Option<u64>,
+    /// Don't ban the player until the next ban wave
+    pub delay_ban: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheatingReport {
+    #[serde(rename(deserialize = "reportid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub report_id: u64,
+    #[serde(rename(deserialize = "steamid"))]
+    pub steam_id: SteamIdStr,
+    #[serde(rename(deserialize = "steamidreporter"))]
+    pub reporter: Option<SteamIdStr>,
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    #[serde(rename(deserialize = "timereported"))]
+    pub time_reported: SteamTime,
+    #[serde(default)]
+    pub heuristic: bool,
+    #[serde(default)]
+    pub detection: bool,
+    #[serde(default)]
+    #[serde(rename(deserialize = "playerreport"))]
+    pub player_report: bool,
+    #[serde(rename(deserialize = "gamemode"))]
+    pub game_mode: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ReportInner {
+    #[serde(deserialize_with = "from_str_or_number")]
+    reportid: u64,
+    #[serde(default, rename(deserialize = "suspicionstarttime"))]
+    _suspicion_start_time: IgnoredAny,
+    #[serde(default, rename(deserialize = "duration"))]
+    _duration: IgnoredAny,
+}
+
+type ReportResponse = Envelope<ReportInner>;
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ReportsInner {
+    #[serde(default)]
+    reports: Vec<CheatingReport>,
+}
+
+type ReportsResponse = Envelope<ReportsInner>;
+
+const fn flag(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+impl Client {
+    fn cheat_reporting_key(&self) -> Result<&str> {
+        self.publisher_key()
+            .ok_or(CheatReportingError::PublisherKey)
+    }
+
+    /// Report a player for cheating, returns the id of the report
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Sent only once, see [`Client::post_form_json_once`], a failed request may have
+    /// filed the report anyway
+    ///
+    /// Uses [`Endpoint::ReportPlayerCheating`]
+    pub async fn report_player_cheating(&self, report: &CheatReport) -> Result<u64> {
+        let key = self.cheat_reporting_key()?;
+        let steam_id = report.steam_id.to_string();
+        let app_id = report.app_id.to_string();
+        let reporter = report.reporter.map(|id| id.to_string());
+        let game_mode = report.game_mode.map(|mode| mode.to_string());
+        let severity = report.severity.map(|severity| severity.to_string());
+        let app_data = report.app_data.map(|data| data.to_string());
+
+        let mut form = vec![
+            ("key", key),
+            ("steamid", steam_id.as_str()),
+            ("appid", app_id.as_str()),
+            ("heuristic", flag(report.heuristic)),
+            ("detection", flag(report.detection)),
+            ("playerreport", flag(report.player_report)),
+        ];
+        let optional = [
+            ("steamidreporter", &reporter),
+            ("gamemode", &game_mode),
+            ("severity", &severity),
+            ("appdata", &app_data),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = value {
+                form.push((name, value));
+            }
+        }
+
+        let json = self
+            .post_form_json_once::<ReportResponse>(Endpoint::ReportPlayerCheating, &form)
+            .await?;
+        Ok(json.into_inner().reportid)
+    }
+
+    /// Ban a player from the game for a previous report
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Sent only once, see [`Client::post_form_json_once`], a failed request may have
+    /// requested the ban anyway
+    ///
+    /// Uses [`Endpoint::RequestPlayerGameBan`]
+    pub async fn request_player_game_ban(&self, request: &GameBanRequest) -> Result<()> {
+        let key = self.cheat_reporting_key()?;
+        let steam_id = request.steam_id.to_string();
+        let app_id = request.app_id.to_string();
+        let report_id = request.report_id.to_string();
+        let duration = request.duration.unwrap_or(0).to_string();
+
+        let form = [
+            ("key", key),
+            ("steamid", steam_id.as_str()),
+            ("appid", app_id.as_str()),
+            ("reportid", report_id.as_str()),
+            ("cheatdescription", request.description.as_str()),
+            ("duration", duration.as_str()),
+            ("delayban", flag(request.delay_ban)),
+            ("flags", "0"),
+        ];
+
+        self.post_form_json_once::<serde_json::Value>(Endpoint::RequestPlayerGameBan, &form)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the reports of an app in the given time range, optionally only those of one player
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Uses [`Endpoint::CheatingReports`]
+    pub async fn get_cheating_reports(
+        &self,
+        app_id: u32,
+        begin: DateTime<Utc>,
+        end: DateTime<Utc>,
+        steam_id: Option<SteamId>,
+    ) -> Result<Vec<CheatingReport>> {
+        let key = self.cheat_reporting_key()?;
+        let app_id = app_id.to_string();
+        let begin = begin.timestamp().to_string();
+        let end = end.timestamp().to_string();
+        let steam_id = steam_id.map(|id| id.to_string());
+
+        let mut query = vec![
+            ("key", key),
+            ("appid", app_id.as_str()),
+            ("timebegin", begin.as_str()),
+            ("timeend", end.as_str()),
+            ("includereports", "1"),
+            ("includebans", "0"),
+        ];
+        if let Some(steam_id) = &steam_id {
+            query.push(("steamid", steam_id));
+        }
+
+        let json = self
+            .get_json::<ReportsResponse>(Endpoint::CheatingReports, &query)
+            .await?;
+        Ok(json.into_inner().reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReportResponse, ReportsResponse};
+
+    #[test]
+    fn parses() {
+        let json: ReportResponse = load_test_json!("report_player_cheating.json");
+        assert_eq!(json.into_inner().reportid, 8427194661);
+
+        let json: ReportsResponse = load_test_json!("cheating_reports.json");
+        let reports = json.into_inner().reports;
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].report_id, 8427194661);
+        assert_eq!(reports[0].reporter.unwrap().0, 76561198196615742);
+        assert!(reports[0].player_report);
+        assert_eq!(reports[1].reporter, None);
+        assert!(reports[1].heuristic);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn reports_and_bans() {
+        use chrono::{Duration, Utc};
+
+        use super::{CheatReport, GameBanRequest};
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let client = MockSteam::start().await.client().await;
+        let cheater = SteamId(76561199063760869);
+
+        let report = CheatReport {
+            player_report: true,
+            reporter: Some(SteamId(76561198196615742)),
+            ..CheatReport::new(cheater, 480)
+        };
+        let report_id = client.report_player_cheating(&report).await.unwrap();
+        assert_eq!(report_id, 8427194661);
+
+        let ban = GameBanRequest {
+            steam_id: cheater,
+            app_id: 480,
+            report_id,
+            description: "aimbot".to_string(),
+            duration: None,
+            delay_ban: true,
+        };
+        client.request_player_game_ban(&ban).await.unwrap();
+
+        let now = Utc::now();
+        let reports = client
+            .get_cheating_reports(480, now - Duration::days(1), now, Some(cheater))
+            .await
+            .unwrap();
+        assert_eq!(reports.len(), 2);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn reports_and_bans_are_sent_once() {
+        use super::{CheatReport, CheatReportingError, GameBanRequest};
+        use crate::testing::MockSteam;
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start().await;
+        steam
+            .mount_status(Endpoint::ReportPlayerCheating, 500, 4)
+            .await;
+        steam
+            .mount_status(Endpoint::RequestPlayerGameBan, 500, 4)
+            .await;
+        let client = steam.client().await;
+        let cheater = SteamId(76561199063760869);
+
+        let report = client
+            .report_player_cheating(&CheatReport::new(cheater, 480))
+            .await;
+        assert!(matches!(report, Err(CheatReportingError::Reqwest(err)) if err.status().is_some()));
+        assert_eq!(steam.received(Endpoint::ReportPlayerCheating).await, 1);
+
+        let ban = GameBanRequest {
+            steam_id: cheater,
+            app_id: 480,
+            report_id: 8427194661,
+            description: "aimbot".to_string(),
+            duration: None,
+            delay_ban: false,
+        };
+        assert!(client.request_player_game_ban(&ban).await.is_err());
+        assert_eq!(steam.received(Endpoint::RequestPlayerGameBan).await, 1);
+        assert_eq!(client.total_retries(), 0);
+    }
+}
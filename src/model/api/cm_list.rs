@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::Endpoint;
+
+#[derive(Error, Debug)]
+pub enum CmListError {
+    /// Steam answered, but didn't return a list
+    #[error("couldn't get the cm list: {0}")]
+    Failed(String),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, CmListError>;
+
+/// Connection managers, the servers Steam clients connect to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CmList {
+    /// `ip:port` of the servers that take tcp/udp connections
+    pub servers: Vec<String>,
+    /// `host:port` of the servers that take websocket connections
+    pub websockets: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CmType {
+    /// Takes tcp/udp connections
+    Netfilter,
+    Websockets,
+    /// A type this crate doesn't know about yet
+    #[serde(other)]
+    Other,
+}
+
+impl CmType {
+    /// The name Steam uses for the type
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            CmType::Netfilter => "netfilter",
+            CmType::Websockets => "websockets",
+            CmType::Other => "other",
+        }
+    }
+}
+
+/// A connection manager from [`Client::get_cm_list_for_connect`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CmServer {
+    /// `host:port` to connect to
+    pub endpoint: String,
+    /// `ip:port` to connect to
+    pub legacy_endpoint: String,
+    #[serde(rename = "type")]
+    pub cm_type: CmType,
+    /// Data center, e.g. `fra1`
+    pub dc: String,
+    pub realm: String,
+    pub load: u32,
+    /// Load weighted by the distance to the requested cell, lower is better
+    pub wtd_load: f64,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct CmListInner {
+    #[serde(default)]
+    serverlist: Vec<String>,
+    #[serde(default)]
+    serverlist_websockets: Vec<String>,
+    result: u32,
+    #[serde(default)]
+    message: String,
+}
+
+type CmListResponse = Envelope<CmListInner>;
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ForConnectInner {
+    #[serde(default)]
+    serverlist: Vec<CmServer>,
+    success: bool,
+    #[serde(default)]
+    message: String,
+}
+
+type ForConnectResponse = Envelope<ForConnectInner>;
+
+impl TryFrom<CmListResponse> for CmList {
+    type Error = CmListError;
+    fn try_from(value: CmListResponse) -> Result<Self> {
+        let inner = value.into_inner();
+        if inner.result != 1 {
+            return Err(CmListError::Failed(inner.message));
+        }
+        Ok(CmList {
+            servers: inner.serverlist,
+            websockets: inner.serverlist_websockets,
+        })
+    }
+}
+
+impl TryFrom<ForConnectResponse> for Vec<CmServer> {
+    type Error = CmListError;
+    fn try_from(value: ForConnectResponse) -> Result<Self> {
+        let inner = value.into_inner();
+        if !inner.success {
+            return Err(CmListError::Failed(inner.message));
+        }
+        Ok(inner.serverlist)
+    }
+}
+
+impl Client {
+    /// Get the connection managers for the given cell, the region a client is in.
+    ///
+    /// Cell `0` lets Steam pick based on the ip of the request.
+    ///
+    /// Uses [`Endpoint::CmList`]
+    pub async fn get_cm_list(&self, cell_id: u32, max_count: Option<u32>) -> Result<CmList> {
+        let cell_id = cell_id.to_string();
+        let max_count = max_count.map(|count| count.to_string());
+
+        let mut query = vec![("cellid", cell_id.as_str())];
+        if let Some(max_count) = &max_count {
+            query.push(("maxcount", max_count));
+        }
+
+        self.get_json::<CmListResponse>(Endpoint::CmList, &query)
+            .await?
+            .try_into()
+    }
+
+    /// Get the connection managers sorted by how well suited they are,
+    /// optionally only those of the given type.
+    ///
+    /// Uses [`Endpoint::CmListForConnect`]
+    pub async fn get_cm_list_for_connect(
+        &self,
+        cell_id: Option<u32>,
+        cm_type: Option<CmType>,
+        max_count: Option<u32>,
+    ) -> Result<Vec<CmServer>> {
+        let cell_id = cell_id.map(|id| id.to_string());
+        let max_count = max_count.map(|count| count.to_string());
+
+        let mut query = Vec::new();
+        if let Some(cell_id) = &cell_id {
+            query.push(("cellid", cell_id.as_str()));
+        }
+        if let Some(cm_type) = cm_type {
+            query.push(("cmtype", cm_type.as_str()));
+        }
+        if let Some(max_count) = &max_count {
+            query.push(("maxcount", max_count));
+        }
+
+        self.get_json::<ForConnectResponse>(Endpoint::CmListForConnect, &query)
+            .await?
+            .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CmList, CmListResponse, CmServer, CmType, ForConnectResponse};
+
+    #[test]
+    fn parses() {
+        let json: CmListResponse = load_test_json!("cm_list.json");
+        let list = CmList::try_from(json).unwrap();
+        assert_eq!(list.servers.len(), 3);
+        assert_eq!(list.servers[0], "162.254.197.40:27017");
+        assert_eq!(list.websockets[0], "cmp1-fra1.steamserver.net:443");
+    }
+
+    #[test]
+    fn parses_for_connect() {
+        let json: ForConnectResponse = load_test_json!("cm_list_for_connect.json");
+        let servers = Vec::<CmServer>::try_from(json).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].cm_type, CmType::Netfilter);
+        assert_eq!(servers[1].cm_type, CmType::Websockets);
+        assert_eq!(servers[1].dc, "fra1");
+    }
+
+    #[test]
+    fn fails() {
+        let json: CmListResponse =
+            serde_json::from_str(r#"{"response":{"result":8,"message":"Invalid CellID"}}"#)
+                .unwrap();
+        assert!(CmList::try_from(json).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+
+        let client = MockSteam::start().await.client().await;
+        let list = client.get_cm_list(0, Some(3)).await.unwrap();
+        assert_eq!(list.servers.len(), 3);
+        let servers = client
+            .get_cm_list_for_connect(None, Some(CmType::Websockets), None)
+            .await
+            .unwrap();
+        assert_eq!(servers.len(), 2);
+    }
+}
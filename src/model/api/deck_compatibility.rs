@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::{DeckCompatibility, Endpoint};
+
+#[derive(Error, Debug)]
+pub enum DeckCompatibilityError {
+    /// The store answered with a `success` other than `1`
+    #[error("request failed with success {0}")]
+    Failed(u32),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, DeckCompatibilityError>;
+
+/// One of the tests Valve ran the app through, as listed on the store page
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeckTestResult {
+    /// How the result is shown, `4` for a passed test, `3` for a caveat and `2` for a failure
+    pub display_type: u32,
+    /// Localization token of the description,
+    /// e.g. `#SteamDeckVerified_TestResult_InterfaceTextIsLegible`
+    pub loc_token: String,
+}
+
+/// See [`Client::get_deck_compatibility`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeckCompatibilityReport {
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    #[serde(rename(deserialize = "resolved_category"))]
+    pub category: DeckCompatibility,
+    #[serde(rename(deserialize = "resolved_items"))]
+    #[serde(default)]
+    pub results: Vec<DeckTestResult>,
+}
+
+impl DeckCompatibilityReport {
+    /// The report of an app Valve hasn't tested
+    const fn unknown(app_id: u32) -> Self {
+        DeckCompatibilityReport {
+            app_id,
+            category: DeckCompatibility::Unknown,
+            results: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct Response {
+    success: u32,
+    /// Missing for apps that don't exist
+    #[serde(default)]
+    results: Option<DeckCompatibilityReport>,
+}
+
+impl Response {
+    fn into_report(self, app_id: u32) -> Result<DeckCompatibilityReport> {
+        if self.success != 1 {
+            return Err(DeckCompatibilityError::Failed(self.success));
+        }
+        Ok(self
+            .results
+            .unwrap_or_else(|| DeckCompatibilityReport::unknown(app_id)))
+    }
+}
+
+impl Client {
+    /// Get the Steam Deck compatibility category of an app and the tests it's based on,
+    /// which needs no api-key
+    ///
+    /// Apps Valve hasn't tested, or that don't exist, are [`DeckCompatibility::Unknown`].
+    ///
+    /// Uses [`Endpoint::DeckCompatibilityReport`]
+    pub async fn get_deck_compatibility(&self, app_id: u32) -> Result<DeckCompatibilityReport> {
+        let app_id_str = app_id.to_string();
+        let query = [("nAppID", app_id_str.as_str()), ("l", "english")];
+
+        let json = self
+            .get_json::<Response>(Endpoint::DeckCompatibilityReport, &query)
+            .await?;
+        json.into_report(app_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeckCompatibilityError, Response};
+    use crate::model::DeckCompatibility;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("deck_compatibility.json");
+        let report = json.into_report(620).unwrap();
+        assert_eq!(report.app_id, 620);
+        assert_eq!(report.category, DeckCompatibility::Verified);
+        assert!(report.category.is_playable());
+        assert_eq!(report.results.len(), 4);
+        assert!(report.results.iter().all(|result| result.display_type == 4));
+    }
+
+    #[test]
+    fn handles_unknown_apps() {
+        let json: Response = serde_json::from_str(r#"{"success":1}"#).unwrap();
+        let report = json.into_report(1).unwrap();
+        assert_eq!(report.category, DeckCompatibility::Unknown);
+        assert!(report.results.is_empty());
+
+        let json: Response = serde_json::from_str(r#"{"success":2}"#).unwrap();
+        assert!(matches!(
+            json.into_report(1),
+            Err(DeckCompatibilityError::Failed(2))
+        ));
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let report = client.get_deck_compatibility(620).await.unwrap();
+        assert_eq!(report.category, DeckCompatibility::Verified);
+        assert_eq!(steam.received(Endpoint::DeckCompatibilityReport).await, 1);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+/// A result wrapped in an object with a single key, e.g. `{"response": {..}}`,
+/// `{"friendslist": {..}}` or `{"players": [..]}`
+///
+/// The name of the key doesn't matter, so every wrapper is parsed the same way.
+/// An empty object parses as `None` if `T` is an [`Option`], Steam sends one e.g. for
+/// the friends of private profiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope<T> {
+    key: String,
+    inner: T,
+}
+
+impl<T> Envelope<T> {
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// The key the result was wrapped in, empty for an empty object
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl<T> Deref for Envelope<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Envelope<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+struct EnvelopeVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for EnvelopeVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Envelope<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with a single key wrapping the result")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let Some(key) = map.next_key::<String>()? else {
+            let inner = T::deserialize(de::value::UnitDeserializer::<A::Error>::new())
+                .map_err(|_| de::Error::custom("empty envelope, expected a single key"))?;
+            return Ok(Envelope {
+                key: String::new(),
+                inner,
+            });
+        };
+        let inner = map.next_value::<T>()?;
+
+        while let Some(other) = map.next_key::<String>()? {
+            if cfg!(feature = "strict_parse") {
+                return Err(de::Error::custom(format!(
+                    "unexpected key `{other}` next to envelope `{key}`"
+                )));
+            }
+            map.next_value::<IgnoredAny>()?;
+        }
+        Ok(Envelope { key, inner })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Envelope<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EnvelopeVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Envelope;
+
+    #[test]
+    fn unwraps_any_key() {
+        let json: Envelope<Vec<u32>> = serde_json::from_str(r#"{"players": [1, 2]}"#).unwrap();
+        assert_eq!(json.key(), "players");
+        assert_eq!(json.len(), 2);
+        assert_eq!(json.into_inner(), [1, 2]);
+
+        let json: Envelope<Option<u32>> = serde_json::from_str("{}").unwrap();
+        assert_eq!(json.into_inner(), None);
+    }
+
+    #[test]
+    fn rejects_missing_envelope() {
+        let err = serde_json::from_str::<Envelope<u32>>("{}").unwrap_err();
+        assert!(err.to_string().starts_with("empty envelope"));
+        let err = serde_json::from_str::<Envelope<u32>>("[1]").unwrap_err();
+        assert!(err.to_string().contains("single key"));
+    }
+
+    #[cfg(feature = "strict_parse")]
+    #[test]
+    fn rejects_extra_keys() {
+        let err =
+            serde_json::from_str::<Envelope<u32>>(r#"{"response": 1, "other": 2}"#).unwrap_err();
+        assert!(err.to_string().contains("`other`"));
+    }
+}
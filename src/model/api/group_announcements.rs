@@ -0,0 +1,52 @@
+use thiserror::Error;
+
+use crate::client::{encode_path_segment, Client};
+use crate::model::xml::rss;
+use crate::model::Endpoint;
+
+#[derive(Debug, Error)]
+pub enum GroupAnnouncementsError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the rss feed
+    #[error("couldn't parse rss feed ({0})")]
+    ParseError(#[from] rss::Error),
+}
+type Result<T> = std::result::Result<T, GroupAnnouncementsError>;
+
+/// An announcement posted in a Steam group
+pub type GroupAnnouncement = rss::FeedEntry;
+
+impl Client {
+    /// Get the announcements of the group with the url name `name`
+    /// (the part after `steamcommunity.com/groups/`), newest first
+    ///
+    /// Uses [`Endpoint::GroupAnnouncements`]
+    pub async fn get_group_announcements(&self, name: &str) -> Result<Vec<GroupAnnouncement>> {
+        let sub_path = format!("{}/rss/", encode_path_segment(name));
+        let xml = self
+            .get_text(Endpoint::GroupAnnouncements, &sub_path, &[])
+            .await?;
+        Ok(rss::parse(&xml)?)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::MockSteam;
+    use crate::Endpoint;
+
+    #[tokio::test]
+    async fn fetches() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let announcements = client
+            .get_group_announcements("steamuniverse")
+            .await
+            .unwrap();
+        assert_eq!(announcements.len(), 2);
+        assert_eq!(steam.received(Endpoint::GroupAnnouncements).await, 1);
+    }
+}
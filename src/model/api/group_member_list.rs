@@ -0,0 +1,133 @@
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::xml::group_member_list;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Debug, Error)]
+pub enum GroupMemberListError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing a page of the member list
+    #[error("couldn't parse member list ({0})")]
+    ParseError(#[from] group_member_list::Error),
+
+    /// The client was shut down before the list was complete, see [`Client::shutdown`]
+    #[error("cancelled")]
+    Cancelled,
+}
+type Result<T> = std::result::Result<T, GroupMemberListError>;
+
+pub use group_member_list::{GroupDetails, GroupMemberPage};
+
+/// Every member of a group, see [`Client::get_group_member_list`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupMemberList {
+    pub group_id: SteamId,
+    /// The details of the first page
+    pub details: GroupDetails,
+    /// In the order of the pages
+    pub members: Vec<SteamId>,
+}
+
+impl Client {
+    /// Get the page `page` (`1`-based) of the member list of the group with the 64-bit id
+    /// `group_id`
+    ///
+    /// Uses [`Endpoint::GroupMemberList`]
+    pub async fn get_group_member_page(
+        &self,
+        group_id: SteamId,
+        page: u32,
+    ) -> Result<GroupMemberPage> {
+        let sub_path = format!("{}/memberslistxml/", group_id);
+        let page = page.to_string();
+        let query = [("xml", "1"), ("p", page.as_str())];
+        let xml = self
+            .get_text(Endpoint::GroupMemberList, &sub_path, &query)
+            .await?;
+        Ok(group_member_list::parse(&xml)?)
+    }
+
+    /// Get every member of the group with the 64-bit id `group_id`, the pages after the first
+    /// are requested concurrently
+    ///
+    /// There is no Web API equivalent, the member list is only available as xml.
+    /// Stops with [`GroupMemberListError::Cancelled`] once the client is
+    /// [shut down](Client::shutdown).
+    ///
+    /// Uses [`Endpoint::GroupMemberList`]
+    pub async fn get_group_member_list(&self, group_id: SteamId) -> Result<GroupMemberList> {
+        let list = async {
+            let first = self.get_group_member_page(group_id, 1).await?;
+            let mut members = first.members;
+            let pages = futures::stream::iter(2..=first.total_pages)
+                .map(|page| self.get_group_member_page(group_id, page))
+                .buffered(Endpoint::GroupMemberList.concurrent_requests())
+                .try_collect::<Vec<_>>()
+                .await?;
+            for page in pages {
+                members.extend(page.members);
+            }
+
+            Ok(GroupMemberList {
+                group_id: first.group_id,
+                details: first.details,
+                members,
+            })
+        };
+        self.until_shutdown(list)
+            .await
+            .ok_or(GroupMemberListError::Cancelled)?
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use wiremock::matchers::query_param;
+    use wiremock::{Mock, ResponseTemplate};
+
+    use crate::testing::MockSteam;
+    use crate::{Endpoint, SteamId};
+
+    #[tokio::test]
+    async fn fetches_every_page() {
+        let steam = MockSteam::start().await;
+        Mock::given(query_param("p", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(load_test_str!("group_member_list_2.xml"), "text/xml"),
+            )
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        let client = steam.client().await;
+
+        let group_id = SteamId(103582791429521412);
+        let list = client.get_group_member_list(group_id).await.unwrap();
+        assert_eq!(list.group_id, group_id);
+        assert_eq!(list.details.url_name, "Valve");
+        assert_eq!(list.members.len(), 5);
+        assert_eq!(list.members[4], SteamId(76561198230177976));
+        assert_eq!(steam.received(Endpoint::GroupMemberList).await, 2);
+    }
+
+    #[tokio::test]
+    async fn stops_when_cancelled() {
+        use super::GroupMemberListError;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        client.shutdown();
+        assert!(client.is_shut_down());
+        let list = client
+            .get_group_member_list(SteamId(103582791429521412))
+            .await;
+        assert!(matches!(list, Err(GroupMemberListError::Cancelled)));
+        assert_eq!(steam.received(Endpoint::GroupMemberList).await, 0);
+    }
+}
@@ -0,0 +1,80 @@
+use thiserror::Error;
+
+use crate::client::{encode_path_segment, Client};
+use crate::model::html::group_members;
+use crate::model::Endpoint;
+
+#[derive(Debug, Error)]
+pub enum GroupOfficersError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the member list
+    #[error("couldn't parse member list ({0})")]
+    ParseError(#[from] group_members::Error),
+}
+type Result<T> = std::result::Result<T, GroupOfficersError>;
+
+pub use group_members::{GroupOfficer, GroupRole};
+
+impl Client {
+    /// Get the owner, officers and moderators of the group with the url name `name`
+    /// (the part after `steamcommunity.com/groups/`)
+    ///
+    /// They're listed before the other members on the first page, groups with more
+    /// officers than fit on it aren't supported.
+    ///
+    /// Uses [`Endpoint::GroupMembers`]
+    pub async fn get_group_officers(&self, name: &str) -> Result<Vec<GroupOfficer>> {
+        let sub_path = format!("{}/members/", encode_path_segment(name));
+        let html = self
+            .get_text(Endpoint::GroupMembers, &sub_path, &[])
+            .await?;
+        let parser = group_members::Parser::new()?;
+        Ok(parser.parse(&html)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupOfficer, GroupRole};
+    use crate::model::html::group_members::Parser;
+    use crate::SteamId;
+
+    #[test]
+    fn parses() {
+        let html = include_str!("../../../test_resources/group_members.html");
+        let officers = Parser::new().unwrap().parse(html).unwrap();
+        assert_eq!(
+            officers
+                .iter()
+                .map(|officer| officer.role)
+                .collect::<Vec<_>>(),
+            [GroupRole::Owner, GroupRole::Officer, GroupRole::Moderator]
+        );
+        assert_eq!(
+            officers[1],
+            GroupOfficer {
+                steam_id: SteamId(76561198089612262),
+                role: GroupRole::Officer,
+                persona_name: "Officer & Gentleman".to_string(),
+            }
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn fetches() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let officers = client.get_group_officers("steamuniverse").await.unwrap();
+        assert_eq!(officers.len(), 3);
+        assert_eq!(officers[0].steam_id, SteamId(76561197960287930));
+        assert_eq!(steam.received(Endpoint::GroupMembers).await, 1);
+        assert_eq!(steam.received(Endpoint::GroupAnnouncements).await, 0);
+    }
+}
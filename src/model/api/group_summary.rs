@@ -0,0 +1,91 @@
+use thiserror::Error;
+
+use crate::client::{encode_path_segment, Client};
+use crate::model::html::group_page;
+use crate::model::Endpoint;
+
+#[derive(Debug, Error)]
+pub enum GroupSummaryError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the group page
+    #[error("couldn't parse group page ({0})")]
+    ParseError(#[from] group_page::Error),
+}
+type Result<T> = std::result::Result<T, GroupSummaryError>;
+
+pub use group_page::GroupSummary;
+
+impl Client {
+    /// Get the name, headline, member counts and id of the group with the url name `name`
+    /// (the part after `steamcommunity.com/groups/`)
+    ///
+    /// Uses [`Endpoint::GroupPage`]
+    pub async fn get_group_summary(&self, name: &str) -> Result<GroupSummary> {
+        let sub_path = format!("{}/", encode_path_segment(name));
+        let html = self.get_text(Endpoint::GroupPage, &sub_path, &[]).await?;
+        let parser = group_page::Parser::new()?;
+        Ok(parser.parse(&html)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GroupSummary;
+    use crate::model::html::group_page::{Error, Parser};
+    use crate::SteamId;
+
+    #[test]
+    fn parses() {
+        let html = include_str!("../../../test_resources/group_page.html");
+        let summary = Parser::new().unwrap().parse(html).unwrap();
+        assert_eq!(
+            summary,
+            GroupSummary {
+                group_id: SteamId(103582791434672565),
+                name: "Steam Universe".to_string(),
+                abbreviation: Some("SteamU".to_string()),
+                headline: Some("Official group of the Steam Universe & friends".to_string()),
+                member_count: 1274503,
+                members_in_game: 21377,
+                members_online: 98204,
+                members_in_chat: Some(412),
+            }
+        );
+    }
+
+    #[test]
+    fn fails_on_errors() {
+        let parser = Parser::new().unwrap();
+        let html = r#"<div class="error_ctn"><h3>No group could be retrieved for the given URL.</h3></div>"#;
+        assert!(
+            matches!(parser.parse(html), Err(Error::Steam(msg)) if msg.starts_with("No group"))
+        );
+
+        let html =
+            include_str!("../../../test_resources/group_page.html").replace("1,274,503", "n/a");
+        assert!(matches!(
+            parser.parse(&html),
+            Err(Error::InvalidCount {
+                field: "member count",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn fetches() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let summary = client.get_group_summary("steamuniverse").await.unwrap();
+        assert_eq!(summary.group_id, SteamId(103582791434672565));
+        assert_eq!(steam.received(Endpoint::GroupPage).await, 1);
+        assert_eq!(steam.received(Endpoint::GroupMembers).await, 0);
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use futures::join;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use crate::client::Client;
+use crate::model::{Endpoint, Host};
+
+/// Reachability of a single [`Host`]
+#[derive(Serialize, Debug, Clone)]
+pub struct HostHealth {
+    pub host: Host,
+    /// The endpoint that was pinged
+    pub endpoint: Endpoint,
+    /// Time until the response headers arrived, or until the request failed
+    pub latency: Duration,
+    /// [`None`], if no response arrived at all
+    pub status: Option<u16>,
+    /// Why the request failed, if it did
+    pub error: Option<String>,
+}
+
+impl HostHealth {
+    /// A response arrived and it isn't a server error or a rate limit
+    pub fn is_reachable(&self) -> bool {
+        self.status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .is_some_and(|status| {
+                !status.is_server_error() && status != StatusCode::TOO_MANY_REQUESTS
+            })
+    }
+}
+
+/// See [`Client::health_check`]
+#[derive(Serialize, Debug, Clone)]
+pub struct HealthReport {
+    pub api: HostHealth,
+    pub community: HostHealth,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.api.is_reachable() && self.community.is_reachable()
+    }
+}
+
+impl Client {
+    async fn ping(&self, endpoint: Endpoint) -> HostHealth {
+        let start = Instant::now();
+        let resp = self
+            .clone_client()
+            .get(self.endpoint_url(endpoint))
+            .send()
+            .await;
+        let latency = start.elapsed();
+
+        let (status, error) = match resp {
+            Ok(resp) => (Some(resp.status().as_u16()), None),
+            Err(err) => (err.status().map(|s| s.as_u16()), Some(err.to_string())),
+        };
+        HostHealth {
+            host: endpoint.host(),
+            endpoint,
+            latency,
+            status,
+            error,
+        }
+    }
+
+    /// Ping a lightweight endpoint of each [`Host`] once, without retries.
+    ///
+    /// Long-running crawlers can use this to decide whether to pause.
+    /// The community host answers the ping with `401 Unauthorized`, which counts as reachable.
+    ///
+    /// Uses [`Endpoint::ServerInfo`] and [`Endpoint::UserSearch`]
+    pub async fn health_check(&self) -> HealthReport {
+        let (api, community) = join!(
+            self.ping(Endpoint::ServerInfo),
+            self.ping(Endpoint::UserSearch)
+        );
+        HealthReport { api, community }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::MockSteam;
+    use crate::{Endpoint, Host};
+
+    #[tokio::test]
+    async fn reports_hosts() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let report = client.health_check().await;
+        assert!(report.is_healthy());
+        assert_eq!(report.api.host, Host::Api);
+        assert_eq!(report.api.status, Some(200));
+        assert_eq!(report.community.status, Some(401));
+
+        steam.mount_status(Endpoint::ServerInfo, 503, 1).await;
+        let report = client.health_check().await;
+        assert!(!report.api.is_reachable());
+        assert!(report.community.is_reachable());
+    }
+}
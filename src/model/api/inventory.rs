@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::serde_util::from_str_or_number;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Error, Debug)]
+pub enum InventoryError {
+    /// See [`ClientBuilder::publisher_key`](crate::ClientBuilder::publisher_key)
+    #[error("client has no publisher key")]
+    PublisherKey,
+
+    /// The items or item definitions aren't valid json
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, InventoryError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InventoryItem {
+    #[serde(rename(deserialize = "accountid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub account_id: u32,
+    #[serde(rename(deserialize = "itemid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub item_id: u64,
+    pub quantity: u32,
+    /// The id of the item before it was e.g. traded or split
+    #[serde(rename(deserialize = "originalitemid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub original_item_id: u64,
+    #[serde(rename(deserialize = "itemdefid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub item_def_id: u32,
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    /// When the item was acquired, e.g. `20231013T151200Z`
+    pub acquired: String,
+    pub state: String,
+    /// How the item was acquired, e.g. `external` or `playtime`
+    pub origin: String,
+}
+
+/// An item definition from the schema of an app, see [`Client::get_item_defs`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ItemDef {
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    #[serde(rename(deserialize = "itemdefid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub item_def_id: u32,
+    /// `item`, `bundle`, `generator`, `playtimegenerator` or `tag_generator`
+    #[serde(rename = "type")]
+    pub item_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tradable: bool,
+    #[serde(default)]
+    pub marketable: bool,
+    /// Every other property of the definition, they're up to the developer
+    #[serde(flatten)]
+    pub properties: HashMap<String, serde_json::Value>,
+}
+
+/// The items are a json string inside the json response
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ItemsInner {
+    item_json: String,
+}
+
+type ItemsResponse = Envelope<ItemsInner>;
+
+impl TryFrom<ItemsResponse> for Vec<InventoryItem> {
+    type Error = InventoryError;
+    fn try_from(value: ItemsResponse) -> Result<Self> {
+        Ok(serde_json::from_str(&value.item_json)?)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct MetaInner {
+    #[serde(default, rename(deserialize = "modified"))]
+    _modified: IgnoredAny,
+    digest: String,
+}
+
+type MetaResponse = Envelope<MetaInner>;
+
+/// The archive is sometimes terminated with a nul byte
+fn parse_item_defs(archive: &str) -> Result<Vec<ItemDef>> {
+    Ok(serde_json::from_str(archive.trim_end_matches('\0'))?)
+}
+
+impl Client {
+    fn inventory_key(&self) -> Result<&str> {
+        self.publisher_key().ok_or(InventoryError::PublisherKey)
+    }
+
+    /// Get the items a player has in an app
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Uses [`Endpoint::Inventory`]
+    pub async fn get_inventory(
+        &self,
+        app_id: u32,
+        steam_id: SteamId,
+    ) -> Result<Vec<InventoryItem>> {
+        let key = self.inventory_key()?;
+        let query = [
+            ("key", key),
+            ("appid", &app_id.to_string()),
+            ("steamid", &steam_id.to_string()),
+        ];
+
+        self.get_json::<ItemsResponse>(Endpoint::Inventory, &query)
+            .await?
+            .try_into()
+    }
+
+    /// Grant a player one item of each of the given definitions, returns the new items
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Sent only once, see [`Client::post_form_json_once`], a failed request may have
+    /// granted the items anyway
+    ///
+    /// Uses [`Endpoint::AddItem`]
+    pub async fn add_item(
+        &self,
+        app_id: u32,
+        steam_id: SteamId,
+        item_def_ids: &[u32],
+        notify: bool,
+    ) -> Result<Vec<InventoryItem>> {
+        let key = self.inventory_key()?;
+        let app_id = app_id.to_string();
+        let steam_id = steam_id.to_string();
+        let names = (0..item_def_ids.len())
+            .map(|i| format!("itemdefid[{}]", i))
+            .collect::<Vec<_>>();
+        let ids = item_def_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        let mut form = vec![
+            ("key", key),
+            ("appid", app_id.as_str()),
+            ("steamid", steam_id.as_str()),
+            ("notify", if notify { "1" } else { "0" }),
+        ];
+        form.extend(
+            names
+                .iter()
+                .map(String::as_str)
+                .zip(ids.iter().map(String::as_str)),
+        );
+
+        self.post_form_json_once::<ItemsResponse>(Endpoint::AddItem, &form)
+            .await?
+            .try_into()
+    }
+
+    /// Remove `quantity` of an item from a player, returns the changed item
+    ///
+    /// Requires a publisher key with access to the app
+    ///
+    /// Sent only once, see [`Client::post_form_json_once`], a failed request may have
+    /// consumed the item anyway
+    ///
+    /// Uses [`Endpoint::ConsumeItem`]
+    pub async fn consume_item(
+        &self,
+        app_id: u32,
+        steam_id: SteamId,
+        item_id: u64,
+        quantity: u32,
+    ) -> Result<Vec<InventoryItem>> {
+        let key = self.inventory_key()?;
+        let form = [
+            ("key", key),
+            ("appid", &app_id.to_string()),
+            ("steamid", &steam_id.to_string()),
+            ("itemid", &item_id.to_string()),
+            ("quantity", &quantity.to_string()),
+        ];
+
+        self.post_form_json_once::<ItemsResponse>(Endpoint::ConsumeItem, &form)
+            .await?
+            .try_into()
+    }
+
+    /// Download the item definitions of an app
+    ///
+    /// Uses [`Endpoint::ItemDefMeta`] for the digest of the current schema and
+    /// [`Endpoint::ItemDefArchive`] to download it
+    pub async fn get_item_defs(&self, app_id: u32) -> Result<Vec<ItemDef>> {
+        let app_id = app_id.to_string();
+        let meta = self
+            .get_json::<MetaResponse>(
+                Endpoint::ItemDefMeta,
+                &[("key", self.api_key()), ("appid", &app_id)],
+            )
+            .await?;
+
+        let query = [("appid", app_id.as_str()), ("digest", &meta.digest)];
+        let archive = self.get_text(Endpoint::ItemDefArchive, "", &query).await?;
+        parse_item_defs(&archive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_item_defs, InventoryItem, ItemsResponse};
+
+    #[test]
+    fn parses_items() {
+        let json: ItemsResponse = load_test_json!("inventory.json");
+        let items = Vec::<InventoryItem>::try_from(json).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].account_id, 236349870);
+        assert_eq!(items[0].item_id, 4219203012045842543);
+        assert_eq!(items[1].item_def_id, 101);
+        assert_eq!(items[1].quantity, 3);
+        assert_eq!(items[1].origin, "playtime");
+    }
+
+    #[test]
+    fn parses_item_defs() {
+        let archive = include_str!("../../../test_resources/item_def_archive.json");
+        let defs = parse_item_defs(archive).unwrap();
+        assert_eq!(defs.len(), 3);
+        assert_eq!(defs[0].name, "Hat");
+        assert!(defs[0].tradable);
+        assert_eq!(
+            defs[0].properties["icon_url"],
+            "https://example.com/hat.png"
+        );
+        assert_eq!(defs[2].item_type, "generator");
+        assert!(!defs[2].marketable);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let client = MockSteam::start().await.client().await;
+        let id = SteamId(76561198196615742);
+        assert_eq!(client.get_inventory(480, id).await.unwrap().len(), 2);
+        assert_eq!(
+            client
+                .add_item(480, id, &[100, 101], false)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            client
+                .consume_item(480, id, 4219203012045842543, 1)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(client.get_item_defs(480).await.unwrap().len(), 3);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn changes_are_sent_once() {
+        use super::InventoryError;
+        use crate::testing::MockSteam;
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::AddItem, 500, 4).await;
+        steam.mount_status(Endpoint::ConsumeItem, 500, 4).await;
+        let client = steam.client().await;
+        let id = SteamId(76561198196615742);
+
+        let added = client.add_item(480, id, &[100], false).await;
+        assert!(matches!(added, Err(InventoryError::Reqwest(err)) if err.status().is_some()));
+        assert_eq!(steam.received(Endpoint::AddItem).await, 1);
+
+        let consumed = client.consume_item(480, id, 4219203012045842543, 1).await;
+        assert!(consumed.is_err());
+        assert_eq!(steam.received(Endpoint::ConsumeItem).await, 1);
+        assert_eq!(client.total_retries(), 0);
+    }
+}
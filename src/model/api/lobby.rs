@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, LobbyId, SteamIdStr};
+
+#[derive(Error, Debug)]
+pub enum LobbyError {
+    /// See [`ClientBuilder::publisher_key`](crate::ClientBuilder::publisher_key)
+    #[error("client has no publisher key")]
+    PublisherKey,
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, LobbyError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LobbyMember {
+    #[serde(rename(deserialize = "steamid"))]
+    pub steam_id: SteamIdStr,
+    /// Set by the game for the member
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// See [`Client::get_lobby_data`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LobbyData {
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    #[serde(rename(deserialize = "steamid_lobby"))]
+    pub lobby_id: LobbyId,
+    /// Set by the game for the lobby, e.g. the map
+    #[serde(rename(deserialize = "lobby_metadata"))]
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub members: Vec<LobbyMember>,
+}
+
+type Response = Envelope<LobbyData>;
+
+impl Client {
+    /// Get the metadata and members of a lobby, e.g. one from
+    /// [`PlayerSummary::lobby_id`](crate::api::PlayerSummary::lobby_id)
+    ///
+    /// Requires a publisher key with access to the app, there's no way to list
+    /// lobbies through the Web API.
+    ///
+    /// Uses [`Endpoint::LobbyData`]
+    pub async fn get_lobby_data(&self, app_id: u32, lobby_id: LobbyId) -> Result<LobbyData> {
+        let key = self.publisher_key().ok_or(LobbyError::PublisherKey)?;
+        let query = [
+            ("key", key),
+            ("appid", &app_id.to_string()),
+            ("steamid_lobby", &lobby_id.to_string()),
+        ];
+
+        let json = self
+            .get_json::<Response>(Endpoint::LobbyData, &query)
+            .await?;
+        Ok(json.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("lobby_data.json");
+        let lobby = json.into_inner();
+        assert_eq!(lobby.app_id, 480);
+        assert_eq!(lobby.lobby_id.steam_id().0, 109775241046845834);
+        assert_eq!(lobby.metadata["map"], "de_dust2");
+        assert_eq!(lobby.members.len(), 2);
+        assert_eq!(lobby.members[0].metadata["team"], "ct");
+        assert!(lobby.members[1].metadata.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+
+        let client = MockSteam::start().await.client().await;
+        let lobby_id = "109775241046845834".parse().unwrap();
+        let lobby = client.get_lobby_data(480, lobby_id).await.unwrap();
+        assert_eq!(lobby.lobby_id, lobby_id);
+    }
+}
@@ -0,0 +1,59 @@
+use thiserror::Error;
+
+use crate::client::{encode_path_segment, Client};
+use crate::model::html::market_listings;
+use crate::model::Endpoint;
+
+#[derive(Debug, Error)]
+pub enum MarketListingsError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the scripts of the page
+    #[error("couldn't parse listings page ({0})")]
+    ParseError(#[from] market_listings::Error),
+}
+type Result<T> = std::result::Result<T, MarketListingsError>;
+
+pub use market_listings::{MarketListing, MarketListingsPage, PricePoint};
+
+impl Client {
+    /// Get the price history and the cheapest listings of the item `market_hash_name`
+    /// of the app `app_id` from its listings page
+    ///
+    /// Unlike the `pricehistory` endpoint this doesn't need a logged in session,
+    /// prices are in USD then.
+    ///
+    /// Uses [`Endpoint::MarketListings`]
+    pub async fn get_market_listings(
+        &self,
+        app_id: u32,
+        market_hash_name: &str,
+    ) -> Result<MarketListingsPage> {
+        // market hash names contain spaces, `|` and sometimes `/`
+        let sub_path = format!("{}/{}", app_id, encode_path_segment(market_hash_name));
+        let html = self
+            .get_text(Endpoint::MarketListings, &sub_path, &[])
+            .await?;
+        Ok(market_listings::parse(&html)?)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    #[tokio::test]
+    async fn fetches() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let page = client
+            .get_market_listings(440, "Mann Co. Supply Crate Key")
+            .await
+            .unwrap();
+        assert_eq!(page.listings.len(), 2);
+        assert_eq!(steam.received(Endpoint::MarketListings).await, 1);
+    }
+}
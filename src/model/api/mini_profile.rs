@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::serde_util::from_str_or_number_lenient;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Error, Debug)]
+pub enum MiniProfileError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// Steam answers with an empty page for accounts that don't exist
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+type Result<T> = std::result::Result<T, MiniProfileError>;
+
+/// The game a player is in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InGame {
+    pub name: String,
+    /// A shortcut to a game that isn't on Steam
+    #[serde(default)]
+    pub is_non_steam: bool,
+    /// Url of the capsule image of the game
+    #[serde(default)]
+    pub logo: Option<String>,
+    /// What the game reports the player is doing, e.g. `Competitive - Mirage`
+    #[serde(default)]
+    pub rich_presence: Option<String>,
+}
+
+/// The badge a player picked to show on their profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FavoriteBadge {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Url of the badge image
+    pub icon: String,
+    #[serde(default)]
+    pub level: Option<u32>,
+    #[serde(default, deserialize_with = "from_str_or_number_lenient")]
+    pub xp: Option<u64>,
+}
+
+/// The data the hover card of a profile shows, see [`Client::get_miniprofile`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MiniProfile {
+    pub persona_name: String,
+    pub avatar_url: String,
+    /// Url of the avatar frame, if one is equipped
+    #[serde(default)]
+    pub avatar_frame: Option<String>,
+    /// [`None`], if the profile is private
+    #[serde(default)]
+    pub level: Option<u64>,
+    /// Css classes Steam uses to color the level, e.g. `friendPlayerLevelNum100`
+    #[serde(default)]
+    pub level_class: Option<String>,
+    /// [`None`], if the player isn't in a game
+    #[serde(default)]
+    pub in_game: Option<InGame>,
+    #[serde(default)]
+    pub favorite_badge: Option<FavoriteBadge>,
+    /// Urls of the profile background by mime type, e.g. `video/webm`,
+    /// empty if no background is equipped
+    #[serde(default)]
+    pub profile_background: HashMap<String, String>,
+}
+
+/// What a player is currently doing, see [`Client::get_rich_presence`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RichPresence {
+    pub persona_name: String,
+    pub avatar_url: String,
+    /// [`None`], if the profile is private
+    #[serde(default)]
+    pub level: Option<u64>,
+    /// [`None`], if the player isn't in a game
+    #[serde(default)]
+    pub in_game: Option<InGame>,
+}
+
+impl MiniProfile {
+    /// Clear the avatar, the only personal field besides the name
+    ///
+    /// Done for every mini profile, if the client was built with
+    /// [`ClientBuilder::minimal_fields`](crate::ClientBuilder::minimal_fields)
+    pub fn minimize(&mut self) {
+        self.avatar_url.clear();
+    }
+}
+
+impl From<MiniProfile> for RichPresence {
+    fn from(value: MiniProfile) -> Self {
+        RichPresence {
+            persona_name: value.persona_name,
+            avatar_url: value.avatar_url,
+            level: value.level,
+            in_game: value.in_game,
+        }
+    }
+}
+
+impl Client {
+    /// Get the data the hover card of a profile shows, which needs no api-key
+    ///
+    /// Uses [`Endpoint::MiniProfile`]
+    pub async fn get_miniprofile(&self, steam_id: SteamId) -> Result<MiniProfile> {
+        let sub_path = format!("{}/json", steam_id.account_id());
+        let json = self.get_text(Endpoint::MiniProfile, &sub_path, &[]).await?;
+        let mut profile: MiniProfile = serde_json::from_str(&json)?;
+        if self.minimal_fields() {
+            profile.minimize();
+        }
+        Ok(profile)
+    }
+
+    /// Get what a player is currently doing, including the rich presence string
+    /// of the game, which [`Client::get_player_summaries`] doesn't have
+    ///
+    /// Uses [`Endpoint::MiniProfile`]
+    pub async fn get_rich_presence(&self, steam_id: SteamId) -> Result<RichPresence> {
+        Ok(self.get_miniprofile(steam_id).await?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MiniProfile, RichPresence};
+
+    #[test]
+    fn parses() {
+        let presence: RichPresence = load_test_json!("mini_profile.json");
+        assert_eq!(presence.persona_name, "cry");
+        assert_eq!(presence.level, Some(135));
+        let in_game = presence.in_game.unwrap();
+        assert_eq!(in_game.name, "Counter-Strike 2");
+        assert_eq!(
+            in_game.rich_presence.as_deref(),
+            Some("Competitive - Mirage [ 7 : 5 ]")
+        );
+    }
+
+    #[test]
+    fn parses_miniprofile() {
+        let profile: MiniProfile = load_test_json!("mini_profile.json");
+        assert!(profile.avatar_frame.unwrap().ends_with(".png"));
+        let badge = profile.favorite_badge.unwrap();
+        assert_eq!(badge.name, "Years of Service");
+        assert_eq!(badge.level, Some(8));
+        assert_eq!(badge.xp, Some(400));
+        assert!(profile.profile_background["video/webm"].ends_with(".webm"));
+
+        let profile: MiniProfile =
+            serde_json::from_str(r#"{"persona_name":"x","avatar_url":""}"#).unwrap();
+        assert!(profile.profile_background.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let presence = client
+            .get_rich_presence(SteamId(76561198089612262))
+            .await
+            .unwrap();
+        assert!(presence.in_game.is_some());
+        assert!(!presence.avatar_url.is_empty());
+        assert_eq!(steam.received(Endpoint::MiniProfile).await, 1);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn minimizes() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let steam = MockSteam::start().await;
+        let client = steam
+            .client_builder()
+            .minimal_fields(true)
+            .build()
+            .await
+            .unwrap();
+        let id = SteamId(76561198089612262);
+
+        let profile = client.get_miniprofile(id).await.unwrap();
+        assert_eq!(profile.persona_name, "cry");
+        assert!(profile.avatar_url.is_empty());
+        let presence = client.get_rich_presence(id).await.unwrap();
+        assert!(presence.avatar_url.is_empty());
+        assert!(presence.in_game.is_some());
+    }
+}
@@ -1,3 +1,75 @@
+mod account_status;
+pub use account_status::*;
+
+mod asset_prices;
+pub use asset_prices::*;
+
+mod avatar;
+pub use avatar::*;
+
+mod app_builds;
+pub use app_builds::*;
+
+mod app_list;
+pub use app_list::*;
+
+#[cfg(feature = "xml")]
+mod group_announcements;
+#[cfg(feature = "xml")]
+pub use group_announcements::*;
+
+#[cfg(feature = "xml")]
+mod group_member_list;
+#[cfg(feature = "xml")]
+pub use group_member_list::*;
+
+#[cfg(feature = "cheat_reporting")]
+mod cheat_reporting;
+#[cfg(feature = "cheat_reporting")]
+pub use cheat_reporting::*;
+
+mod batched;
+pub use batched::*;
+
+mod cm_list;
+pub use cm_list::*;
+
+mod deck_compatibility;
+pub use deck_compatibility::*;
+
+mod envelope;
+pub use envelope::*;
+
+#[cfg(feature = "user_search")]
+mod group_officers;
+#[cfg(feature = "user_search")]
+pub use group_officers::*;
+
+#[cfg(feature = "user_search")]
+mod group_summary;
+#[cfg(feature = "user_search")]
+pub use group_summary::*;
+
+mod health_check;
+pub use health_check::*;
+
+#[cfg(feature = "inventory")]
+mod inventory;
+#[cfg(feature = "inventory")]
+pub use inventory::*;
+
+mod lobby;
+pub use lobby::*;
+
+mod market_listings;
+pub use market_listings::*;
+
+mod mini_profile;
+pub use mini_profile::*;
+
+mod owned_games;
+pub use owned_games::*;
+
 mod player_bans;
 pub use player_bans::*;
 
@@ -7,13 +79,55 @@ pub use player_friends::*;
 mod player_summary;
 pub use player_summary::*;
 
+mod profile_items;
+pub use profile_items::*;
+
+#[cfg(feature = "user_search")]
+mod profile_page;
+#[cfg(feature = "user_search")]
+pub use profile_page::*;
+
+#[cfg(feature = "xml")]
+mod profile_xml;
+#[cfg(feature = "xml")]
+pub use profile_xml::*;
+
+mod profiles_from_vanities;
+pub use profiles_from_vanities::*;
+
+mod profile_with_bans;
+pub use profile_with_bans::*;
+
+mod published_file_details;
+pub use published_file_details::*;
+
+mod raw;
+pub use raw::*;
+
+mod server_info;
+pub use server_info::*;
+
+mod servers_at_address;
+pub use servers_at_address::*;
+
 mod steam_level;
 pub use steam_level::*;
 
+mod supported_api_list;
+pub use supported_api_list::*;
+
 #[cfg(feature = "user_search")]
 mod user_search;
 #[cfg(feature = "user_search")]
 pub use user_search::*;
 
+mod user_stats;
+pub use user_stats::*;
+
 mod vanity_url;
 pub use vanity_url::*;
+
+#[cfg(feature = "user_search")]
+mod verify_parsers;
+#[cfg(feature = "user_search")]
+pub use verify_parsers::*;
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamId, SteamTime};
+
+#[derive(Error, Debug)]
+pub enum OwnedGamesError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, OwnedGamesError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OwnedGame {
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    pub name: Option<String>,
+    /// Total playtime in minutes
+    pub playtime_forever: u64,
+    /// Playtime in the last two weeks in minutes, if there is any
+    pub playtime_2weeks: Option<u64>,
+    /// [`None`], if the game was never played
+    #[serde(rename(deserialize = "rtime_last_played"))]
+    #[serde(default, deserialize_with = "time_or_none")]
+    pub last_played: Option<SteamTime>,
+}
+
+/// Steam sends `0` for games that were never played
+fn time_or_none<'de, D>(deserializer: D) -> std::result::Result<Option<SteamTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let time = Option::<SteamTime>::deserialize(deserializer)?;
+    Ok(time.filter(|time| time.timestamp() != 0))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct OwnedGames {
+    /// - [`None`], if the user has set his game details to **private**
+    /// - [`Some`], if the user has set his game details to **public**
+    inner: Option<HashMap<u32, OwnedGame>>,
+}
+
+/// The playtime of a game that changed between two [`OwnedGames`]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaytimeDelta {
+    pub app_id: u32,
+    /// Total playtime before in minutes
+    pub before: u64,
+    /// Total playtime after in minutes
+    pub after: u64,
+}
+
+impl PlaytimeDelta {
+    /// Minutes played in between
+    pub const fn minutes(&self) -> u64 {
+        self.after.saturating_sub(self.before)
+    }
+}
+
+/// Changes of a library between two fetches, see [`OwnedGames::diff`]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnedGamesDiff {
+    /// App ids that are only in the new library
+    pub added: Vec<u32>,
+    /// App ids that are only in the old library
+    pub removed: Vec<u32>,
+    /// Games in both libraries, whose playtime changed
+    pub played: Vec<PlaytimeDelta>,
+}
+
+impl OwnedGamesDiff {
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.played.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ResponseInner {
+    #[serde(default, rename(deserialize = "game_count"))]
+    _game_count: IgnoredAny,
+    games: Option<Vec<OwnedGame>>,
+}
+
+type Response = Envelope<ResponseInner>;
+
+impl From<Response> for OwnedGames {
+    fn from(value: Response) -> Self {
+        let inner = value
+            .into_inner()
+            .games
+            .map(|games| games.into_iter().map(|game| (game.app_id, game)).collect());
+        OwnedGames { inner }
+    }
+}
+
+impl OwnedGames {
+    pub fn into_inner(self) -> Option<HashMap<u32, OwnedGame>> {
+        self.inner
+    }
+    pub const fn as_inner_ref(&self) -> Option<&HashMap<u32, OwnedGame>> {
+        self.inner.as_ref()
+    }
+
+    /// When each game was last played, most recent first.
+    ///
+    /// Games that were never played are left out, [`None`] if the library is **private**.
+    pub fn last_played(&self) -> Option<Vec<(u32, SteamTime)>> {
+        let mut times = self
+            .inner
+            .as_ref()?
+            .values()
+            .filter_map(|game| Some((game.app_id, game.last_played?)))
+            .collect::<Vec<_>>();
+        times.sort_unstable_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+        Some(times)
+    }
+
+    /// Compare two fetches of the same library, e.g. a week apart.
+    ///
+    /// Returns [`None`], if one of them is **private**. The lists are sorted by app id.
+    pub fn diff(old: &OwnedGames, new: &OwnedGames) -> Option<OwnedGamesDiff> {
+        let (old, new) = (old.inner.as_ref()?, new.inner.as_ref()?);
+
+        let mut diff = OwnedGamesDiff::default();
+        for (app_id, game) in new {
+            match old.get(app_id) {
+                None => diff.added.push(*app_id),
+                Some(before) if before.playtime_forever != game.playtime_forever => {
+                    diff.played.push(PlaytimeDelta {
+                        app_id: *app_id,
+                        before: before.playtime_forever,
+                        after: game.playtime_forever,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|app_id| !new.contains_key(app_id))
+            .copied()
+            .collect();
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.played.sort_unstable_by_key(|delta| delta.app_id);
+        Some(diff)
+    }
+}
+
+impl Client {
+    /// Get the games owned by the profile with the given [`SteamId`], including free games
+    ///
+    /// Uses [`Endpoint::OwnedGames`]
+    pub async fn get_owned_games(&self, id: SteamId) -> Result<OwnedGames> {
+        let query = [
+            ("key", self.api_key()),
+            ("steamid", &id.to_string()),
+            ("include_appinfo", "1"),
+            ("include_played_free_games", "1"),
+        ];
+
+        let resp = self
+            .get_json::<Response>(Endpoint::OwnedGames, &query)
+            .await?;
+        Ok(resp.into())
+    }
+
+    /// Get when the profile with the given [`SteamId`] last played each of its games,
+    /// see [`OwnedGames::last_played`]
+    ///
+    /// `IPlayerService/ClientGetLastPlayedTimes` needs a token of the user, so the times
+    /// are taken from [`Client::get_owned_games`] instead, which has them for public libraries.
+    ///
+    /// Uses [`Endpoint::OwnedGames`]
+    pub async fn get_last_played_times(
+        &self,
+        id: SteamId,
+    ) -> Result<Option<Vec<(u32, SteamTime)>>> {
+        Ok(self.get_owned_games(id).await?.last_played())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OwnedGames, PlaytimeDelta, Response};
+
+    #[test]
+    fn parses() {
+        let resp: Response = load_test_json!("owned_games.json");
+        let games: OwnedGames = resp.into();
+        let games = games.into_inner().unwrap();
+        assert_eq!(games.len(), 4);
+        assert_eq!(games[&730].playtime_2weeks, Some(754));
+        assert_eq!(games[&10].playtime_2weeks, None);
+        assert_eq!(games[&730].last_played.unwrap().timestamp(), 1697210511);
+
+        let resp: Response = load_test_json!("owned_games_private.json");
+        let games: OwnedGames = resp.into();
+        assert!(games.as_inner_ref().is_none());
+    }
+
+    #[test]
+    fn sorts_last_played() {
+        let resp: Response = load_test_json!("owned_games.json");
+        let games: OwnedGames = resp.into();
+        let times = games.last_played().unwrap();
+        assert_eq!(times[0].0, 730);
+        assert!(times.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(times.len() < games.as_inner_ref().unwrap().len());
+
+        assert!(OwnedGames { inner: None }.last_played().is_none());
+    }
+
+    #[test]
+    fn diffs() {
+        let resp: Response = load_test_json!("owned_games.json");
+        let old: OwnedGames = resp.into();
+
+        let mut new = old.clone().into_inner().unwrap();
+        new.remove(&10);
+        new.get_mut(&730).unwrap().playtime_forever += 120;
+        let mut added = new[&440].clone();
+        added.app_id = 620;
+        new.insert(620, added);
+        let new = OwnedGames { inner: Some(new) };
+
+        let diff = OwnedGames::diff(&old, &new).unwrap();
+        assert_eq!(diff.added, [620]);
+        assert_eq!(diff.removed, [10]);
+        assert_eq!(
+            diff.played,
+            [PlaytimeDelta {
+                app_id: 730,
+                before: 98261,
+                after: 98381
+            }]
+        );
+        assert_eq!(diff.played[0].minutes(), 120);
+
+        assert!(OwnedGames::diff(&old, &old).unwrap().is_empty());
+        let private = OwnedGames { inner: None };
+        assert!(OwnedGames::diff(&old, &private).is_none());
+    }
+}
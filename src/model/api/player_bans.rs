@@ -2,12 +2,13 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ops::Deref;
 
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::{PLAYER_BANS_API, PLAYER_BANS_IDS_PER_REQUEST};
-use crate::model::{EconomyBan, SteamId, SteamIdQueryExt, SteamIdStr};
+use crate::model::api::{Batched, Envelope};
+use crate::model::{EconomyBan, Endpoint, SteamId, SteamIdQueryExt, SteamIdStr};
 
 #[derive(Debug, Error)]
 pub enum PlayerBanError {
@@ -19,6 +20,10 @@ pub enum PlayerBanError {
 
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+
+    /// The client was shut down before the bulk request finished, see [`Client::shutdown`]
+    #[error("cancelled")]
+    Cancelled,
 }
 type Result<T> = std::result::Result<T, PlayerBanError>;
 
@@ -40,7 +45,27 @@ pub struct PlayerBan {
     pub economy_ban: EconomyBan,
 }
 
-#[derive(Debug, Clone)]
+impl PlayerBan {
+    /// No ban of any kind
+    pub fn is_clean(&self) -> bool {
+        !self.community_banned
+            && !self.vac_banned
+            && self.number_of_vac_bans == 0
+            && self.number_of_game_bans == 0
+            && self.economy_ban == EconomyBan::None
+    }
+    /// The same bans, ignoring [`PlayerBan::days_since_last_ban`] which changes every day
+    pub fn has_same_bans(&self, other: &PlayerBan) -> bool {
+        self.community_banned == other.community_banned
+            && self.vac_banned == other.vac_banned
+            && self.number_of_vac_bans == other.number_of_vac_bans
+            && self.number_of_game_bans == other.number_of_game_bans
+            && self.economy_ban == other.economy_ban
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct PlayerBans {
     inner: HashMap<SteamId, PlayerBan>,
 }
@@ -49,6 +74,28 @@ impl PlayerBans {
     pub fn into_inner(self) -> HashMap<SteamId, PlayerBan> {
         self.inner
     }
+
+    /// The entries of `self` whose bans differ from the ones in `snapshot`.
+    ///
+    /// Profiles missing from `snapshot` are compared as if they had no bans.
+    pub fn changed_since(self, snapshot: &PlayerBans) -> PlayerBans {
+        let inner = self
+            .inner
+            .into_iter()
+            .filter(|(steam_id, ban)| {
+                snapshot
+                    .get(steam_id)
+                    .map_or_else(|| !ban.is_clean(), |old| !ban.has_same_bans(old))
+            })
+            .collect();
+        PlayerBans { inner }
+    }
+
+    /// Insert or replace the entries of `other`, e.g. to update a snapshot with
+    /// the result of [`Client::get_new_bans_since`]
+    pub fn merge(&mut self, other: PlayerBans) {
+        self.inner.extend(other.inner);
+    }
 }
 
 impl Deref for PlayerBans {
@@ -58,14 +105,11 @@ impl Deref for PlayerBans {
     }
 }
 
-#[derive(Deserialize, Debug)]
-struct Response {
-    players: Vec<PlayerBan>,
-}
+type Response = Envelope<Vec<PlayerBan>>;
 
 impl From<Response> for PlayerBans {
     fn from(value: Response) -> Self {
-        let bans = value.players;
+        let bans = value.into_inner();
         let map = bans
             .into_iter()
             .map(|ban| (ban.steam_id.into(), ban))
@@ -77,33 +121,60 @@ impl From<Response> for PlayerBans {
 impl Client {
     /// Get the bans of the profiles with the given [`SteamId`]
     ///
-    /// Uses [`PLAYER_BANS_API`]
+    /// Uses [`Endpoint::PlayerBans`]
     pub async fn get_player_bans(&self, steam_id_chunk: Cow<'_, [SteamId]>) -> Result<PlayerBans> {
-        // deduplicated ids
-        let mut steam_ids = steam_id_chunk.into_owned();
-        steam_ids.sort_unstable();
-        steam_ids.dedup();
-
-        // check length
-        if steam_ids.len() > PLAYER_BANS_IDS_PER_REQUEST {
-            return Err(PlayerBanError::TooManyIds);
-        }
+        // deduplicated ids, at most PlayerBans::MAX_IDS
+        let steam_ids =
+            PlayerBans::prepare_ids(steam_id_chunk).ok_or(PlayerBanError::TooManyIds)?;
 
         // build query string
         let ids = steam_ids.iter().to_steam_id_string(",");
         let query = [("key", self.api_key()), ("steamids", &ids)];
 
         // make request
-        let resp = self.get_json::<Response>(PLAYER_BANS_API, &query).await?;
+        let resp = self
+            .get_json::<Response>(Endpoint::PlayerBans, &query)
+            .await?;
 
         // conversion
         Ok(resp.into())
     }
+
+    /// Get the bans of any number of profiles and keep only those that changed
+    /// compared to `snapshot`, see [`PlayerBans::changed_since`]
+    ///
+    /// The ids are split into chunks that are requested concurrently.
+    /// Stops with [`PlayerBanError::Cancelled`] once the client is [shut down](Client::shutdown).
+    ///
+    /// Uses [`Endpoint::PlayerBans`]
+    pub async fn get_new_bans_since(
+        &self,
+        steam_ids: &[SteamId],
+        snapshot: &PlayerBans,
+    ) -> Result<PlayerBans> {
+        let chunks = futures::stream::iter(PlayerBans::chunks(steam_ids))
+            .map(|chunk| self.get_player_bans(chunk.into()))
+            .buffer_unordered(Endpoint::PlayerBans.concurrent_requests())
+            .try_collect::<Vec<_>>();
+        let chunks = self
+            .until_shutdown(chunks)
+            .await
+            .ok_or(PlayerBanError::Cancelled)??;
+
+        let mut changed = PlayerBans {
+            inner: HashMap::default(),
+        };
+        for bans in chunks {
+            changed.merge(bans.changed_since(snapshot));
+        }
+        Ok(changed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{PlayerBans, Response};
+    use crate::SteamId;
 
     #[test]
     fn parses() {
@@ -111,4 +182,38 @@ mod tests {
         let bans: PlayerBans = resp.into();
         println!("{:#?}", bans);
     }
+
+    #[test]
+    fn finds_changes() {
+        let resp: Response = load_test_json!("player_bans.json");
+        let snapshot: PlayerBans = resp.into();
+        assert!(snapshot.clone().changed_since(&snapshot).is_empty());
+
+        let mut current = snapshot.clone().into_inner();
+        let (&changed_id, ban) = current.iter_mut().next().unwrap();
+        ban.number_of_game_bans += 1;
+        for ban in current.values_mut() {
+            ban.days_since_last_ban += 1;
+        }
+        let current = PlayerBans { inner: current };
+
+        let changed = current.clone().changed_since(&snapshot);
+        assert_eq!(
+            changed.keys().copied().collect::<Vec<SteamId>>(),
+            [changed_id]
+        );
+
+        let empty: PlayerBans = serde_json::from_str("{}").unwrap();
+        let dirty = current.values().filter(|ban| !ban.is_clean()).count();
+        assert_eq!(current.changed_since(&empty).len(), dirty);
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let resp: Response = load_test_json!("player_bans.json");
+        let bans: PlayerBans = resp.into();
+        let decoded = assert_bincode_round_trip!(bans, PlayerBans);
+        assert_eq!(decoded.len(), bans.len());
+    }
 }
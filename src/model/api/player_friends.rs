@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::PLAYER_FRIENDS_API;
-use crate::model::{SteamId, SteamTime};
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamId, SteamTime};
 use crate::SteamIdStr;
 
 #[derive(Error, Debug)]
@@ -19,17 +19,96 @@ pub enum PlayerFriendsError {
 }
 type Result<T> = std::result::Result<T, PlayerFriendsError>;
 
+/// Which relationships [`Client::get_player_friends_with`] returns
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationshipFilter {
+    /// Every relationship that is visible, see [`Relationship`]
+    All,
+    /// Only friends
+    #[default]
+    Friend,
+}
+
+impl RelationshipFilter {
+    /// The value of the `relationship` parameter
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            RelationshipFilter::All => "all",
+            RelationshipFilter::Friend => "friend",
+        }
+    }
+}
+
+/// How a profile in a [`FriendsList`] relates to its owner
+///
+/// (De)serialized as the lowercase name Steam uses, e.g. `ignoredfriend`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Relationship {
+    Friend,
+    Blocked,
+    Ignored,
+    /// A friend that has been blocked
+    IgnoredFriend,
+    /// A friend request the owner received
+    RequestRecipient,
+    /// A friend request the owner sent
+    RequestInitiator,
+    /// A relationship this crate doesn't know about yet, with the name Steam sent
+    Other(String),
+}
+
+impl Relationship {
+    /// The name Steam uses for the relationship
+    pub fn as_str(&self) -> &str {
+        match self {
+            Relationship::Friend => "friend",
+            Relationship::Blocked => "blocked",
+            Relationship::Ignored => "ignored",
+            Relationship::IgnoredFriend => "ignoredfriend",
+            Relationship::RequestRecipient => "requestrecipient",
+            Relationship::RequestInitiator => "requestinitiator",
+            Relationship::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for Relationship {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "friend" => Relationship::Friend,
+            "blocked" => Relationship::Blocked,
+            "ignored" => Relationship::Ignored,
+            "ignoredfriend" => Relationship::IgnoredFriend,
+            "requestrecipient" => Relationship::RequestRecipient,
+            "requestinitiator" => Relationship::RequestInitiator,
+            _ => Relationship::Other(value),
+        }
+    }
+}
+
+impl From<Relationship> for String {
+    fn from(value: Relationship) -> Self {
+        match value {
+            Relationship::Other(name) => name,
+            known => known.as_str().to_owned(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Friend {
     #[serde(rename(deserialize = "steamid"))]
     pub steam_id: SteamIdStr,
     #[serde(rename(deserialize = "relationship"))]
-    pub relationship: String,
+    pub relationship: Relationship,
     #[serde(rename(deserialize = "friend_since"))]
     pub friends_since: SteamTime,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
 pub struct FriendsList {
     /// - [`None`], if the user has set his friends to **private**
     /// - [`Some`], if the user has set his friends to **public**
@@ -38,22 +117,33 @@ pub struct FriendsList {
     inner: Option<HashMap<SteamId, Friend>>,
 }
 
+/// A [`FriendsList`] fetched with all relationship types, split up by relationship
+///
+/// See [`Client::get_player_relationships`]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Relationships {
+    /// `friend`
+    pub friends: HashMap<SteamId, Friend>,
+    /// `blocked`, `ignored` and `ignoredfriend` (a friend that has been blocked)
+    pub blocked: HashMap<SteamId, Friend>,
+    /// `requestrecipient` and `requestinitiator`
+    pub pending: HashMap<SteamId, Friend>,
+    /// Relationships that aren't known (yet)
+    pub other: HashMap<SteamId, Friend>,
+}
+
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
 struct ResponseInner {
     friends: Vec<Friend>,
 }
 
-#[derive(Deserialize)]
-struct Response {
-    #[serde(rename(deserialize = "friendslist"))]
-    friend_list: Option<ResponseInner>,
-}
+type Response = Envelope<Option<ResponseInner>>;
 
 impl From<Response> for FriendsList {
     fn from(value: Response) -> Self {
-        let friends = match value.friend_list {
-            None => return FriendsList { inner: None },
-            Some(friends) => friends,
+        let Some(friends) = value.into_inner() else {
+            return FriendsList { inner: None };
         };
 
         let map = friends
@@ -73,20 +163,65 @@ impl FriendsList {
     pub const fn as_inner_ref(&self) -> Option<&HashMap<SteamId, Friend>> {
         self.inner.as_ref()
     }
+
+    /// Split the list up by relationship, [`None`] if the list is **private**
+    pub fn partition(self) -> Option<Relationships> {
+        let mut relationships = Relationships::default();
+        for (id, friend) in self.inner? {
+            let map = match friend.relationship {
+                Relationship::Friend => &mut relationships.friends,
+                Relationship::Blocked | Relationship::Ignored | Relationship::IgnoredFriend => {
+                    &mut relationships.blocked
+                }
+                Relationship::RequestRecipient | Relationship::RequestInitiator => {
+                    &mut relationships.pending
+                }
+                Relationship::Other(_) => &mut relationships.other,
+            };
+            map.insert(id, friend);
+        }
+        Some(relationships)
+    }
 }
 
 impl Client {
     /// Get the friends of the profile with the given [`SteamId`]
     ///
-    /// Uses [`PLAYER_FRIENDS_API`]
+    /// Uses [`Endpoint::PlayerFriends`]
     pub async fn get_player_friends(&self, id: SteamId) -> Result<FriendsList> {
+        self.get_player_friends_with(id, RelationshipFilter::Friend)
+            .await
+    }
+
+    /// Get every relationship of the profile with the given [`SteamId`], including blocked
+    /// profiles and pending requests where they are visible
+    ///
+    /// Uses [`Endpoint::PlayerFriends`]
+    pub async fn get_player_relationships(&self, id: SteamId) -> Result<Option<Relationships>> {
+        let list = self
+            .get_player_friends_with(id, RelationshipFilter::All)
+            .await?;
+        Ok(list.partition())
+    }
+
+    /// Get the profiles with the given relationships to the profile with the given [`SteamId`]
+    ///
+    /// Uses [`Endpoint::PlayerFriends`]
+    pub async fn get_player_friends_with(
+        &self,
+        id: SteamId,
+        relationship: RelationshipFilter,
+    ) -> Result<FriendsList> {
         let query = [
             ("key", self.api_key()),
-            ("relationship", "friend"),
+            ("relationship", relationship.as_str()),
             ("steamid", &id.to_string()),
         ];
 
-        let resp = match self.get_json::<Response>(PLAYER_FRIENDS_API, &query).await {
+        let resp = match self
+            .get_json::<Response>(Endpoint::PlayerFriends, &query)
+            .await
+        {
             Ok(resp) => resp,
             Err(err) => match err.status() {
                 Some(StatusCode::UNAUTHORIZED) => return Ok(FriendsList { inner: None }),
@@ -100,7 +235,8 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use super::{FriendsList, Response};
+    use super::{FriendsList, Relationship, Response};
+    use crate::SteamId;
 
     #[test]
     fn parses_private() {
@@ -115,4 +251,62 @@ mod tests {
         let bans: FriendsList = resp.into();
         println!("{:#?}", bans);
     }
+
+    #[test]
+    fn partitions_all() {
+        let resp: Response = load_test_json!("player_friends_all.json");
+        let list: FriendsList = resp.into();
+        let relationships = list.partition().unwrap();
+        assert_eq!(relationships.friends.len(), 2);
+        assert_eq!(relationships.blocked.len(), 2);
+        assert_eq!(relationships.pending.len(), 2);
+        assert!(relationships.other.is_empty());
+        let ignored = &relationships.blocked[&SteamId(76561197960857266)];
+        assert_eq!(ignored.relationship, Relationship::Blocked);
+        assert!(relationships
+            .blocked
+            .contains_key(&SteamId(76561197960857266)));
+
+        let resp: Response = load_test_json!("player_friends_private.json");
+        let list: FriendsList = resp.into();
+        assert!(list.partition().is_none());
+    }
+
+    #[test]
+    fn parses_relationships() {
+        let parse =
+            |name: &str| serde_json::from_value::<Relationship>(serde_json::json!(name)).unwrap();
+        assert_eq!(parse("ignoredfriend"), Relationship::IgnoredFriend);
+        assert_eq!(parse("requestinitiator"), Relationship::RequestInitiator);
+        assert_eq!(
+            parse("suggested"),
+            Relationship::Other("suggested".to_owned())
+        );
+        assert_eq!(
+            serde_json::to_value(Relationship::RequestRecipient).unwrap(),
+            "requestrecipient"
+        );
+        assert_eq!(
+            serde_json::to_value(Relationship::Other("suggested".to_owned())).unwrap(),
+            "suggested"
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let resp: Response = load_test_json!("player_friends_public.json");
+        let friends: FriendsList = resp.into();
+        let decoded = assert_bincode_round_trip!(friends, FriendsList);
+        assert_eq!(decoded.as_inner_ref().map(|f| f.len()), Some(4));
+
+        let resp: Response = load_test_json!("player_friends_private.json");
+        let friends: FriendsList = resp.into();
+        let decoded = assert_bincode_round_trip!(friends, FriendsList);
+        assert!(decoded.as_inner_ref().is_none());
+
+        let unknown = Relationship::Other("suggested".to_owned());
+        let decoded = assert_bincode_round_trip!(unknown, Relationship);
+        assert_eq!(decoded, Relationship::Other("suggested".to_owned()));
+    }
 }
@@ -2,19 +2,20 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ops::Deref;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::{PLAYER_SUMMARIES_API, PLAYER_SUMMARIES_IDS_PER_REQUEST};
+use crate::model::api::{is_default_avatar, Batched, Envelope};
 use crate::model::{
-    CommunityVisibilityState, PersonaState, ProfileState, SteamIdQueryExt, SteamIdStr, SteamTime,
+    ClientType, CommunityVisibilityState, LobbyId, PersonaState, PersonaStateFlags, ProfileState,
+    SteamIdQueryExt, SteamIdStr, SteamTime,
 };
-use crate::SteamId;
+use crate::{Endpoint, SteamId};
 
 #[derive(Error, Debug)]
 pub enum PlayerSummaryError {
-    /// This API can only handle up to [`PLAYER_SUMMARIES_IDS_PER_REQUEST`] ids per request
+    /// This API can only handle up to [`Batched::MAX_IDS`](crate::api::Batched::MAX_IDS) ids per request
     #[error("too many ids passed for request")]
     TooManyIds,
 
@@ -26,7 +27,7 @@ pub enum PlayerSummaryError {
 }
 type Result<T> = std::result::Result<T, PlayerSummaryError>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerSummary {
     #[serde(rename(deserialize = "steamid"))]
     steam_id: SteamIdStr,
@@ -57,16 +58,115 @@ pub struct PlayerSummary {
     #[serde(rename(deserialize = "timecreated"))]
     time_created: Option<SteamTime>,
     #[serde(rename(deserialize = "personastateflags"))]
-    persona_state_flags: Option<u64>,
+    persona_state_flags: Option<PersonaStateFlags>,
     #[serde(rename(deserialize = "loccountrycode"))]
     local_country_code: Option<String>,
+    #[serde(rename(deserialize = "lobbysteamid"))]
+    #[serde(default, deserialize_with = "lobby_id_or_none")]
+    lobby_id: Option<LobbyId>,
+}
+
+/// Ids that aren't lobby ids are dropped instead of failing the whole summary
+fn lobby_id_or_none<'de, D>(deserializer: D) -> std::result::Result<Option<LobbyId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let id = Option::<Cow<'de, str>>::deserialize(deserializer)?;
+    Ok(id.and_then(|id| id.parse().ok()))
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(transparent)]
 pub struct PlayerSummaries {
     inner: HashMap<SteamId, PlayerSummary>,
 }
 
+impl PlayerSummary {
+    pub fn steam_id(&self) -> SteamId {
+        self.steam_id.steam_id()
+    }
+
+    pub const fn persona_name(&self) -> &str {
+        self.persona_name.as_str()
+    }
+
+    pub const fn profile_url(&self) -> &str {
+        self.profile_url.as_str()
+    }
+
+    /// [`None`], if the player didn't set one or the summary was minimized
+    pub fn real_name(&self) -> Option<&str> {
+        self.real_name.as_deref()
+    }
+
+    /// ISO 3166 code, e.g. `DE`, [`None`] if the player didn't set one or the summary
+    /// was minimized
+    pub fn local_country_code(&self) -> Option<&str> {
+        self.local_country_code.as_deref()
+    }
+
+    pub const fn community_visibility_state(&self) -> CommunityVisibilityState {
+        self.community_visibility_state
+    }
+
+    pub const fn time_created(&self) -> Option<SteamTime> {
+        self.time_created
+    }
+
+    pub const fn persona_state(&self) -> PersonaState {
+        self.persona_state
+    }
+
+    /// [`None`], if the profile is private
+    pub const fn persona_state_flags(&self) -> Option<PersonaStateFlags> {
+        self.persona_state_flags
+    }
+
+    /// The kind of client the player is online with, [`None`] if they're offline
+    /// or the profile is private, see [`PersonaStateFlags::client_type`]
+    pub fn client_type(&self) -> Option<ClientType> {
+        if self.persona_state == PersonaState::Offline {
+            return None;
+        }
+        self.persona_state_flags.map(PersonaStateFlags::client_type)
+    }
+
+    /// The lobby the player is in, only visible while they're in game
+    pub const fn lobby_id(&self) -> Option<LobbyId> {
+        self.lobby_id
+    }
+
+    /// Url of the `184x184` avatar, empty if the summary was minimized
+    pub const fn avatar_full(&self) -> &str {
+        self.avatar_full.as_str()
+    }
+
+    /// Empty, if the summary was minimized, see [`PlayerSummary::minimize`]
+    pub const fn avatar_hash(&self) -> &str {
+        self.avatar_hash.as_str()
+    }
+
+    /// Whether the profile has an avatar that isn't one of Steam's defaults.
+    ///
+    /// [`None`], if the summary was minimized and the avatar is unknown.
+    pub fn has_custom_avatar(&self) -> Option<bool> {
+        is_default_avatar(&self.avatar_hash).map(|default| !default)
+    }
+
+    /// Clear the personal fields, i.e. the real name, the country and the avatar
+    ///
+    /// Done for every summary, if the client was built with
+    /// [`ClientBuilder::minimal_fields`](crate::ClientBuilder::minimal_fields)
+    pub fn minimize(&mut self) {
+        self.real_name = None;
+        self.local_country_code = None;
+        self.avatar.clear();
+        self.avatar_medium.clear();
+        self.avatar_full.clear();
+        self.avatar_hash.clear();
+    }
+}
+
 impl PlayerSummaries {
     pub fn into_inner(self) -> HashMap<SteamId, PlayerSummary> {
         self.inner
@@ -81,18 +181,16 @@ impl Deref for PlayerSummaries {
 }
 
 #[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
 struct ResponseInner {
     players: Vec<PlayerSummary>,
 }
 
-#[derive(Deserialize, Debug)]
-struct Response {
-    response: ResponseInner,
-}
+type Response = Envelope<ResponseInner>;
 
 impl From<Response> for PlayerSummaries {
     fn from(value: Response) -> Self {
-        let summaries = value.response.players;
+        let summaries = value.into_inner().players;
 
         let map = summaries
             .into_iter()
@@ -106,24 +204,23 @@ impl From<Response> for PlayerSummaries {
 impl Client {
     /// Get the summaries of the profiles with the given [`SteamId`]
     ///
-    /// Uses [`PLAYER_SUMMARIES_API`]
+    /// Uses [`Endpoint::PlayerSummaries`]
     pub async fn get_player_summaries(
         &self,
         steam_id_chunk: Cow<'_, [SteamId]>,
     ) -> Result<PlayerSummaries> {
-        let mut steam_ids = steam_id_chunk.into_owned();
-        steam_ids.sort_unstable();
-        steam_ids.dedup();
-
-        if steam_ids.len() > PLAYER_SUMMARIES_IDS_PER_REQUEST {
-            return Err(PlayerSummaryError::TooManyIds);
-        }
+        // deduplicated ids, at most PlayerSummaries::MAX_IDS
+        let steam_ids =
+            PlayerSummaries::prepare_ids(steam_id_chunk).ok_or(PlayerSummaryError::TooManyIds)?;
 
         let ids = steam_ids.iter().to_steam_id_string(",");
         let query = [("key", self.api_key()), ("steamids", &ids)];
-        let resp = self
-            .get_json::<Response>(PLAYER_SUMMARIES_API, &query)
+        let mut resp = self
+            .get_json::<Response>(Endpoint::PlayerSummaries, &query)
             .await?;
+        if self.minimal_fields() {
+            resp.players.iter_mut().for_each(PlayerSummary::minimize);
+        }
 
         Ok(resp.into())
     }
@@ -131,12 +228,86 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use super::{PlayerSummaries, Response};
+    use serde_json::json;
+
+    use super::{PlayerSummaries, PlayerSummary, Response};
+    use crate::model::ClientType;
+    use crate::test_util::player_summary;
 
     #[test]
     fn parses() {
         let json: Response = load_test_json!("player_summaries.json");
         let summaries: PlayerSummaries = json.into();
         println!("{:?}", summaries);
+        assert!(summaries
+            .values()
+            .all(|summary| summary.has_custom_avatar() == Some(true)));
+    }
+
+    #[test]
+    fn parses_lobby_id() {
+        let summary =
+            |lobby: &str| player_summary(json!({ "personastate": 1, "lobbysteamid": lobby }));
+        let lobby = summary("109775241046845834").lobby_id().unwrap();
+        assert_eq!(lobby.steam_id().0, 109775241046845834);
+        assert_eq!(summary("0").lobby_id(), None);
+    }
+
+    #[test]
+    fn detects_client_type() {
+        let summary = |state: u8, flags: u64| {
+            player_summary(json!({ "personastate": state, "personastateflags": flags }))
+        };
+        assert_eq!(summary(1, 512).client_type(), Some(ClientType::Mobile));
+        assert_eq!(summary(3, 0).client_type(), Some(ClientType::Desktop));
+        assert_eq!(summary(0, 512).client_type(), None);
+    }
+
+    #[test]
+    fn minimizes() {
+        let mut json: Response = load_test_json!("player_summaries.json");
+        json.players.iter_mut().for_each(PlayerSummary::minimize);
+        for summary in PlayerSummaries::from(json).values() {
+            assert!(summary.real_name.is_none());
+            assert!(summary.local_country_code.is_none());
+            assert!(summary.avatar_full.is_empty());
+            assert!(summary.avatar_hash.is_empty());
+            assert!(!summary.persona_name.is_empty());
+            assert_eq!(summary.has_custom_avatar(), None);
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn minimizes_when_configured() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let steam = MockSteam::start().await;
+        let client = steam
+            .client_builder()
+            .minimal_fields(true)
+            .build()
+            .await
+            .unwrap();
+        let ids = [SteamId(76561197960287930)];
+        let summaries = client
+            .get_player_summaries(ids.as_slice().into())
+            .await
+            .unwrap();
+        assert!(!summaries.is_empty());
+        for summary in summaries.values() {
+            assert!(summary.real_name.is_none());
+            assert!(summary.avatar_full.is_empty());
+        }
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let json: Response = load_test_json!("player_summaries.json");
+        let summaries: PlayerSummaries = json.into();
+        let decoded = assert_bincode_round_trip!(summaries, PlayerSummaries);
+        assert_eq!(decoded.len(), summaries.len());
     }
 }
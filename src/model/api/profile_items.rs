@@ -0,0 +1,150 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::constants::COMMUNITY_IMAGES_URL;
+use crate::model::api::Envelope;
+use crate::model::serde_util::from_str_or_number;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Error, Debug)]
+pub enum ProfileItemsError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, ProfileItemsError>;
+
+/// Slots without an item are sent as empty objects
+fn item_or_none<'de, D>(deserializer: D) -> std::result::Result<Option<ProfileItem>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::Object(map)) if map.is_empty() => Ok(None),
+        Some(value) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// The full url of a path relative to [`COMMUNITY_IMAGES_URL`], [`None`] if it's empty
+fn image_url(path: &str) -> Option<String> {
+    (!path.is_empty()).then(|| format!("{}{}", COMMUNITY_IMAGES_URL, path))
+}
+
+/// An item equipped on a profile, see [`Client::get_profile_items_equipped`]
+///
+/// The images and movies are paths, the `*_url` methods turn them into urls.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProfileItem {
+    #[serde(rename(deserialize = "communityitemid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub community_item_id: u64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub item_title: String,
+    #[serde(default)]
+    pub item_description: String,
+    /// The app the item belongs to, e.g. the Steam Points Shop or a sale event
+    #[serde(rename(deserialize = "appid"))]
+    pub app_id: u32,
+    pub item_type: u32,
+    pub item_class: u32,
+    #[serde(default)]
+    pub image_small: String,
+    #[serde(default)]
+    pub image_large: String,
+    #[serde(default)]
+    pub movie_webm: String,
+    #[serde(default)]
+    pub movie_mp4: String,
+}
+
+impl ProfileItem {
+    pub fn image_small_url(&self) -> Option<String> {
+        image_url(&self.image_small)
+    }
+    pub fn image_large_url(&self) -> Option<String> {
+        image_url(&self.image_large)
+    }
+    /// Only animated backgrounds have movies
+    pub fn movie_webm_url(&self) -> Option<String> {
+        image_url(&self.movie_webm)
+    }
+    pub fn movie_mp4_url(&self) -> Option<String> {
+        image_url(&self.movie_mp4)
+    }
+    /// Whether the item is animated, i.e. has a movie or an animated image
+    pub fn is_animated(&self) -> bool {
+        !self.movie_webm.is_empty()
+            || !self.movie_mp4.is_empty()
+            || self.image_small.ends_with(".gif")
+            || self.image_large.ends_with(".gif")
+    }
+}
+
+/// The customization of a profile, [`None`] for every slot without an item
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProfileItemsEquipped {
+    #[serde(default, deserialize_with = "item_or_none")]
+    pub profile_background: Option<ProfileItem>,
+    #[serde(default, deserialize_with = "item_or_none")]
+    pub mini_profile_background: Option<ProfileItem>,
+    #[serde(default, deserialize_with = "item_or_none")]
+    pub avatar_frame: Option<ProfileItem>,
+    #[serde(default, deserialize_with = "item_or_none")]
+    pub animated_avatar: Option<ProfileItem>,
+    /// A profile theme, e.g. from a seasonal sale
+    #[serde(default, deserialize_with = "item_or_none")]
+    pub profile_modifier: Option<ProfileItem>,
+}
+
+type Response = Envelope<ProfileItemsEquipped>;
+
+impl Client {
+    /// Get the backgrounds, avatar frame, animated avatar and theme equipped on a profile
+    ///
+    /// Uses [`Endpoint::ProfileItemsEquipped`]
+    pub async fn get_profile_items_equipped(
+        &self,
+        steam_id: SteamId,
+    ) -> Result<ProfileItemsEquipped> {
+        let query = [("key", self.api_key()), ("steamid", &steam_id.to_string())];
+
+        let json = self
+            .get_json::<Response>(Endpoint::ProfileItemsEquipped, &query)
+            .await?;
+        Ok(json.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+    use crate::constants::COMMUNITY_IMAGES_URL;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("profile_items_equipped.json");
+        let items = json.into_inner();
+        assert!(items.mini_profile_background.is_none());
+        assert!(items.profile_modifier.is_none());
+
+        let background = items.profile_background.unwrap();
+        assert_eq!(background.community_item_id, 26372948815);
+        assert!(background.is_animated());
+        assert!(background.movie_webm_url().unwrap().ends_with(".webm"));
+        assert_eq!(background.image_small_url(), None);
+
+        let frame = items.avatar_frame.unwrap();
+        assert!(!frame.is_animated());
+        assert!(frame
+            .image_small_url()
+            .unwrap()
+            .starts_with(COMMUNITY_IMAGES_URL));
+
+        assert!(items.animated_avatar.unwrap().is_animated());
+    }
+}
@@ -0,0 +1,134 @@
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::html::profile;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Debug, Error)]
+pub enum ProfilePageError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the profile page
+    #[error("couldn't parse profile page ({0})")]
+    ParseError(#[from] profile::Error),
+}
+type Result<T> = std::result::Result<T, ProfilePageError>;
+
+pub use profile::{ProfileBackground, ProfilePage, Showcase, ShowcaseItem, ShowcaseScreenshot};
+
+impl Client {
+    /// Get the equipped background and the showcases of the profile of `steam_id`
+    ///
+    /// Private profiles have neither. The api only knows the equipped items,
+    /// see [`Client::get_profile_items_equipped`].
+    ///
+    /// Uses [`Endpoint::ProfilePage`]
+    pub async fn get_profile_page(&self, steam_id: SteamId) -> Result<ProfilePage> {
+        let sub_path = format!("{}/", steam_id);
+        let html = self.get_text(Endpoint::ProfilePage, &sub_path, &[]).await?;
+        let parser = profile::Parser::new()?;
+        let mut page = parser.parse(&html);
+        if self.minimal_fields() {
+            page.minimize();
+        }
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProfileBackground, Showcase};
+    use crate::model::html::profile::Parser;
+
+    #[test]
+    fn parses() {
+        let html = include_str!("../../../test_resources/profile.html");
+        let page = Parser::new().unwrap().parse(html);
+        assert!(matches!(
+            page.background,
+            Some(ProfileBackground::Image(url)) if url.ends_with("f2a6e3.jpg")
+        ));
+
+        assert_eq!(page.showcases.len(), 3);
+        let Showcase::Items(items) = &page.showcases[0] else {
+            panic!("not an item showcase: {:?}", page.showcases[0]);
+        };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].app_id, 440);
+        assert_eq!(items[1].instance_id, 11040578);
+        assert!(items[1].image_url.ends_with("/73fx73f"));
+
+        let Showcase::Screenshots(screenshots) = &page.showcases[1] else {
+            panic!("not a screenshot showcase: {:?}", page.showcases[1]);
+        };
+        assert_eq!(
+            screenshots
+                .iter()
+                .map(|screenshot| screenshot.published_file_id)
+                .collect::<Vec<_>>(),
+            [2934102281, 2934102282]
+        );
+        assert_eq!(
+            page.showcases[2],
+            Showcase::Other {
+                title: "Rarest Achievement Showcase".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_animated_background() {
+        let html = include_str!("../../../test_resources/profile_animated.html");
+        let page = Parser::new().unwrap().parse(html);
+        let Some(ProfileBackground::Animated { poster, videos }) = page.background else {
+            panic!("not animated: {:?}", page.background);
+        };
+        assert!(poster.ends_with(".jpg"));
+        assert_eq!(videos.len(), 2);
+        assert!(page.showcases.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn fetches() {
+        use crate::testing::MockSteam;
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let page = client
+            .get_profile_page(SteamId(76561197960287930))
+            .await
+            .unwrap();
+        assert_eq!(page.showcases.len(), 3);
+        assert_eq!(steam.received(Endpoint::ProfilePage).await, 1);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn minimizes() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let steam = MockSteam::start().await;
+        let client = steam
+            .client_builder()
+            .minimal_fields(true)
+            .build()
+            .await
+            .unwrap();
+
+        let page = client
+            .get_profile_page(SteamId(76561197960287930))
+            .await
+            .unwrap();
+        assert!(page.background.is_some());
+        assert_eq!(page.showcases.len(), 2);
+        assert!(!page
+            .showcases
+            .iter()
+            .any(|showcase| matches!(showcase, Showcase::Screenshots(_))));
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::{
+    Batched, PlayerBan, PlayerBanError, PlayerBans, PlayerSummaries, PlayerSummary,
+    PlayerSummaryError,
+};
+use crate::{Endpoint, SteamId};
+
+#[derive(Error, Debug)]
+pub enum ProfileWithBansError {
+    #[error(transparent)]
+    Summaries(#[from] PlayerSummaryError),
+
+    #[error(transparent)]
+    Bans(#[from] PlayerBanError),
+
+    /// The client was shut down before the bulk request finished, see [`Client::shutdown`]
+    #[error("cancelled")]
+    Cancelled,
+}
+type Result<T> = std::result::Result<T, ProfileWithBansError>;
+
+/// The summary and bans of a single profile, see [`Client::get_profiles_with_bans`]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProfileWithBans {
+    pub steam_id: SteamId,
+    /// [`None`], if the profile has no summary (e.g. it has been deleted)
+    pub summary: Option<PlayerSummary>,
+    /// [`None`], if the profile has no bans entry
+    pub bans: Option<PlayerBan>,
+}
+
+impl Client {
+    /// Get the summaries and bans of the profiles with the given [`SteamId`]
+    ///
+    /// The ids are split into chunks and both requests for a chunk are issued concurrently.
+    /// Ids that are in neither response are left out. Stops with
+    /// [`ProfileWithBansError::Cancelled`] once the client is [shut down](Client::shutdown).
+    ///
+    /// Uses [`Endpoint::PlayerSummaries`] and [`Endpoint::PlayerBans`]
+    pub async fn get_profiles_with_bans(
+        &self,
+        steam_ids: &[SteamId],
+    ) -> Result<HashMap<SteamId, ProfileWithBans>> {
+        let chunk_size = PlayerSummaries::MAX_IDS.min(PlayerBans::MAX_IDS);
+        let concurrency = Endpoint::PlayerSummaries
+            .concurrent_requests()
+            .min(Endpoint::PlayerBans.concurrent_requests());
+
+        let chunks = futures::stream::iter(steam_ids.chunks(chunk_size))
+            .map(|chunk| async move {
+                let (summaries, bans) = futures::join!(
+                    self.get_player_summaries(chunk.into()),
+                    self.get_player_bans(chunk.into()),
+                );
+                Ok::<_, ProfileWithBansError>((summaries?, bans?))
+            })
+            .buffer_unordered(concurrency)
+            .try_collect::<Vec<_>>();
+        let chunks = self
+            .until_shutdown(chunks)
+            .await
+            .ok_or(ProfileWithBansError::Cancelled)??;
+
+        let mut profiles = HashMap::with_capacity(steam_ids.len());
+        for (summaries, bans) in chunks {
+            let mut bans = bans.into_inner();
+            for (steam_id, summary) in summaries.into_inner() {
+                let profile = ProfileWithBans {
+                    steam_id,
+                    summary: Some(summary),
+                    bans: bans.remove(&steam_id),
+                };
+                profiles.insert(steam_id, profile);
+            }
+            for (steam_id, ban) in bans {
+                let profile = ProfileWithBans {
+                    steam_id,
+                    summary: None,
+                    bans: Some(ban),
+                };
+                profiles.insert(steam_id, profile);
+            }
+        }
+
+        Ok(profiles)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::testing::MockSteam;
+    use crate::{Endpoint, SteamId};
+
+    #[tokio::test]
+    async fn zips_summaries_and_bans() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let ids = [
+            SteamId(76561198230177976),
+            SteamId(76561199159691884),
+            SteamId(76561198196615742),
+        ];
+        let profiles = client.get_profiles_with_bans(&ids).await.unwrap();
+        assert_eq!(profiles.len(), 3);
+
+        let both = &profiles[&SteamId(76561199159691884)];
+        assert!(both.summary.is_some() && both.bans.is_some());
+        let summary_only = &profiles[&SteamId(76561198230177976)];
+        assert!(summary_only.bans.is_none());
+        let bans_only = &profiles[&SteamId(76561198196615742)];
+        assert!(bans_only.summary.is_none());
+
+        assert_eq!(steam.received(Endpoint::PlayerSummaries).await, 1);
+        assert_eq!(steam.received(Endpoint::PlayerBans).await, 1);
+    }
+
+    #[tokio::test]
+    async fn stops_when_cancelled() {
+        use std::time::Duration;
+
+        use wiremock::{Mock, ResponseTemplate};
+
+        use super::ProfileWithBansError;
+
+        let steam = MockSteam::start().await;
+        // a response that would arrive long after the token is cancelled
+        Mock::given(wiremock::matchers::path(Endpoint::PlayerBans.path()))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        let client = steam.client().await;
+
+        let stop = client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            stop.shutdown();
+        });
+        let ids = [SteamId(76561197960287930)];
+        let profiles =
+            tokio::time::timeout(Duration::from_secs(5), client.get_profiles_with_bans(&ids))
+                .await
+                .expect("cancelling should stop the requests in flight");
+        assert!(matches!(profiles, Err(ProfileWithBansError::Cancelled)));
+    }
+}
@@ -0,0 +1,94 @@
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::xml::profile;
+use crate::model::{Endpoint, SteamId};
+
+#[derive(Debug, Error)]
+pub enum ProfileXmlError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// There was an error while parsing the profile
+    #[error("couldn't parse profile ({0})")]
+    ParseError(#[from] profile::Error),
+}
+type Result<T> = std::result::Result<T, ProfileXmlError>;
+
+pub use profile::{MostPlayedGame, ProfileGroup, ProfileXml};
+
+impl Client {
+    /// Get the xml version of the profile of `steam_id`
+    ///
+    /// It has some fields the api doesn't, e.g. [`ProfileXml::member_since`] even for private
+    /// profiles, the most played games and the groups.
+    ///
+    /// Uses [`Endpoint::ProfilePage`]
+    pub async fn get_profile_xml(&self, steam_id: SteamId) -> Result<ProfileXml> {
+        let sub_path = format!("{}/", steam_id);
+        let xml = self
+            .get_text(Endpoint::ProfilePage, &sub_path, &[("xml", "1")])
+            .await?;
+        let mut profile = profile::parse(&xml)?;
+        if self.minimal_fields() {
+            profile.minimize();
+        }
+        Ok(profile)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use wiremock::matchers::query_param;
+    use wiremock::{Mock, ResponseTemplate};
+
+    use crate::testing::MockSteam;
+    use crate::{Endpoint, SteamId};
+
+    /// A server that serves the xml fixture, the html fixture is served without the parameter
+    async fn start() -> MockSteam {
+        let steam = MockSteam::start().await;
+        Mock::given(query_param("xml", "1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(load_test_str!("profile.xml"), "text/xml"),
+            )
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        steam
+    }
+
+    #[tokio::test]
+    async fn fetches() {
+        let steam = start().await;
+        let client = steam.client().await;
+
+        let profile = client
+            .get_profile_xml(SteamId(76561198089612262))
+            .await
+            .unwrap();
+        assert_eq!(profile.most_played_games.len(), 2);
+        assert!(profile.real_name.is_some());
+        assert_eq!(steam.received(Endpoint::ProfilePage).await, 1);
+    }
+
+    #[tokio::test]
+    async fn minimizes() {
+        let steam = start().await;
+        let client = steam
+            .client_builder()
+            .minimal_fields(true)
+            .build()
+            .await
+            .unwrap();
+
+        let profile = client
+            .get_profile_xml(SteamId(76561198089612262))
+            .await
+            .unwrap();
+        assert!(profile.real_name.is_none());
+        assert!(profile.location.is_none());
+        assert!(profile.avatar_full.is_empty());
+        assert!(!profile.persona_name.is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::{
+    Batched, PlayerSummaries, PlayerSummary, PlayerSummaryError, VanityResolution, VanityUrlError,
+};
+use crate::{Endpoint, SteamId};
+
+/// Why a single name of [`Client::profiles_from_vanities`] has no profile
+#[derive(Error, Debug, Clone)]
+pub enum VanityProfileError {
+    /// Resolving the vanity url failed, the error can't be cloned
+    #[error("couldn't resolve the vanity url: {0}")]
+    Resolve(Arc<VanityUrlError>),
+
+    #[error("vanity url not found")]
+    NotFound,
+
+    #[error("rate limited while resolving the vanity url")]
+    RateLimited,
+
+    /// The request for the chunk of summaries the id was in failed
+    #[error("couldn't get the summary of {steam_id}: {source}")]
+    Summary {
+        steam_id: SteamId,
+        source: Arc<PlayerSummaryError>,
+    },
+
+    /// The vanity url resolved, but Steam has no summary for the id
+    #[error("no summary for {0}")]
+    NoSummary(SteamId),
+
+    /// The client was shut down before the name was done, see [`Client::shutdown`]
+    #[error("cancelled")]
+    Cancelled,
+}
+
+impl Client {
+    /// Resolve the vanity urls `names` concurrently, the id or the error of each name
+    ///
+    /// Once the client is [shut down](Client::shutdown) every name is
+    /// [`VanityProfileError::Cancelled`].
+    pub(crate) async fn resolve_vanities<'a, S: AsRef<str>>(
+        &self,
+        names: &'a [S],
+    ) -> Vec<(&'a str, Result<SteamId, VanityProfileError>)> {
+        let resolutions = futures::stream::iter(names)
+            .map(|name| async move {
+                let name = name.as_ref();
+                (name, self.resolve_vanity_url(name).await)
+            })
+            .buffer_unordered(Endpoint::ResolveVanityUrl.concurrent_requests())
+            .collect::<Vec<_>>();
+        let Some(resolutions) = self.until_shutdown(resolutions).await else {
+            return names
+                .iter()
+                .map(|name| (name.as_ref(), Err(VanityProfileError::Cancelled)))
+                .collect();
+        };
+        resolutions
+            .into_iter()
+            .map(|(name, resolution)| {
+                let resolution = match resolution {
+                    Ok(VanityResolution::Found(steam_id)) => Ok(steam_id),
+                    Ok(VanityResolution::NotFound) => Err(VanityProfileError::NotFound),
+                    Ok(VanityResolution::RateLimited) => Err(VanityProfileError::RateLimited),
+                    Err(err) => Err(VanityProfileError::Resolve(Arc::new(err))),
+                };
+                (name, resolution)
+            })
+            .collect()
+    }
+
+    /// Resolve the vanity urls `names` and get the summaries of the profiles they belong to,
+    /// keyed by the names as they were passed
+    ///
+    /// Every name gets an entry, failures only affect the names they belong to.
+    /// The found ids are deduplicated and fetched in as few requests as possible.
+    /// Once the client is [shut down](Client::shutdown) the names that aren't done yet
+    /// get [`VanityProfileError::Cancelled`].
+    ///
+    /// Uses [`Endpoint::ResolveVanityUrl`] and [`Endpoint::PlayerSummaries`]
+    pub async fn profiles_from_vanities<S: AsRef<str>>(
+        &self,
+        names: &[S],
+    ) -> HashMap<String, Result<PlayerSummary, VanityProfileError>> {
+        let resolutions = self.resolve_vanities(names).await;
+        let mut profiles = HashMap::with_capacity(resolutions.len());
+        let mut found = Vec::with_capacity(resolutions.len());
+        for (name, resolution) in resolutions {
+            match resolution {
+                Ok(steam_id) => found.push((name.to_string(), steam_id)),
+                Err(err) => {
+                    profiles.insert(name.to_string(), Err(err));
+                }
+            }
+        }
+
+        let mut ids = found.iter().map(|(_, id)| *id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        let chunks = futures::stream::iter(PlayerSummaries::chunks(&ids))
+            .map(|chunk| async move { (chunk, self.get_player_summaries(chunk.into()).await) })
+            .buffer_unordered(Endpoint::PlayerSummaries.concurrent_requests())
+            .collect::<Vec<_>>();
+        let Some(chunks) = self.until_shutdown(chunks).await else {
+            for (name, _) in found {
+                profiles.insert(name, Err(VanityProfileError::Cancelled));
+            }
+            return profiles;
+        };
+
+        let mut summaries = HashMap::with_capacity(ids.len());
+        let mut failed = HashMap::new();
+        for (chunk, result) in chunks {
+            match result {
+                Ok(chunk) => summaries.extend(chunk.into_inner()),
+                Err(err) => {
+                    let err = Arc::new(err);
+                    failed.extend(chunk.iter().map(|id| (*id, err.clone())));
+                }
+            }
+        }
+
+        for (name, steam_id) in found {
+            let profile = match (summaries.get(&steam_id), failed.get(&steam_id)) {
+                (Some(summary), _) => Ok(summary.clone()),
+                (None, Some(err)) => Err(VanityProfileError::Summary {
+                    steam_id,
+                    source: err.clone(),
+                }),
+                (None, None) => Err(VanityProfileError::NoSummary(steam_id)),
+            };
+            profiles.insert(name, profile);
+        }
+        profiles
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use serde_json::json;
+
+    use super::VanityProfileError;
+    use crate::testing::MockSteam;
+    use crate::{Endpoint, SteamId};
+
+    #[tokio::test]
+    async fn resolves_and_fetches_summaries() {
+        use wiremock::matchers::query_param;
+        use wiremock::{Mock, ResponseTemplate};
+
+        let steam = MockSteam::start().await;
+        let found = |id: &str| json!({"response": {"steamid": id, "success": 1}});
+        for (name, body) in [
+            ("summary", found("76561198230177976")),
+            ("same", found("76561198230177976")),
+            ("deleted", found("76561197960265729")),
+            (
+                "missing",
+                json!({"response": {"success": 42, "message": "No match"}}),
+            ),
+        ] {
+            Mock::given(query_param("vanityurl", name))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .with_priority(1)
+                .mount(steam.server())
+                .await;
+        }
+        let client = steam.client().await;
+
+        let names = ["summary", "same", "deleted", "missing"];
+        let profiles = client.profiles_from_vanities(&names).await;
+        assert_eq!(profiles.len(), 4);
+        assert!(profiles["summary"].is_ok());
+        assert!(profiles["same"].is_ok());
+        assert!(matches!(
+            profiles["deleted"],
+            Err(VanityProfileError::NoSummary(SteamId(76561197960265729)))
+        ));
+        assert!(matches!(
+            profiles["missing"],
+            Err(VanityProfileError::NotFound)
+        ));
+        // both names resolved to the same id, which was only requested once
+        assert_eq!(steam.received(Endpoint::PlayerSummaries).await, 1);
+    }
+
+    #[tokio::test]
+    async fn stops_when_shut_down() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        client.shutdown();
+        let profiles = client.profiles_from_vanities(&["a", "b"]).await;
+        assert_eq!(profiles.len(), 2);
+        assert!(profiles
+            .values()
+            .all(|profile| matches!(profile, Err(VanityProfileError::Cancelled))));
+        assert_eq!(steam.received(Endpoint::ResolveVanityUrl).await, 0);
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::serde_util::from_str_or_number;
+use crate::model::{Endpoint, SteamIdStr, SteamTime};
+
+#[derive(Error, Debug)]
+pub enum PublishedFileDetailsError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, PublishedFileDetailsError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PublishedFileTag {
+    pub tag: String,
+}
+
+/// A workshop item
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublishedFileDetails {
+    #[serde(rename(deserialize = "publishedfileid"))]
+    #[serde(deserialize_with = "from_str_or_number")]
+    pub published_file_id: u64,
+    pub creator: SteamIdStr,
+    pub consumer_app_id: u32,
+    pub title: String,
+    pub description: String,
+    pub preview_url: String,
+    pub time_created: SteamTime,
+    pub time_updated: SteamTime,
+    pub subscriptions: u64,
+    pub favorited: u64,
+    pub lifetime_subscriptions: u64,
+    pub lifetime_favorited: u64,
+    pub views: u64,
+    #[serde(default)]
+    pub tags: Vec<PublishedFileTag>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct PublishedFiles {
+    /// Items that don't exist or are hidden are left out
+    inner: HashMap<u64, PublishedFileDetails>,
+}
+
+impl PublishedFiles {
+    pub fn into_inner(self) -> HashMap<u64, PublishedFileDetails> {
+        self.inner
+    }
+}
+
+impl Deref for PublishedFiles {
+    type Target = HashMap<u64, PublishedFileDetails>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// An entry is only complete if `result` is `1`, otherwise it just has the id
+#[derive(Deserialize)]
+struct ResponseEntry {
+    result: i32,
+    #[serde(flatten)]
+    details: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ResponseInner {
+    #[serde(default, rename(deserialize = "result"))]
+    _result: IgnoredAny,
+    #[serde(default, rename(deserialize = "resultcount"))]
+    _result_count: IgnoredAny,
+    #[serde(rename(deserialize = "publishedfiledetails"), default)]
+    published_file_details: Vec<ResponseEntry>,
+}
+
+type Response = Envelope<ResponseInner>;
+
+impl From<Response> for PublishedFiles {
+    fn from(value: Response) -> Self {
+        let inner = value
+            .into_inner()
+            .published_file_details
+            .into_iter()
+            .filter(|entry| entry.result == 1)
+            .filter_map(|entry| serde_json::from_value::<PublishedFileDetails>(entry.details).ok())
+            .map(|details| (details.published_file_id, details))
+            .collect();
+        PublishedFiles { inner }
+    }
+}
+
+/// The counters of a workshop item at one point in time
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishedFileStats {
+    pub at: DateTime<Utc>,
+    pub subscriptions: u64,
+    pub favorited: u64,
+    pub lifetime_subscriptions: u64,
+    pub lifetime_favorited: u64,
+    pub views: u64,
+}
+
+impl PublishedFileDetails {
+    /// The counters of the item, stamped with `at`
+    pub const fn stats(&self, at: DateTime<Utc>) -> PublishedFileStats {
+        PublishedFileStats {
+            at,
+            subscriptions: self.subscriptions,
+            favorited: self.favorited,
+            lifetime_subscriptions: self.lifetime_subscriptions,
+            lifetime_favorited: self.lifetime_favorited,
+            views: self.views,
+        }
+    }
+}
+
+/// How the counters of a workshop item changed between two samples
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishedFileTrend {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub subscriptions: i64,
+    pub favorited: i64,
+    pub lifetime_subscriptions: i64,
+    pub views: i64,
+}
+
+impl PublishedFileTrend {
+    /// Change of the current subscriptions per day
+    pub fn subscriptions_per_day(&self) -> f64 {
+        const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+        let days = (self.to - self.from).num_seconds() as f64 / SECONDS_PER_DAY;
+        if days <= 0.0 {
+            return 0.0;
+        }
+        self.subscriptions as f64 / days
+    }
+}
+
+/// Samples of the counters of a workshop item, collected by polling
+/// [`Client::get_published_file_details`] because Steam doesn't keep a history.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PublishedFileStatsHistory {
+    samples: Vec<PublishedFileStats>,
+}
+
+impl PublishedFileStatsHistory {
+    /// Add a sample, samples are kept sorted by time
+    pub fn record(&mut self, stats: PublishedFileStats) {
+        let pos = self.samples.partition_point(|sample| sample.at <= stats.at);
+        self.samples.insert(pos, stats);
+    }
+    pub fn samples(&self) -> &[PublishedFileStats] {
+        &self.samples
+    }
+    /// Drop samples taken before `before`, to bound the history of long-running pollers
+    pub fn prune(&mut self, before: DateTime<Utc>) {
+        self.samples.retain(|sample| sample.at >= before);
+    }
+    /// The change from the first sample at or after `since` to the latest sample,
+    /// [`None`] if there are less than two such samples
+    pub fn trend(&self, since: DateTime<Utc>) -> Option<PublishedFileTrend> {
+        let start = self.samples.partition_point(|sample| sample.at < since);
+        let window = &self.samples[start..];
+        let (first, last) = (window.first()?, window.last()?);
+        if window.len() < 2 {
+            return None;
+        }
+
+        let delta = |a: u64, b: u64| b as i64 - a as i64;
+        Some(PublishedFileTrend {
+            from: first.at,
+            to: last.at,
+            subscriptions: delta(first.subscriptions, last.subscriptions),
+            favorited: delta(first.favorited, last.favorited),
+            lifetime_subscriptions: delta(
+                first.lifetime_subscriptions,
+                last.lifetime_subscriptions,
+            ),
+            views: delta(first.views, last.views),
+        })
+    }
+}
+
+impl Client {
+    /// Get the details of the workshop items with the given ids
+    ///
+    /// Uses [`Endpoint::PublishedFileDetails`]
+    pub async fn get_published_file_details(&self, ids: &[u64]) -> Result<PublishedFiles> {
+        let count = ids.len().to_string();
+        let keys = (0..ids.len())
+            .map(|i| format!("publishedfileids[{}]", i))
+            .collect::<Vec<_>>();
+        let values = ids.iter().map(u64::to_string).collect::<Vec<_>>();
+
+        let mut form = vec![("itemcount", count.as_str())];
+        form.extend(
+            keys.iter()
+                .map(String::as_str)
+                .zip(values.iter().map(String::as_str)),
+        );
+
+        let resp = self
+            .post_form_json::<Response>(Endpoint::PublishedFileDetails, &form)
+            .await?;
+        Ok(resp.into())
+    }
+
+    /// Fetch the details of the workshop items and add a sample to their histories
+    pub async fn record_published_file_stats(
+        &self,
+        histories: &mut HashMap<u64, PublishedFileStatsHistory>,
+    ) -> Result<()> {
+        let ids = histories.keys().copied().collect::<Vec<_>>();
+        let files = self.get_published_file_details(&ids).await?;
+        let now = Utc::now();
+        for (id, details) in files.iter() {
+            if let Some(history) = histories.get_mut(id) {
+                history.record(details.stats(now));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{PublishedFileStatsHistory, PublishedFiles, Response};
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("published_file_details.json");
+        let files: PublishedFiles = json.into();
+        assert_eq!(files.len(), 1);
+
+        let file = &files[&3070290869];
+        assert_eq!(file.title, "aim_botz - Training");
+        assert_eq!(file.subscriptions, 1204567);
+        assert_eq!(file.tags.len(), 2);
+    }
+
+    #[test]
+    fn tracks_trend() {
+        let json: Response = load_test_json!("published_file_details.json");
+        let files: PublishedFiles = json.into();
+        let file = &files[&3070290869];
+
+        let start = Utc.timestamp_opt(1697210511, 0).unwrap();
+        let mut history = PublishedFileStatsHistory::default();
+        assert!(history.trend(start).is_none());
+
+        let mut later = file.stats(start + Duration::days(2));
+        later.subscriptions += 200;
+        later.views += 1000;
+        history.record(later);
+        history.record(file.stats(start));
+        assert!(history.trend(start + Duration::days(1)).is_none());
+
+        let trend = history.trend(start).unwrap();
+        assert_eq!(trend.subscriptions, 200);
+        assert_eq!(trend.views, 1000);
+        assert_eq!(trend.favorited, 0);
+        assert!((trend.subscriptions_per_day() - 100.0).abs() < f64::EPSILON);
+
+        history.prune(start + Duration::days(1));
+        assert_eq!(history.samples().len(), 1);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn records_stats() {
+        use std::collections::HashMap;
+
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let mut histories = HashMap::from([(3070290869, PublishedFileStatsHistory::default())]);
+        client
+            .record_published_file_stats(&mut histories)
+            .await
+            .unwrap();
+        client
+            .record_published_file_stats(&mut histories)
+            .await
+            .unwrap();
+        let history = &histories[&3070290869];
+        assert_eq!(history.samples().len(), 2);
+        assert_eq!(history.samples()[0].subscriptions, 1204567);
+    }
+}
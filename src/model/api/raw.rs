@@ -0,0 +1,297 @@
+//! Escape hatch for api endpoints and parameters this crate has no methods for.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::{Endpoint, Host};
+
+#[derive(Error, Debug)]
+pub enum RawError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("invalid json ({0})")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid query ({0})")]
+    Query(#[from] serde_urlencoded::ser::Error),
+
+    #[cfg(feature = "vdf")]
+    #[error(transparent)]
+    Vdf(#[from] crate::vdf::VdfError),
+
+    #[cfg(feature = "protobuf")]
+    #[error(transparent)]
+    Protobuf(#[from] crate::protobuf::ProtobufError),
+
+    /// A service method answered with an `x-eresult` other than `1`
+    #[cfg(feature = "protobuf")]
+    #[error("service method failed with eresult {0}")]
+    EResult(i32),
+}
+type Result<T> = std::result::Result<T, RawError>;
+
+/// The output formats of [`Host::Api`] endpoints, sent as the `format` parameter
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    #[default]
+    Json,
+    Xml,
+    /// Valve's KeyValues, see [`crate::vdf`]
+    Vdf,
+}
+
+impl Format {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Xml => "xml",
+            Format::Vdf => "vdf",
+        }
+    }
+}
+
+impl Client {
+    /// Request `endpoint` with `query` and return the body in `format` as text,
+    /// the api-key is added for [`Host::Api`] endpoints.
+    ///
+    /// Retried like [`Client::get_json`].
+    pub async fn get_api_text(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, &str)],
+        format: Format,
+    ) -> Result<String> {
+        let mut query = query.to_vec();
+        if endpoint.host() == Host::Api {
+            query.push(("key", self.api_key()));
+        }
+        query.push(("format", format.as_str()));
+        Ok(self.get_text(endpoint, "", &query).await?)
+    }
+
+    /// Request `endpoint` like [`Client::get_api_text`] and parse the json into `T`,
+    /// e.g. a [`serde_json::Value`] for fields the models don't have
+    pub async fn get_api_json<T>(&self, endpoint: Endpoint, query: &[(&str, &str)]) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let text = self.get_api_text(endpoint, query, Format::Json).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Request `endpoint` like [`Client::get_api_json`] with the query taken from a struct,
+    /// see [`crate::query`] to format its fields
+    pub async fn get_api_json_with<Q, T>(&self, endpoint: Endpoint, query: &Q) -> Result<T>
+    where
+        Q: Serialize,
+        T: DeserializeOwned,
+    {
+        let pairs = crate::query::to_pairs(query)?;
+        let query = pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        self.get_api_json(endpoint, &query).await
+    }
+
+    /// Request `endpoint` in `format` like [`Client::get_api_text`] and parse it into
+    /// a [`KeyValues`](crate::vdf::KeyValues) tree, which looks the same for every format
+    #[cfg(feature = "vdf")]
+    pub async fn get_api_key_values(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, &str)],
+        format: Format,
+    ) -> Result<crate::vdf::KeyValues> {
+        let text = self.get_api_text(endpoint, query, format).await?;
+        let tree = match format {
+            Format::Json => crate::vdf::from_json(&text)?,
+            Format::Xml => crate::vdf::from_xml(&text)?,
+            Format::Vdf => crate::vdf::parse(&text)?,
+        };
+        Ok(tree)
+    }
+
+    /// Call a protobuf service method with `request` and decode its answer,
+    /// see [`crate::protobuf`]
+    ///
+    /// `request` is sent base64 encoded in `input_protobuf_encoded`,
+    /// the api-key is added for [`Host::Api`] endpoints.
+    #[cfg(feature = "protobuf")]
+    pub async fn call_protobuf<Req, Resp>(&self, endpoint: Endpoint, request: &Req) -> Result<Resp>
+    where
+        Req: crate::protobuf::Message,
+        Resp: crate::protobuf::Message,
+    {
+        use base64::Engine;
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(request.to_bytes());
+        let mut query = vec![("input_protobuf_encoded", encoded.as_str())];
+        if endpoint.host() == Host::Api {
+            query.push(("key", self.api_key()));
+        }
+
+        let resp = self.get_response(endpoint, &query).await?;
+        // the result of the call, the status is 200 even if it failed
+        let eresult = resp
+            .headers()
+            .get("x-eresult")
+            .and_then(|value| value.to_str().ok()?.parse::<i32>().ok())
+            .unwrap_or(1);
+        if eresult != 1 {
+            return Err(RawError::EResult(eresult));
+        }
+        let body = resp.bytes().await?;
+        Ok(Resp::decode(&body)?)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::Format;
+    use crate::testing::MockSteam;
+    use crate::Endpoint;
+
+    #[tokio::test]
+    async fn requests_json() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let json: serde_json::Value = client
+            .get_api_json(
+                Endpoint::PlayerSteamLevel,
+                &[("steamid", "76561198196615742")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(json["response"]["player_level"], 135);
+
+        let text = client
+            .get_api_text(Endpoint::PlayerSteamLevel, &[], Format::Json)
+            .await
+            .unwrap();
+        assert!(text.contains("player_level"));
+    }
+
+    #[tokio::test]
+    async fn requests_json_with_struct() {
+        use serde::Serialize;
+        use wiremock::matchers::{path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use crate::SteamId;
+
+        #[derive(Serialize)]
+        struct Request {
+            #[serde(with = "crate::query::steam_ids")]
+            steamids: Vec<SteamId>,
+        }
+
+        let steam = MockSteam::start().await;
+        Mock::given(path(Endpoint::PlayerBans.path()))
+            .and(query_param(
+                "steamids",
+                "76561197960287930,76561198196615742",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"players":[]}"#))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+
+        let client = steam.client().await;
+        let request = Request {
+            steamids: vec![SteamId(76561197960287930), SteamId(76561198196615742)],
+        };
+        let json: serde_json::Value = client
+            .get_api_json_with(Endpoint::PlayerBans, &request)
+            .await
+            .unwrap();
+        assert_eq!(json["players"], serde_json::json!([]));
+    }
+
+    #[cfg(feature = "vdf")]
+    #[tokio::test]
+    async fn requests_vdf() {
+        use wiremock::matchers::{path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let steam = MockSteam::start().await;
+        Mock::given(path(Endpoint::PlayerSteamLevel.path()))
+            .and(query_param("format", "vdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("\"response\"\n{\n\t\"player_level\"\t\"135\"\n}\n"),
+            )
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+
+        let client = steam.client().await;
+        let tree = client
+            .get_api_key_values(Endpoint::PlayerSteamLevel, &[], Format::Vdf)
+            .await
+            .unwrap();
+        assert_eq!(
+            tree.get_path(&["response", "player_level"])
+                .and_then(|value| value.as_str()),
+            Some("135")
+        );
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[tokio::test]
+    async fn calls_protobuf() {
+        use wiremock::matchers::{path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use super::RawError;
+        use crate::protobuf::{Decoder, Encoder, Message, ProtobufError};
+
+        struct Level(u64);
+        impl Message for Level {
+            fn encode(&self, encoder: &mut Encoder) {
+                encoder.uint64(1, self.0);
+            }
+            fn decode(bytes: &[u8]) -> Result<Self, ProtobufError> {
+                let mut level = 0;
+                for field in Decoder::new(bytes) {
+                    let (number, value) = field?;
+                    if number == 1 {
+                        level = value.as_u64()?;
+                    }
+                }
+                Ok(Self(level))
+            }
+        }
+
+        let steam = MockSteam::start().await;
+        // field 1 set to 135, base64 of [0x08, 0x87, 0x01]
+        Mock::given(path(Endpoint::PlayerSteamLevel.path()))
+            .and(query_param("input_protobuf_encoded", "CIcB"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(Level(135).to_bytes()))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        Mock::given(path(Endpoint::PlayerSteamLevel.path()))
+            .and(query_param("input_protobuf_encoded", "CAE="))
+            .respond_with(ResponseTemplate::new(200).insert_header("x-eresult", "8"))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+
+        let client = steam.client().await;
+        let level: Level = client
+            .call_protobuf(Endpoint::PlayerSteamLevel, &Level(135))
+            .await
+            .unwrap();
+        assert_eq!(level.0, 135);
+
+        let err = client
+            .call_protobuf::<_, ()>(Endpoint::PlayerSteamLevel, &Level(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RawError::EResult(8)));
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::{Endpoint, SteamTime};
+
+#[derive(Error, Debug)]
+pub enum ServerInfoError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, ServerInfoError>;
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct Response {
+    #[serde(rename = "servertime")]
+    server_time: SteamTime,
+    /// The same time as text, e.g. `Fri Oct 13 08:21:51 2023`
+    #[serde(rename = "servertimestring")]
+    _server_time_string: String,
+}
+
+impl Client {
+    /// Get the current time of the Web API servers, which needs no api-key
+    ///
+    /// Comparing it with the local clock tells how far timestamps like
+    /// [`PlayerSummary::time_created`](crate::api::PlayerSummary::time_created) are off.
+    ///
+    /// Uses [`Endpoint::ServerInfo`]
+    pub async fn get_webapi_server_info(&self) -> Result<SteamTime> {
+        let json = self.get_json::<Response>(Endpoint::ServerInfo, &[]).await?;
+        Ok(json.server_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("server_info.json");
+        // the string is in Pacific time, the timestamp is utc
+        assert_eq!(json.server_time.timestamp(), 1697210511);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let time = client.get_webapi_server_info().await.unwrap();
+        assert_eq!(time.timestamp(), 1697210511);
+        assert_eq!(steam.received(Endpoint::ServerInfo).await, 1);
+    }
+}
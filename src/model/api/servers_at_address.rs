@@ -0,0 +1,135 @@
+use std::net::{IpAddr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamIdStr};
+
+#[derive(Error, Debug)]
+pub enum ServersAtAddressError {
+    /// Steam answered with `success: false`, e.g. for addresses it can't parse
+    #[error("couldn't get the servers: {0}")]
+    Failed(String),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, ServersAtAddressError>;
+
+/// A game server registered with the master server, see [`Client::get_servers_at_address`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameServer {
+    /// `ip:port` the server answers queries on
+    pub addr: SocketAddr,
+    /// Index in the master server list, `-1` if it isn't listed
+    #[serde(rename = "gmsindex")]
+    pub gms_index: i32,
+    #[serde(rename = "steamid")]
+    pub steam_id: SteamIdStr,
+    #[serde(rename = "appid")]
+    pub app_id: u32,
+    /// Mod directory of the game, e.g. `tf`
+    #[serde(rename = "gamedir")]
+    pub game_dir: String,
+    /// Master server region code, `255` for the whole world
+    pub region: i32,
+    /// Whether the server is secured by VAC
+    pub secure: bool,
+    pub lan: bool,
+    #[serde(rename = "gameport")]
+    pub game_port: u16,
+    /// SourceTV port, `0` if it has none
+    #[serde(rename = "specport")]
+    pub spec_port: u16,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct Inner {
+    success: bool,
+    #[serde(default)]
+    servers: Vec<GameServer>,
+    #[serde(default)]
+    message: String,
+}
+
+type Response = Envelope<Inner>;
+
+impl TryFrom<Response> for Vec<GameServer> {
+    type Error = ServersAtAddressError;
+    fn try_from(value: Response) -> Result<Self> {
+        let inner = value.into_inner();
+        if !inner.success {
+            return Err(ServersAtAddressError::Failed(inner.message));
+        }
+        Ok(inner.servers)
+    }
+}
+
+impl Client {
+    /// Get the game servers hosted at `addr`, which needs no api-key
+    ///
+    /// Uses [`Endpoint::ServersAtAddress`]
+    pub async fn get_servers_at_address(&self, addr: IpAddr) -> Result<Vec<GameServer>> {
+        let addr = addr.to_string();
+        self.get_json::<Response>(Endpoint::ServersAtAddress, &[("addr", addr.as_str())])
+            .await?
+            .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameServer, Response};
+    use crate::model::SteamIdStr;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("servers_at_address.json");
+        let servers = Vec::<GameServer>::try_from(json).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].addr, "162.254.193.6:27015".parse().unwrap());
+        assert_eq!(servers[0].gms_index, -1);
+        assert_eq!(servers[0].steam_id, SteamIdStr(85568392932669237));
+        assert_eq!(servers[0].app_id, 730);
+        assert_eq!(servers[1].game_dir, "tf");
+        assert!(servers[0].secure && !servers[1].secure);
+        assert_eq!(servers[1].spec_port, 27020);
+    }
+
+    #[test]
+    fn fails() {
+        let json: Response = serde_json::from_str(
+            r#"{"response":{"success":false,"message":"Invalid IP address"}}"#,
+        )
+        .unwrap();
+        assert!(Vec::<GameServer>::try_from(json).is_err());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let json: Response = load_test_json!("servers_at_address.json");
+        let servers = Vec::<GameServer>::try_from(json).unwrap();
+        let decoded = assert_bincode_round_trip!(servers, Vec<GameServer>);
+        assert_eq!(decoded, servers);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let servers = client
+            .get_servers_at_address([162, 254, 193, 6].into())
+            .await
+            .unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(steam.received(Endpoint::ServersAtAddress).await, 1);
+    }
+}
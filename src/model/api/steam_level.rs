@@ -2,8 +2,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::PLAYER_STEAM_LEVEL_API;
-use crate::model::SteamId;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamId};
 
 #[derive(Error, Debug)]
 pub enum SteamLevelError {
@@ -12,7 +12,7 @@ pub enum SteamLevelError {
 }
 type Result<T> = std::result::Result<T, SteamLevelError>;
 
-#[derive(Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub struct SteamLevel(Option<u64>);
 
 impl SteamLevel {
@@ -28,30 +28,28 @@ impl SteamLevel {
 }
 
 #[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
 struct ResponseInner {
     player_level: Option<u64>,
 }
 
-#[derive(Deserialize, Debug)]
-struct Response {
-    response: ResponseInner,
-}
+type Response = Envelope<ResponseInner>;
 
 impl From<Response> for SteamLevel {
     fn from(value: Response) -> Self {
-        SteamLevel(value.response.player_level)
+        SteamLevel(value.into_inner().player_level)
     }
 }
 
 impl Client {
     /// Get the Steam level of the given [`SteamId`]
     ///
-    /// Uses [`PLAYER_STEAM_LEVEL_API`]
+    /// Uses [`Endpoint::PlayerSteamLevel`]
     pub async fn get_player_steam_level(&self, id: SteamId) -> Result<SteamLevel> {
         let query = [("key", self.api_key()), ("steamid", &id.to_string())];
 
         let json = self
-            .get_json::<Response>(PLAYER_STEAM_LEVEL_API, &query)
+            .get_json::<Response>(Endpoint::PlayerSteamLevel, &query)
             .await?;
 
         Ok(json.into())
@@ -75,4 +73,12 @@ mod tests {
         let lvl: SteamLevel = json.into();
         assert_eq!(lvl, SteamLevel(None));
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let levels = vec![SteamLevel(Some(135)), SteamLevel(None)];
+        let decoded = assert_bincode_round_trip!(levels, Vec<SteamLevel>);
+        assert_eq!(decoded, levels);
+    }
 }
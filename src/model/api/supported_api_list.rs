@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::Endpoint;
+
+#[derive(Error, Debug)]
+pub enum SupportedApiListError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, SupportedApiListError>;
+
+/// A parameter of an [`ApiMethod`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+pub struct ApiParameter {
+    pub name: String,
+    /// e.g. `string`, `uint64` or `{enum}`
+    #[serde(rename = "type")]
+    pub param_type: String,
+    pub optional: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A method of an [`ApiInterface`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+pub struct ApiMethod {
+    pub name: String,
+    pub version: u32,
+    /// `GET` or `POST`
+    #[serde(rename = "httpmethod")]
+    pub http_method: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<ApiParameter>,
+}
+
+impl ApiMethod {
+    /// The path of the method, e.g. `GetPlayerSummaries/v2/`
+    pub fn path(&self) -> String {
+        format!("{}/v{}/", self.name, self.version)
+    }
+}
+
+/// An interface like `ISteamUser`, see [`Client::get_supported_api_list`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+pub struct ApiInterface {
+    pub name: String,
+    pub methods: Vec<ApiMethod>,
+}
+
+impl ApiInterface {
+    /// The newest version of the method called `name`
+    pub fn method(&self, name: &str) -> Option<&ApiMethod> {
+        self.methods
+            .iter()
+            .filter(|method| method.name == name)
+            .max_by_key(|method| method.version)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct ApiList {
+    interfaces: Vec<ApiInterface>,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct Response {
+    #[serde(rename = "apilist")]
+    api_list: ApiList,
+}
+
+impl Client {
+    /// Get every interface and method the api-key may call, including the ones of
+    /// publisher keys and of apps the key belongs to
+    ///
+    /// Uses [`Endpoint::SupportedApiList`]
+    pub async fn get_supported_api_list(&self) -> Result<Vec<ApiInterface>> {
+        let json = self
+            .get_json::<Response>(Endpoint::SupportedApiList, &[("key", self.api_key())])
+            .await?;
+        Ok(json.api_list.interfaces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Response;
+
+    #[test]
+    fn parses() {
+        let json: Response = load_test_json!("supported_api_list.json");
+        let interfaces = json.api_list.interfaces;
+        assert_eq!(interfaces.len(), 3);
+
+        let user = &interfaces[1];
+        assert_eq!(user.name, "ISteamUser");
+        let summaries = user.method("GetPlayerSummaries").unwrap();
+        assert_eq!(summaries.path(), "GetPlayerSummaries/v2/");
+        assert_eq!(summaries.http_method, "GET");
+        assert_eq!(summaries.parameters[1].name, "steamids");
+        assert!(!summaries.parameters[1].optional);
+        let vanity = user.method("ResolveVanityURL").unwrap();
+        assert_eq!(vanity.parameters[2].param_type, "int32");
+        assert!(vanity.parameters[2].optional);
+        assert!(user.method("GetFriendList").is_none());
+        assert!(interfaces[2].methods[0].description.is_some());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn requests_with_key() {
+        use crate::testing::{MockSteam, API_KEY};
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let interfaces = client.get_supported_api_list().await.unwrap();
+        assert_eq!(interfaces.len(), 3);
+
+        let requests = steam.server().received_requests().await.unwrap();
+        let request = requests
+            .iter()
+            .find(|request| request.url.path() == Endpoint::SupportedApiList.path())
+            .unwrap();
+        assert!(request
+            .url
+            .query_pairs()
+            .any(|(name, value)| name == "key" && value == API_KEY));
+    }
+}
@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::USER_SEARCH_API;
+use crate::constants::USER_SEARCH_RESULTS_PER_PAGE;
 use crate::model::html::user_search;
+use crate::model::Endpoint;
 
 #[derive(Debug, Error)]
 pub enum UserSearchError {
@@ -27,7 +28,7 @@ pub enum UserSearchError {
 }
 type Result<T> = std::result::Result<T, UserSearchError>;
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserSearchPage {
     pub search_string: String,
     pub total_result_count: usize,
@@ -36,7 +37,17 @@ pub struct UserSearchPage {
     pub results: Vec<user_search::UserSearchEntry>,
 }
 
+/// The results of a search with the `apps` filter, see [`Client::get_app_search_page`]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppSearchPage {
+    pub search_string: String,
+    pub total_result_count: usize,
+    pub search_page: usize,
+    pub results: Vec<user_search::AppSearchEntry>,
+}
+
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
 struct Response {
     success: i32,
     search_text: String,
@@ -55,44 +66,115 @@ impl TryFrom<Response> for UserSearchPage {
 
         let parser = user_search::Parser::new()?;
         let results = parser.parse(&value.html)?;
+        let search_page = value.search_page()?;
+
+        Ok(Self {
+            search_string: value.search_text,
+            total_result_count: value.search_result_count,
+            search_filter: value.search_filter,
+            search_page,
+            results,
+        })
+    }
+}
+
+impl TryFrom<Response> for AppSearchPage {
+    type Error = UserSearchError;
+    fn try_from(value: Response) -> Result<Self> {
+        if value.success != 1 {
+            return Err(UserSearchError::NoSuccess);
+        }
+
+        let parser = user_search::Parser::new()?;
+        let results = parser.parse_apps(&value.html)?;
+        let search_page = value.search_page()?;
 
+        Ok(Self {
+            search_string: value.search_text,
+            total_result_count: value.search_result_count,
+            search_page,
+            results,
+        })
+    }
+}
+
+impl Response {
+    fn search_page(&self) -> Result<usize> {
         // Steam sometimes returns this as a number
         // and sometimes as a string 🤡
-        let search_page = match value.search_page {
+        let search_page = match &self.search_page {
             serde_json::Value::Number(num) => num.as_u64(),
             serde_json::Value::String(str) => str.parse::<u64>().ok(),
             _ => None,
         }
         .ok_or(UserSearchError::InvalidSearchPage)?;
+        Ok(search_page as usize)
+    }
 
-        Ok(Self {
-            search_string: value.search_text,
-            total_result_count: value.search_result_count,
-            search_filter: value.search_filter,
-            search_page: search_page as usize,
-            results,
-        })
+    /// The search succeeded and claims there are results on `page`, but the payload is empty
+    fn is_transiently_empty(&self, page: usize) -> bool {
+        let skipped = page.saturating_sub(1) * USER_SEARCH_RESULTS_PER_PAGE;
+        self.success == 1 && self.html.trim().is_empty() && self.search_result_count > skipped
     }
 }
 
 impl Client {
-    /// Query [`USER_SEARCH_API`] for the name `query` and the page `page`
+    /// Request a page of [`Endpoint::UserSearch`], again while it's transiently empty,
+    /// see [`ClientBuilder::retry_on_empty`](crate::ClientBuilder::retry_on_empty)
+    async fn search(&self, query: &[(&str, &str)], page: usize) -> Result<Response> {
+        // failed attempts and empty results take their retries from the same budget
+        let mut budget = self.retry_budget();
+        loop {
+            let resp = self
+                .get_json_with_budget::<Response>(Endpoint::UserSearch, query, &mut budget)
+                .await?;
+            if !resp.is_transiently_empty(page)
+                || !self.retry_empty(Endpoint::UserSearch, &mut budget).await
+            {
+                break Ok(resp);
+            }
+        }
+    }
+
+    /// Query [`Endpoint::UserSearch`] for the name `query` and the page `page`
     pub async fn get_search_page(&self, query: &str, page: usize) -> Result<UserSearchPage> {
+        let page_str = page.to_string();
         let query = [
             ("filter", "users"),
             ("text", query),
             ("sessionid", self.session_id()),
-            ("page", &page.to_string()),
+            ("page", page_str.as_str()),
+        ];
+
+        let resp = self.search(&query, page).await?;
+        let mut page: UserSearchPage = resp.try_into()?;
+        if self.minimal_fields() {
+            page.results
+                .iter_mut()
+                .for_each(user_search::UserSearchEntry::minimize);
+        }
+        Ok(page)
+    }
+
+    /// Query [`Endpoint::UserSearch`] for game hubs named like `query`,
+    /// a lightweight way to resolve an app name to its app id
+    pub async fn get_app_search_page(&self, query: &str, page: usize) -> Result<AppSearchPage> {
+        let page_str = page.to_string();
+        let query = [
+            ("filter", "apps"),
+            ("text", query),
+            ("sessionid", self.session_id()),
+            ("page", page_str.as_str()),
         ];
 
-        let resp = self.get_json::<Response>(USER_SEARCH_API, &query).await?;
+        let resp = self.search(&query, page).await?;
         resp.try_into()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Response, UserSearchPage};
+    use super::{AppSearchPage, Response, UserSearchPage};
     use crate::model::SteamId;
 
     #[test]
@@ -112,4 +194,122 @@ mod tests {
         assert_eq!(snd.aliases.len(), 0);
         assert_eq!(snd.steam_id(), Some(SteamId(76561197971683832)));
     }
+
+    #[test]
+    fn parses_apps() {
+        let json: Response = load_test_json!("app_search.json");
+        let search: AppSearchPage = json.try_into().unwrap();
+
+        assert_eq!(search.search_string, "counter-strike");
+        assert_eq!(search.search_page, 1);
+        assert_eq!(search.results.len(), 3);
+
+        let fst = &search.results[0];
+        assert_eq!(fst.app_id, 730);
+        assert_eq!(fst.name, "Counter-Strike 2");
+        assert!(fst.icon_url.contains("/apps/730/"));
+        assert_eq!(search.results[2].name, "Counter-Strike: Source");
+    }
+
+    #[test]
+    fn detects_empty_payloads() {
+        let mut json: Response = load_test_json!("user_search.json");
+        assert!(!json.is_transiently_empty(1));
+        json.html.clear();
+        assert!(json.is_transiently_empty(1));
+        // past the last page the payload is empty for real
+        assert!(!json.is_transiently_empty(5000));
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn retries_empty_payloads() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let mut json: serde_json::Value =
+            serde_json::from_str(crate::testing::fixtures::USER_SEARCH).unwrap();
+        json["html"] = "".into();
+        steam.mount_json_times(Endpoint::UserSearch, &json, 1).await;
+
+        let client = steam
+            .client_builder()
+            .retry_on_empty(true)
+            .build()
+            .await
+            .unwrap();
+        let page = client.get_search_page("sauce", 1).await.unwrap();
+        assert_eq!(page.results.len(), 20);
+        assert_eq!(client.empty_results(), 1);
+        assert_eq!(client.endpoint_retries(Endpoint::UserSearch), 1);
+
+        steam.mount_json_times(Endpoint::UserSearch, &json, 1).await;
+        let client = steam.client().await;
+        let page = client.get_search_page("sauce", 1).await.unwrap();
+        assert!(page.results.is_empty());
+        assert_eq!(client.empty_results(), 1);
+        assert_eq!(client.total_retries(), 0);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn shares_the_retry_budget() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let mut json: serde_json::Value =
+            serde_json::from_str(crate::testing::fixtures::USER_SEARCH).unwrap();
+        json["html"] = "".into();
+        // mocks with the same priority answer in the order they were mounted
+        for _ in 0..2 {
+            steam.mount_status(Endpoint::UserSearch, 500, 1).await;
+            steam.mount_json_times(Endpoint::UserSearch, &json, 1).await;
+        }
+
+        let client = steam
+            .client_builder()
+            .retries(3)
+            .retry_on_empty(true)
+            .build()
+            .await
+            .unwrap();
+        // 500, empty, 500 use up the 3 retries, the second empty result is handed out
+        let page = client.get_search_page("sauce", 1).await.unwrap();
+        assert!(page.results.is_empty());
+        assert_eq!(client.endpoint_retries(Endpoint::UserSearch), 3);
+        assert_eq!(client.empty_results(), 2);
+        // the client asked the search page for its session id once
+        assert_eq!(steam.received(Endpoint::UserSearch).await, 1 + 4);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn minimizes() {
+        use crate::testing::MockSteam;
+
+        let steam = MockSteam::start().await;
+        let client = steam
+            .client_builder()
+            .minimal_fields(true)
+            .build()
+            .await
+            .unwrap();
+        let page = client.get_search_page("sauce", 1).await.unwrap();
+        assert!(!page.results.is_empty());
+        assert!(page
+            .results
+            .iter()
+            .all(|entry| entry.avatar_full.is_empty() && !entry.persona_name.is_empty()));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let json: Response = load_test_json!("user_search.json");
+        let search: UserSearchPage = json.try_into().unwrap();
+        let decoded = assert_bincode_round_trip!(search, UserSearchPage);
+        assert_eq!(decoded.results.len(), search.results.len());
+    }
 }
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamId, SteamIdStr};
+
+#[derive(Error, Debug)]
+pub enum UserStatsError {
+    /// Profiles with private game details are answered with `403 Forbidden`,
+    /// games without stats with `400 Bad Request`
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+}
+type Result<T> = std::result::Result<T, UserStatsError>;
+
+/// The value of a stat, its type depends on the stats schema of the game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum StatValue {
+    Int(i64),
+    Float(f64),
+}
+
+impl StatValue {
+    /// The value as a float, no matter the type in the schema
+    pub const fn as_f64(self) -> f64 {
+        match self {
+            StatValue::Int(value) => value as f64,
+            StatValue::Float(value) => value,
+        }
+    }
+
+    /// [`None`], if the stat is a float
+    pub const fn as_i64(self) -> Option<i64> {
+        match self {
+            StatValue::Int(value) => Some(value),
+            StatValue::Float(_) => None,
+        }
+    }
+}
+
+/// The stats and achievements of a user in a single game
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UserStats {
+    pub steam_id: SteamId,
+    pub game_name: String,
+    /// Keyed by the api name of the stat, stats that were never set are left out
+    pub stats: HashMap<String, StatValue>,
+    /// Keyed by the api name of the achievement, only unlocked achievements are listed
+    pub achievements: HashMap<String, bool>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct RawStat {
+    name: String,
+    value: StatValue,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct RawAchievement {
+    name: String,
+    achieved: u8,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict_parse", serde(deny_unknown_fields))]
+struct RawUserStats {
+    #[serde(rename = "steamID")]
+    steam_id: SteamIdStr,
+    #[serde(rename = "gameName")]
+    game_name: String,
+    /// Left out for games without stats
+    #[serde(default)]
+    stats: Vec<RawStat>,
+    /// Left out if no achievement is unlocked
+    #[serde(default)]
+    achievements: Vec<RawAchievement>,
+}
+
+type Response = Envelope<RawUserStats>;
+
+impl From<Response> for UserStats {
+    fn from(value: Response) -> Self {
+        let value = value.into_inner();
+        UserStats {
+            steam_id: value.steam_id.into(),
+            game_name: value.game_name,
+            stats: value
+                .stats
+                .into_iter()
+                .map(|stat| (stat.name, stat.value))
+                .collect(),
+            achievements: value
+                .achievements
+                .into_iter()
+                .map(|achievement| (achievement.name, achievement.achieved != 0))
+                .collect(),
+        }
+    }
+}
+
+impl Client {
+    /// Get the stats and unlocked achievements of the profile with the given [`SteamId`]
+    /// in the game `app_id`
+    ///
+    /// Uses [`Endpoint::UserStatsForGame`]
+    pub async fn get_user_stats_for_game(&self, id: SteamId, app_id: u32) -> Result<UserStats> {
+        let query = [
+            ("key", self.api_key()),
+            ("steamid", &id.to_string()),
+            ("appid", &app_id.to_string()),
+        ];
+
+        let resp = self
+            .get_json::<Response>(Endpoint::UserStatsForGame, &query)
+            .await?;
+        Ok(resp.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Response, StatValue, UserStats};
+    use crate::SteamId;
+
+    #[test]
+    fn parses() {
+        let resp: Response = load_test_json!("user_stats.json");
+        let stats: UserStats = resp.into();
+        assert_eq!(stats.steam_id, SteamId(76561198089612262));
+        assert_eq!(stats.game_name, "Team Fortress 2");
+
+        assert_eq!(stats.stats.len(), 4);
+        let kills = stats.stats["Scout.accum.iNumberOfKills"];
+        assert_eq!(kills, StatValue::Int(2094));
+        assert_eq!(kills.as_f64(), 2094.0);
+        let damage = stats.stats["Sniper.max.fDamageDealtPerMinute"];
+        assert_eq!(damage, StatValue::Float(812.25));
+        assert_eq!(damage.as_i64(), None);
+
+        assert_eq!(stats.achievements.len(), 3);
+        assert!(stats.achievements["TF_GET_HEALPOINTS"]);
+    }
+
+    #[test]
+    fn parses_games_without_stats() {
+        let resp: Response = serde_json::from_str(
+            r#"{"playerstats":{"steamID":"76561198089612262","gameName":"Portal"}}"#,
+        )
+        .unwrap();
+        let stats: UserStats = resp.into();
+        assert!(stats.stats.is_empty() && stats.achievements.is_empty());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn fetches() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+
+        let stats = client
+            .get_user_stats_for_game(SteamId(76561198089612262), 440)
+            .await
+            .unwrap();
+        assert_eq!(stats.achievements.len(), 3);
+        assert_eq!(steam.received(Endpoint::UserStatsForGame).await, 1);
+    }
+}
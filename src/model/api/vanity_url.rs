@@ -1,11 +1,19 @@
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::client::Client;
-use crate::constants::VANITY_API;
-use crate::model::SteamIdStr;
+use crate::model::api::Envelope;
+use crate::model::{Endpoint, SteamIdStr};
 use crate::steam_id::SteamId;
 
+/// `success` of a resolved vanity url
+pub const SUCCESS_FOUND: u32 = 1;
+/// `success` of a vanity url nobody uses
+pub const SUCCESS_NO_MATCH: u32 = 42;
+/// `success` when Steam refuses to answer because of too many requests
+pub const SUCCESS_RATE_LIMITED: u32 = 84;
+
 #[derive(Error, Debug)]
 pub enum VanityUrlError {
     #[error(transparent)]
@@ -14,51 +22,165 @@ pub enum VanityUrlError {
     #[error("invalid steam-id: {0}")]
     Json(#[from] serde_json::Error),
 
-    #[error("vanity url '{0}' not found")]
-    NotFound(String),
+    /// Steam answered with a `success` that isn't one of the known codes,
+    /// or with [`SUCCESS_FOUND`] but without an id
+    #[error("unexpected success {success} ({message:?})")]
+    Unexpected {
+        success: u32,
+        message: Option<String>,
+    },
 }
 type Result<T> = std::result::Result<T, VanityUrlError>;
 
+/// What a vanity url resolved to, see [`Client::resolve_vanity_url`]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VanityResolution {
+    Found(SteamId),
+    /// Nobody uses the vanity url
+    NotFound,
+    /// Steam refused to answer, either with `429 Too Many Requests` after all retries
+    /// or with [`SUCCESS_RATE_LIMITED`]
+    RateLimited,
+}
+
+impl VanityResolution {
+    pub const fn steam_id(self) -> Option<SteamId> {
+        match self {
+            VanityResolution::Found(id) => Some(id),
+            VanityResolution::NotFound | VanityResolution::RateLimited => None,
+        }
+    }
+}
+
+/// The raw answer of Steam, see [`Client::get_vanity_url`]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VanityUrl {
     #[serde(rename = "steamid")]
     pub steam_id: Option<SteamIdStr>,
+    /// `EResult` of the request, e.g. [`SUCCESS_FOUND`] or [`SUCCESS_NO_MATCH`]
+    pub success: u32,
+    /// Only sent on failure, e.g. `No match`
+    #[serde(default)]
+    pub message: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
-struct Response {
-    response: VanityUrl,
+impl VanityUrl {
+    pub fn resolution(&self) -> Result<VanityResolution> {
+        match (self.success, self.steam_id) {
+            (SUCCESS_FOUND, Some(id)) => Ok(VanityResolution::Found(id.steam_id())),
+            (SUCCESS_NO_MATCH, _) => Ok(VanityResolution::NotFound),
+            (SUCCESS_RATE_LIMITED, _) => Ok(VanityResolution::RateLimited),
+            (success, _) => Err(VanityUrlError::Unexpected {
+                success,
+                message: self.message.clone(),
+            }),
+        }
+    }
 }
 
+type Response = Envelope<VanityUrl>;
+
 impl From<Response> for VanityUrl {
     fn from(value: Response) -> Self {
-        value.response
+        value.into_inner()
     }
 }
 
 impl Client {
     /// Resolve a Vanity-URL using [`this endpoint`](https://partner.steamgames.com/doc/webapi/ISteamUser#ResolveVanityURL).
-    pub async fn resolve_vanity_url(&self, vanity_url: &str) -> Result<SteamId> {
+    ///
+    /// Unknown vanity urls and rate limits aren't errors, see [`VanityResolution`].
+    pub async fn resolve_vanity_url(&self, vanity_url: &str) -> Result<VanityResolution> {
+        match self.get_vanity_url(vanity_url).await {
+            Ok(url) => url.resolution(),
+            Err(VanityUrlError::Reqwest(err))
+                if err.status() == Some(StatusCode::TOO_MANY_REQUESTS) =>
+            {
+                Ok(VanityResolution::RateLimited)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Like [`Client::resolve_vanity_url`], but returns the answer as is to look at
+    /// the `success` code
+    pub async fn get_vanity_url(&self, vanity_url: &str) -> Result<VanityUrl> {
         let query = [("key", self.api_key()), ("vanityurl", vanity_url)];
-        let json = self.get_json::<Response>(VANITY_API, &query).await?;
-        Ok(json
-            .response
-            .steam_id
-            .ok_or_else(|| VanityUrlError::NotFound(vanity_url.to_string()))?
-            .steam_id())
+        let json = self
+            .get_json::<Response>(Endpoint::ResolveVanityUrl, &query)
+            .await?;
+        Ok(json.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Response;
+    use super::{Response, VanityResolution, VanityUrlError};
     use crate::model::api::vanity_url::VanityUrl;
     use crate::model::SteamIdStr;
+    use crate::SteamId;
 
     #[test]
     fn parses() {
         let json: Response = load_test_json!("vanity_url.json");
         let url: VanityUrl = json.into();
-        assert_eq!(url.steam_id, Some(SteamIdStr(76561197960287930)))
+        assert_eq!(url.steam_id, Some(SteamIdStr(76561197960287930)));
+        assert_eq!(
+            url.resolution().unwrap(),
+            VanityResolution::Found(SteamId(76561197960287930))
+        );
+    }
+
+    #[test]
+    fn resolves_success_codes() {
+        let resolve = |json: &str| {
+            serde_json::from_str::<Response>(json)
+                .unwrap()
+                .into_inner()
+                .resolution()
+        };
+
+        let no_match = r#"{"response":{"success":42,"message":"No match"}}"#;
+        assert_eq!(resolve(no_match).unwrap(), VanityResolution::NotFound);
+        let limited = r#"{"response":{"success":84}}"#;
+        assert_eq!(resolve(limited).unwrap(), VanityResolution::RateLimited);
+
+        let failed = r#"{"response":{"success":2,"message":"Fail"}}"#;
+        assert!(matches!(
+            resolve(failed),
+            Err(VanityUrlError::Unexpected { success: 2, message: Some(m) }) if m == "Fail"
+        ));
+        // found, but without an id
+        let empty = r#"{"response":{"success":1}}"#;
+        assert!(matches!(
+            resolve(empty),
+            Err(VanityUrlError::Unexpected { success: 1, .. })
+        ));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let json: Response = load_test_json!("vanity_url.json");
+        let url: VanityUrl = json.into();
+        let decoded = assert_bincode_round_trip!(url, VanityUrl);
+        assert_eq!(decoded.steam_id, url.steam_id);
+        assert_eq!(decoded.success, url.success);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn rate_limits_are_not_errors() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        steam
+            .mount_status(Endpoint::ResolveVanityUrl, 429, u64::MAX)
+            .await;
+        let client = steam.client().await;
+
+        let resolution = client.resolve_vanity_url("gabelogannewell").await.unwrap();
+        assert_eq!(resolution, VanityResolution::RateLimited);
     }
 }
@@ -0,0 +1,104 @@
+use thiserror::Error;
+
+use crate::client::Client;
+use crate::constants::{
+    PROFILE_URL_ID64_PREFIX, PROFILE_URL_VANITY_PREFIX, USER_SEARCH_RESULTS_PER_PAGE,
+};
+use crate::model::api::{UserSearchError, UserSearchPage};
+
+/// A search with far more than a page of results for as long as Steam exists
+const CANARY_SEARCH: &str = "valve";
+
+#[derive(Debug, Error)]
+pub enum VerifyParsersError {
+    /// The search itself failed, which includes rows the parser couldn't parse
+    #[error("canary search failed ({0})")]
+    Search(#[from] UserSearchError),
+
+    /// The search parsed, but the result doesn't look like a search result
+    #[error("canary search parsed into nonsense ({0})")]
+    Invariant(&'static str),
+}
+type Result<T> = std::result::Result<T, VerifyParsersError>;
+
+/// The structure every first page of [`CANARY_SEARCH`] has
+fn check_search_page(page: &UserSearchPage) -> Result<()> {
+    let check = |ok: bool, invariant: &'static str| {
+        ok.then_some(())
+            .ok_or(VerifyParsersError::Invariant(invariant))
+    };
+
+    check(
+        page.total_result_count > USER_SEARCH_RESULTS_PER_PAGE,
+        "less than a page of results",
+    )?;
+    check(
+        page.results.len() == USER_SEARCH_RESULTS_PER_PAGE,
+        "first page isn't full",
+    )?;
+    for entry in &page.results {
+        check(!entry.persona_name.is_empty(), "empty persona name")?;
+        check(
+            entry.steam_id().is_some() || entry.profile_url.starts_with(PROFILE_URL_VANITY_PREFIX),
+            "profile url is neither a steam id nor a vanity url",
+        )?;
+        check(
+            !entry.profile_url.starts_with(PROFILE_URL_ID64_PREFIX) || entry.steam_id().is_some(),
+            "invalid steam id in profile url",
+        )?;
+        check(
+            entry.avatar_full.starts_with("https://"),
+            "avatar isn't a url",
+        )?;
+    }
+    Ok(())
+}
+
+impl Client {
+    /// Search a name with a known amount of results and check the structure of the parsed page,
+    /// to notice a changed layout of the community pages at startup instead of in production
+    ///
+    /// Costs a single request of [`Endpoint::UserSearch`](crate::Endpoint::UserSearch).
+    pub async fn verify_parsers(&self) -> Result<()> {
+        let page = self.get_search_page(CANARY_SEARCH, 1).await?;
+        check_search_page(&page)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::VerifyParsersError;
+    use crate::testing::MockSteam;
+    use crate::Endpoint;
+
+    #[tokio::test]
+    async fn verifies_parsers() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        client.verify_parsers().await.unwrap();
+
+        // a layout change that drops all but the first row
+        let mut json: serde_json::Value =
+            serde_json::from_str(crate::testing::fixtures::USER_SEARCH).unwrap();
+        let html = json["html"].as_str().unwrap().to_owned();
+        let first_row = html.find("<div class=\"search_row").unwrap();
+        let second_row = first_row
+            + 1
+            + html[first_row + 1..]
+                .find("<div class=\"search_row")
+                .unwrap();
+        json["html"] = html[..second_row].into();
+        steam.mount_json_times(Endpoint::UserSearch, &json, 1).await;
+        assert!(matches!(
+            client.verify_parsers().await,
+            Err(VerifyParsersError::Invariant("first page isn't full"))
+        ));
+
+        json["html"] = r#"<div class="search_row"></div>"#.into();
+        steam.mount_json_times(Endpoint::UserSearch, &json, 1).await;
+        assert!(matches!(
+            client.verify_parsers().await,
+            Err(VerifyParsersError::Search(_))
+        ));
+    }
+}
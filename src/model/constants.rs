@@ -1,30 +1,7 @@
-/// [`/ISteamUser/ResolveVanityURL/v1/`](https://partner.steamgames.com/doc/webapi/ISteamUser#:~:text=/ISteamUser/ResolveVanityURL/v1/)
-pub const VANITY_API: &str = "https://api.steampowered.com/ISteamUser/ResolveVanityURL/v1/";
-pub const VANITY_CONCURRENT_REQUESTS: usize = 100;
+//! Constants that aren't tied to a single [`Endpoint`](crate::Endpoint).
+//!
+//! Urls, id limits and suggested concurrency of the endpoints live on [`Endpoint`](crate::Endpoint).
 
-/// [`/ISteamUser/GetPlayerSummaries/v2/`](https://partner.steamgames.com/doc/webapi/ISteamUser#:~:text=/ISteamUser/GetPlayerSummaries/v2/)
-pub const PLAYER_SUMMARIES_API: &str =
-    "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/";
-pub const PLAYER_SUMMARIES_CONCURRENT_REQUESTS: usize = 100;
-pub const PLAYER_SUMMARIES_IDS_PER_REQUEST: usize = 100;
-
-/// [`/ISteamUser/GetFriendList/v1/`](https://partner.steamgames.com/doc/webapi/ISteamUser#:~:text=/ISteamUser/GetFriendList/v1/)
-pub const PLAYER_FRIENDS_API: &str = "https://api.steampowered.com/ISteamUser/GetFriendList/v1/";
-pub const PLAYER_FRIENDS_CONCURRENT_REQUESTS: usize = 100;
-
-/// [`/ISteamUser/GetPlayerBans/v1/`](https://partner.steamgames.com/doc/webapi/ISteamUser#:~:text=/ISteamUser/GetPlayerBans/v1/)
-pub const PLAYER_BANS_API: &str = "https://api.steampowered.com/ISteamUser/GetPlayerBans/v1/";
-pub const PLAYER_BANS_CONCURRENT_REQUESTS: usize = 100;
-pub const PLAYER_BANS_IDS_PER_REQUEST: usize = 100;
-
-/// [`/IPlayerService/GetSteamLevel/v1/`](https://partner.steamgames.com/doc/webapi/IPlayerService#GetOwnedGames:~:text=/IPlayerService/GetSteamLevel/v1/)
-pub const PLAYER_STEAM_LEVEL_API: &str =
-    "https://api.steampowered.com/IPlayerService/GetSteamLevel/v1/";
-pub const PLAYER_STEAM_LEVEL_CONCURRENT_REQUESTS: usize = 100;
-
-/// Not documented
-pub const USER_SEARCH_API: &str = "https://steamcommunity.com/search/SearchCommunityAjax/";
-pub const USER_SEARCH_CONCURRENT_REQUESTS: usize = 100;
 /// Each result will contain `20` results
 pub const USER_SEARCH_RESULTS_PER_PAGE: usize = 20;
 /// This endpoint will only return unique results for pages in the range `[1, 500]`
@@ -34,5 +11,16 @@ pub const USER_SEARCH_MAX_RESULTS: usize = USER_SEARCH_MAX_PAGES * USER_SEARCH_R
 
 pub const PROFILE_URL_ID64_PREFIX: &str = "https://steamcommunity.com/profiles/";
 pub const PROFILE_URL_VANITY_PREFIX: &str = "https://steamcommunity.com/id/";
+pub const APP_HUB_URL_PREFIX: &str = "https://steamcommunity.com/app/";
 
 pub const BASE_URL: &str = "https://steamcommunity.com";
+/// Avatars are served from their own host, see [`Client::download_avatar`](crate::Client::download_avatar)
+pub const AVATAR_BASE_URL: &str = "https://avatars.steamstatic.com";
+/// The question mark avatar every account has until a custom one is uploaded
+pub const DEFAULT_AVATAR_HASH: &str = "fef49e7fa7e1997310d705b2a6158ff8dc1cdfeb";
+/// Hashes of avatars Steam hands out itself, an account with one of these never set an avatar
+pub const DEFAULT_AVATAR_HASHES: [&str; 1] = [DEFAULT_AVATAR_HASH];
+/// The images and movies of profile items are relative to this url,
+/// see [`ProfileItem`](crate::api::ProfileItem)
+pub const COMMUNITY_IMAGES_URL: &str =
+    "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/";
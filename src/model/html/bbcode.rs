@@ -0,0 +1,355 @@
+//! Render the BBCode Steam uses in profile summaries, group descriptions and announcements.
+//!
+//! ```
+//! use steam_api_concurrent::html::bbcode;
+//!
+//! let text = "[b]Rules[/b] [list][*]no cheating[*]be nice[/list]";
+//! assert_eq!(bbcode::to_plain_text(text), "Rules \n- no cheating\n- be nice");
+//! assert_eq!(
+//!     bbcode::to_html("[url=https://example.com]Tom &amp; Jerry[/url]"),
+//!     "<a href=\"https://example.com\" rel=\"nofollow\">Tom &amp; Jerry</a>"
+//! );
+//! ```
+//!
+//! Unknown tags are kept as text, unclosed tags are closed at the end.
+
+use std::borrow::Cow;
+
+/// Tags that are recognized, everything else is text
+const TAGS: [&str; 20] = [
+    "b",
+    "i",
+    "u",
+    "s",
+    "strike",
+    "h1",
+    "h2",
+    "h3",
+    "url",
+    "img",
+    "quote",
+    "code",
+    "noparse",
+    "spoiler",
+    "list",
+    "olist",
+    "*",
+    "hr",
+    "previewyoutube",
+    "p",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open { name: String, arg: Option<&'a str> },
+    Close { name: String },
+}
+
+/// Parse the tag at the start of `input`, which starts with `[`, and its length
+fn parse_tag(input: &str) -> Option<(Token<'_>, usize)> {
+    let end = input.find(']')?;
+    let inner = &input[1..end];
+    let (closing, inner) = inner
+        .strip_prefix('/')
+        .map_or((false, inner), |inner| (true, inner));
+    let (name, arg) = match inner.split_once('=') {
+        Some((name, arg)) if !closing => (name, Some(arg.trim_matches(['"', '\'']))),
+        Some(_) => return None,
+        None => (inner, None),
+    };
+    let name = name.to_ascii_lowercase();
+    if !TAGS.contains(&name.as_str()) {
+        return None;
+    }
+    let token = if closing {
+        Token::Close { name }
+    } else {
+        Token::Open { name, arg }
+    };
+    Some((token, end + 1))
+}
+
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('[') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+            rest = &rest[start..];
+        }
+        match parse_tag(rest) {
+            Some((Token::Open { name, .. }, len)) if name == "noparse" => {
+                // everything up to the closing tag is text
+                let body = &rest[len..];
+                let end = body.to_ascii_lowercase().find("[/noparse]");
+                let (text, skip) = end.map_or((body, body.len()), |end| (&body[..end], end + 10));
+                tokens.push(Token::Text(text));
+                rest = &body[skip..];
+            }
+            Some((token, len)) => {
+                tokens.push(token);
+                rest = &rest[len..];
+            }
+            None => {
+                tokens.push(Token::Text("["));
+                rest = &rest[1..];
+            }
+        }
+    }
+    tokens
+}
+
+/// Decode named and numeric html entities like `&amp;` or `&#39;`, unknown ones are kept
+pub fn decode_entities(input: &str) -> Cow<'_, str> {
+    if !input.contains('&') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    let code = entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                        .map_or_else(
+                            || entity.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                            |hex| u32::from_str_radix(hex, 16).ok(),
+                        );
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Escape text so it can be put into html
+fn escape_html(input: &str, out: &mut String) {
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Only links to the web are rendered, so `javascript:` urls can't sneak in
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Render `input` as plain text.
+///
+/// Formatting is dropped, links are written as `text (url)`, list items start a new line
+/// and images are left out. Html entities and `<br>` tags are decoded too.
+pub fn to_plain_text(input: &str) -> String {
+    let input = input
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+
+    let mut out = String::with_capacity(input.len());
+    // url and the position its text starts at
+    let mut links: Vec<(Option<&str>, usize)> = Vec::new();
+    // the next number of each open list, `None` for bullet lists
+    let mut lists: Vec<Option<usize>> = Vec::new();
+    let mut in_img = false;
+
+    for token in tokenize(&input) {
+        match token {
+            Token::Text(text) if !in_img => out.push_str(&decode_entities(text)),
+            Token::Text(_) => {}
+            Token::Open { name, arg } => match name.as_str() {
+                "img" | "previewyoutube" => in_img = true,
+                "url" => links.push((arg, out.len())),
+                "list" => lists.push(None),
+                "olist" => lists.push(Some(1)),
+                "*" => {
+                    out.push('\n');
+                    match lists.last_mut() {
+                        Some(Some(n)) => {
+                            out.push_str(&format!("{}. ", n));
+                            *n += 1;
+                        }
+                        _ => out.push_str("- "),
+                    }
+                }
+                "hr" => out.push('\n'),
+                _ => {}
+            },
+            Token::Close { name } => match name.as_str() {
+                "img" | "previewyoutube" => in_img = false,
+                "url" => {
+                    if let Some((Some(url), start)) = links.pop() {
+                        if out[start..] != *url {
+                            out.push_str(&format!(" ({})", url));
+                        }
+                    }
+                }
+                "list" | "olist" => {
+                    lists.pop();
+                }
+                "h1" | "h2" | "h3" | "p" => out.push('\n'),
+                _ => {}
+            },
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Render `input` as html.
+///
+/// Text is escaped, tags are replaced with their html counterparts
+/// and tags left open are closed at the end.
+pub fn to_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() * 2);
+    // names of the open tags and the html to close them with
+    let mut open: Vec<(String, &'static str)> = Vec::new();
+    let mut in_img = false;
+
+    for token in tokenize(input) {
+        match token {
+            Token::Text(text) if in_img => {
+                let src = decode_entities(text);
+                if is_safe_url(&src) {
+                    out.push_str("<img src=\"");
+                    escape_html(src.trim(), &mut out);
+                    out.push_str("\">");
+                }
+            }
+            Token::Text(text) => escape_html(&decode_entities(text), &mut out),
+            Token::Open { name, arg } => {
+                let (html, close) = match name.as_str() {
+                    "b" => ("<strong>", "</strong>"),
+                    "i" => ("<em>", "</em>"),
+                    "u" => ("<u>", "</u>"),
+                    "s" | "strike" => ("<s>", "</s>"),
+                    "h1" => ("<h1>", "</h1>"),
+                    "h2" => ("<h2>", "</h2>"),
+                    "h3" => ("<h3>", "</h3>"),
+                    "p" => ("<p>", "</p>"),
+                    "quote" => ("<blockquote>", "</blockquote>"),
+                    "code" => ("<pre><code>", "</code></pre>"),
+                    "spoiler" => ("<span class=\"bb_spoiler\">", "</span>"),
+                    "list" => ("<ul>", "</ul>"),
+                    "olist" => ("<ol>", "</ol>"),
+                    "*" => ("<li>", ""),
+                    "hr" => ("<hr>", ""),
+                    "img" | "previewyoutube" => {
+                        in_img = true;
+                        ("", "")
+                    }
+                    "url" => arg
+                        .map(decode_entities)
+                        .filter(|url| is_safe_url(url))
+                        .map_or(("", ""), |url| {
+                            out.push_str("<a href=\"");
+                            escape_html(url.trim(), &mut out);
+                            out.push_str("\" rel=\"nofollow\">");
+                            ("", "</a>")
+                        }),
+                    _ => ("", ""),
+                };
+                out.push_str(html);
+                if !close.is_empty() {
+                    open.push((name, close));
+                }
+            }
+            Token::Close { name } => {
+                if matches!(name.as_str(), "img" | "previewyoutube") {
+                    in_img = false;
+                }
+                // close everything opened after the matching tag, ignore stray closing tags
+                if let Some(pos) = open.iter().rposition(|(open, _)| *open == name) {
+                    for (_, close) in open.drain(pos..).rev() {
+                        out.push_str(close);
+                    }
+                }
+            }
+        }
+    }
+    for (_, close) in open.into_iter().rev() {
+        out.push_str(close);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_entities, to_html, to_plain_text};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(decode_entities("a &amp; b"), "a & b");
+        assert_eq!(decode_entities("&lt;3 &#39;x&#x27;"), "<3 'x'");
+        assert_eq!(decode_entities("&unknown; & &"), "&unknown; & &");
+        assert_eq!(decode_entities("plain"), "plain");
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let text = "[h1]Welcome[/h1][i]Have fun[/i]<br>[url=https://example.com]site[/url] \
+                    [url]https://example.com[/url][img]https://example.com/a.png[/img]";
+        assert_eq!(
+            to_plain_text(text),
+            "Welcome\nHave fun\nsite (https://example.com) https://example.com"
+        );
+        assert_eq!(
+            to_plain_text("[olist][*]one[*]two[/olist]"),
+            "1. one\n2. two"
+        );
+        assert_eq!(to_plain_text("[noparse][b]x[/b][/noparse]"), "[b]x[/b]");
+        assert_eq!(to_plain_text("[x] [b]bold"), "[x] bold");
+    }
+
+    #[test]
+    fn renders_html() {
+        assert_eq!(
+            to_html("[b]bold [i]both[/b] <script>"),
+            "<strong>bold <em>both</em></strong> &lt;script&gt;"
+        );
+        assert_eq!(
+            to_html("[list][*]a[*]b[/list][quote=someone]hi"),
+            "<ul><li>a<li>b</ul><blockquote>hi</blockquote>"
+        );
+        assert_eq!(
+            to_html("[url=javascript:alert(1)]x[/url][img]https://a.b/c.png[/img]"),
+            "x<img src=\"https://a.b/c.png\">"
+        );
+    }
+}
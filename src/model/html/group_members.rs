@@ -0,0 +1,97 @@
+//! Parse the member list of a group for its owner, officers and moderators
+
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::html::{RowSnippet, SelectorError};
+use crate::model::SteamId;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// A member without a parsable `data-miniprofile` account id
+    #[error("no account id ({0})")]
+    NoAccountId(RowSnippet),
+
+    #[error(transparent)]
+    InvalidSelector(#[from] SelectorError),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+/// The rank of a member that manages the group
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GroupRole {
+    Owner,
+    Officer,
+    Moderator,
+}
+
+impl GroupRole {
+    /// The rank shown by the icon with the url `src`, the tooltip is translated but
+    /// the file name of the icon isn't
+    fn from_icon(src: &str) -> Option<Self> {
+        let file = src.rsplit('/').next()?;
+        if file.contains("Owner") {
+            Some(GroupRole::Owner)
+        } else if file.contains("Officer") {
+            Some(GroupRole::Officer)
+        } else if file.contains("Moderator") {
+            Some(GroupRole::Moderator)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupOfficer {
+    pub steam_id: SteamId,
+    pub role: GroupRole,
+    pub persona_name: String,
+}
+
+selectors! {
+    pub struct Parser -> Error {
+        member: "div.member_block",
+        rank_icon: "div.rank_icon>img",
+        name: "a.linkFriend",
+    }
+}
+
+impl Parser {
+    /// [`None`] for members without a rank
+    fn parse_member(&self, member: ElementRef) -> Result<Option<GroupOfficer>> {
+        let role = member
+            .select(&self.rank_icon)
+            .next()
+            .and_then(|icon| icon.value().attr("src"))
+            .and_then(GroupRole::from_icon);
+        let Some(role) = role else {
+            return Ok(None);
+        };
+        let account_id = member
+            .value()
+            .attr("data-miniprofile")
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| Error::NoAccountId(RowSnippet::new(member)))?;
+        let persona_name = member
+            .select(&self.name)
+            .next()
+            .map(|name| name.text().collect())
+            .unwrap_or_default();
+
+        Ok(Some(GroupOfficer {
+            steam_id: SteamId::from_account_id(account_id),
+            role,
+            persona_name,
+        }))
+    }
+
+    /// The members with a rank, in the order of the page, which lists them first
+    pub fn parse(&self, html: &str) -> Result<Vec<GroupOfficer>> {
+        let html = Html::parse_fragment(html);
+        html.select(&self.member)
+            .filter_map(|member| self.parse_member(member).transpose())
+            .collect()
+    }
+}
@@ -0,0 +1,134 @@
+//! Parse the summary at the top of the page of a group, `/groups/{name}/`
+
+use scraper::{ElementRef, Html, Node};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::html::SelectorError;
+use crate::model::SteamId;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Steam answered with an error page instead, e.g. for groups that don't exist
+    #[error("steam returned an error ({0})")]
+    Steam(String),
+
+    /// An element the summary can't do without isn't on the page
+    #[error("missing {0}")]
+    Missing(&'static str),
+
+    /// A member count without digits
+    #[error("invalid {field} '{value}'")]
+    InvalidCount { field: &'static str, value: String },
+
+    #[error(transparent)]
+    InvalidSelector(#[from] SelectorError),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupSummary {
+    pub group_id: SteamId,
+    pub name: String,
+    /// The short name next to the name, [`None`] if the group has none
+    pub abbreviation: Option<String>,
+    /// [`None`] if the group has none
+    pub headline: Option<String>,
+    pub member_count: u32,
+    pub members_in_game: u32,
+    pub members_online: u32,
+    /// [`None`] for groups without a group chat
+    pub members_in_chat: Option<u32>,
+}
+
+selectors! {
+    pub struct Parser -> Error {
+        error: "div.error_ctn h3",
+        name: "div.grouppage_header_name",
+        abbreviation: "span.grouppage_header_abbrev",
+        headline: "div.group_summary>h1",
+        members: "div.membercount.members .count",
+        in_game: "div.membercount.ingame .count",
+        online: "div.membercount.online .count",
+        in_chat: "div.membercount.chat .count",
+        group_id: "input[name=\"abuseID\"]",
+    }
+}
+
+/// The text of `element` with its whitespace trimmed, [`None`] if that's empty
+fn trimmed_text(element: ElementRef) -> Option<String> {
+    let text = element.text().collect::<String>();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// The digits of a count, the thousands separator depends on the language of the page
+fn parse_count(field: &'static str, text: &str) -> Result<u32> {
+    let digits = text
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    digits.parse().map_err(|_| Error::InvalidCount {
+        field,
+        value: text.trim().to_string(),
+    })
+}
+
+impl Parser {
+    fn count(&self, html: &Html, selector: &scraper::Selector, field: &'static str) -> Result<u32> {
+        let count = html.select(selector).next().ok_or(Error::Missing(field))?;
+        parse_count(field, &count.text().collect::<String>())
+    }
+
+    pub fn parse(&self, html: &str) -> Result<GroupSummary> {
+        let html = Html::parse_document(html);
+        if let Some(error) = html.select(&self.error).next().and_then(trimmed_text) {
+            return Err(Error::Steam(error));
+        }
+
+        // the abbreviation is a child of the name, only the text directly inside is the name
+        let header = html
+            .select(&self.name)
+            .next()
+            .ok_or(Error::Missing("name"))?;
+        let name = header
+            .children()
+            .filter_map(|child| match child.value() {
+                Node::Text(text) => Some(&**text),
+                _ => None,
+            })
+            .collect::<String>()
+            .trim()
+            .to_string();
+        let abbreviation = header
+            .select(&self.abbreviation)
+            .next()
+            .and_then(trimmed_text);
+
+        let group_id = html
+            .select(&self.group_id)
+            .next()
+            .and_then(|input| input.value().attr("value"))
+            .and_then(|id| id.parse().ok())
+            .ok_or(Error::Missing("group id"))?;
+
+        let members_in_chat = match html.select(&self.in_chat).next() {
+            Some(count) => Some(parse_count(
+                "members in chat",
+                &count.text().collect::<String>(),
+            )?),
+            None => None,
+        };
+
+        Ok(GroupSummary {
+            group_id: SteamId(group_id),
+            name,
+            abbreviation,
+            headline: html.select(&self.headline).next().and_then(trimmed_text),
+            member_count: self.count(&html, &self.members, "member count")?,
+            members_in_game: self.count(&html, &self.in_game, "members in game")?,
+            members_online: self.count(&html, &self.online, "members online")?,
+            members_in_chat,
+        })
+    }
+}
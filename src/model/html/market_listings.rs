@@ -0,0 +1,184 @@
+//! Parse the price history and the listings embedded in the scripts of a market listings page
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid json in script ({0})")]
+    Json(#[from] serde_json::Error),
+
+    /// A point of the price history with a date that isn't `Nov 19 2012 01: +0`
+    #[error("invalid date '{0}'")]
+    InvalidDate(String),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+/// The median price of the sales in one hour, or one day for older points
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PricePoint {
+    pub at: DateTime<Utc>,
+    /// In the currency of the session, USD for sessions that aren't logged in
+    pub median_price: f64,
+    pub volume: u64,
+}
+
+/// An item listed for sale
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MarketListing {
+    pub listing_id: String,
+    /// What the seller gets in the smallest unit of [`MarketListing::currency_id`]
+    pub price: u64,
+    /// What the buyer pays on top of the price
+    pub fee: u64,
+    /// `2000` plus the number of the currency, e.g. `2001` for USD
+    pub currency_id: u32,
+    pub app_id: u32,
+    pub context_id: String,
+    pub asset_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MarketListingsPage {
+    /// Oldest first, empty for items that were never sold
+    pub price_history: Vec<PricePoint>,
+    /// Cheapest first, like on the page
+    pub listings: Vec<MarketListing>,
+}
+
+#[derive(Deserialize)]
+struct RawAsset {
+    appid: u32,
+    contextid: String,
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct RawListing {
+    listingid: String,
+    #[serde(default)]
+    price: u64,
+    #[serde(default)]
+    fee: u64,
+    currencyid: u32,
+    asset: RawAsset,
+}
+
+impl From<RawListing> for MarketListing {
+    fn from(value: RawListing) -> Self {
+        MarketListing {
+            listing_id: value.listingid,
+            price: value.price,
+            fee: value.fee,
+            currency_id: value.currencyid,
+            app_id: value.asset.appid,
+            context_id: value.asset.contextid,
+            asset_id: value.asset.id,
+        }
+    }
+}
+
+/// Parse the json a script assigns to `var {name} =`, [`None`] if there's no such variable
+fn script_variable<T: DeserializeOwned>(html: &str, name: &str) -> Result<Option<T>> {
+    let Some(start) = html
+        .find(&format!("var {name}="))
+        .map(|start| start + name.len() + 5)
+        .or_else(|| {
+            html.find(&format!("var {name} = "))
+                .map(|start| start + name.len() + 7)
+        })
+    else {
+        return Ok(None);
+    };
+    // only the value is parsed, not the rest of the script after it
+    let mut values = serde_json::Deserializer::from_str(&html[start..]).into_iter::<T>();
+    values.next().transpose().map_err(Error::from)
+}
+
+/// `Nov 19 2012 01: +0`, always in UTC
+fn parse_date(date: &str) -> Result<DateTime<Utc>> {
+    let hour = date
+        .strip_suffix(": +0")
+        .ok_or_else(|| Error::InvalidDate(date.to_owned()))?;
+    NaiveDateTime::parse_from_str(&format!("{hour}:00"), "%b %d %Y %H:%M")
+        .map(|at| at.and_utc())
+        .map_err(|_| Error::InvalidDate(date.to_owned()))
+}
+
+fn parse_price_history(html: &str) -> Result<Vec<PricePoint>> {
+    let points: Vec<(String, f64, String)> = script_variable(html, "line1")?.unwrap_or_default();
+    points
+        .into_iter()
+        .map(|(date, median_price, volume)| {
+            Ok(PricePoint {
+                at: parse_date(&date)?,
+                median_price,
+                volume: volume.parse().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+fn parse_listings(html: &str) -> Result<Vec<MarketListing>> {
+    // an empty array instead of an object if there are no listings
+    let listings: serde_json::Value = script_variable(html, "g_rgListingInfo")?.unwrap_or_default();
+    let serde_json::Value::Object(_) = listings else {
+        return Ok(Vec::new());
+    };
+    let listings: HashMap<String, RawListing> = serde_json::from_value(listings)?;
+    let mut listings = listings
+        .into_values()
+        .map(MarketListing::from)
+        .collect::<Vec<_>>();
+    listings.sort_by(|a, b| {
+        (a.price + a.fee)
+            .cmp(&(b.price + b.fee))
+            .then_with(|| a.listing_id.cmp(&b.listing_id))
+    });
+    Ok(listings)
+}
+
+pub fn parse(html: &str) -> Result<MarketListingsPage> {
+    Ok(MarketListingsPage {
+        price_history: parse_price_history(html)?,
+        listings: parse_listings(html)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{parse, parse_date};
+
+    #[test]
+    fn parses() {
+        let html = include_str!("../../../test_resources/market_listings.html");
+        let page = parse(html).unwrap();
+
+        assert_eq!(page.price_history.len(), 3);
+        assert_eq!(
+            page.price_history[0].at,
+            Utc.with_ymd_and_hms(2012, 11, 19, 1, 0, 0).unwrap()
+        );
+        assert_eq!(page.price_history[2].volume, 4521);
+
+        assert_eq!(page.listings.len(), 2);
+        assert_eq!(page.listings[0].listing_id, "4882012771035211423");
+        assert_eq!(page.listings[0].price + page.listings[0].fee, 227);
+        assert_eq!(page.listings[1].currency_id, 2003);
+        assert_eq!(page.listings[1].asset_id, "14530917397");
+    }
+
+    #[test]
+    fn parses_empty_pages() {
+        let page = parse("<script>var g_rgListingInfo = [];</script>").unwrap();
+        assert!(page.price_history.is_empty());
+        assert!(page.listings.is_empty());
+        assert!(parse_date("Nov 19 2012").is_err());
+    }
+}
@@ -1,2 +1,171 @@
+pub mod bbcode;
+
+#[cfg(feature = "user_search")]
+pub use row::{RowSnippet, PARSER_VERSION};
+#[cfg(feature = "user_search")]
+pub use selectors::SelectorError;
+
+/// Define a parser struct holding compiled [`scraper::Selector`]s and
+/// `Parser::new` returning it
+///
+/// The selectors are compiled on the first call and shared by every later one,
+/// a selector that doesn't compile is a [`SelectorError`], converted into the
+/// error type after the `->`, on every call.
+///
+/// ```ignore
+/// selectors! {
+///     pub struct Parser -> Error {
+///         row: "div.search_row",
+///     }
+/// }
+/// ```
+#[cfg(feature = "user_search")]
+macro_rules! selectors {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident -> $error:ty {
+            $($field:ident: $selector:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field: ::scraper::Selector,)*
+        }
+
+        impl $name {
+            /// The parser with its selectors compiled once for the whole process
+            pub fn new() -> ::std::result::Result<&'static Self, $error> {
+                static PARSER: ::std::sync::OnceLock<
+                    ::std::result::Result<$name, $crate::model::html::SelectorError>,
+                > = ::std::sync::OnceLock::new();
+                PARSER
+                    .get_or_init(|| {
+                        Ok(Self {
+                            $($field: $crate::model::html::selectors::compile($selector)?,)*
+                        })
+                    })
+                    .as_ref()
+                    .map_err(|err| <$error>::from(err.clone()))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "user_search")]
+mod selectors {
+    use scraper::Selector;
+    use thiserror::Error;
+
+    /// A selector of an html parser didn't compile
+    #[derive(Debug, Clone, PartialEq, Eq, Error)]
+    #[error("couldn't construct the html parser, invalid selector '{selector}' ({reason})")]
+    pub struct SelectorError {
+        pub selector: &'static str,
+        pub reason: String,
+    }
+
+    pub(crate) fn compile(selector: &'static str) -> Result<Selector, SelectorError> {
+        Selector::parse(selector).map_err(|err| SelectorError {
+            selector,
+            reason: err.to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "user_search")]
+mod row {
+    use std::fmt;
+
+    use scraper::ElementRef;
+
+    /// The version of the html parsers, reported with every [`RowSnippet`]
+    pub const PARSER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+    /// How much of the html of a row is kept, in bytes
+    const SNIPPET_LEN: usize = 512;
+
+    /// The start of the html of a row a parser failed on, to see what changed
+    /// when Steam changes the layout of a page
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RowSnippet {
+        pub html: String,
+        pub parser_version: &'static str,
+    }
+
+    impl RowSnippet {
+        pub(crate) fn new(row: ElementRef) -> Self {
+            let mut html = row.html();
+            if html.len() > SNIPPET_LEN {
+                let mut end = SNIPPET_LEN;
+                while !html.is_char_boundary(end) {
+                    end -= 1;
+                }
+                html.truncate(end);
+                html.push_str("...");
+            }
+            Self {
+                html,
+                parser_version: PARSER_VERSION,
+            }
+        }
+    }
+
+    impl fmt::Display for RowSnippet {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "parser {} in row `{}`", self.parser_version, self.html)
+        }
+    }
+}
+
+#[cfg(feature = "user_search")]
+pub mod group_members;
+
+#[cfg(feature = "user_search")]
+pub mod group_page;
+
 #[cfg(feature = "user_search")]
 pub mod user_search;
+
+pub mod market_listings;
+
+#[cfg(feature = "user_search")]
+pub mod profile;
+
+#[cfg(all(test, feature = "user_search"))]
+mod tests {
+    selectors! {
+        #[allow(dead_code)]
+        struct Valid -> super::SelectorError {
+            row: "div.row",
+        }
+    }
+
+    selectors! {
+        #[allow(dead_code)]
+        struct Invalid -> super::SelectorError {
+            row: "div.row",
+            broken: "div[",
+        }
+    }
+
+    #[test]
+    fn compiles_once() {
+        let parser = Valid::new().unwrap();
+        assert!(std::ptr::eq(parser, Valid::new().unwrap()));
+
+        let err = Invalid::new().map(|_| ()).unwrap_err();
+        assert_eq!(err.selector, "div[");
+        assert_eq!(Invalid::new().map(|_| ()).unwrap_err(), err);
+    }
+
+    #[test]
+    fn truncates_snippets() {
+        let html = scraper::Html::parse_fragment(&format!("<p>{}</p>", "ä".repeat(300)));
+        let snippet = super::RowSnippet::new(html.root_element());
+        assert!(snippet.html.len() <= 512 + 3);
+        assert!(snippet.html.ends_with("ä..."));
+        assert!(snippet
+            .to_string()
+            .starts_with(&format!("parser {} in row `", super::PARSER_VERSION)));
+    }
+}
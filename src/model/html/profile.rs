@@ -0,0 +1,214 @@
+//! Parse the background and the showcases of a profile page
+
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::html::SelectorError;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    InvalidSelector(#[from] SelectorError),
+}
+
+/// The equipped background of a profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ProfileBackground {
+    Image(String),
+    /// The videos are the same in different formats, the poster is shown while they load
+    Animated {
+        poster: String,
+        videos: Vec<String>,
+    },
+}
+
+/// An item in an item showcase, identified like in the economy
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ShowcaseItem {
+    pub app_id: u32,
+    pub class_id: u64,
+    /// `0` if the class has a single instance
+    pub instance_id: u64,
+    pub image_url: String,
+}
+
+/// A screenshot in a screenshot showcase, the first one is shown large
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ShowcaseScreenshot {
+    pub published_file_id: u64,
+    pub image_url: String,
+}
+
+/// A showcase on a profile, in the order they're shown
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Showcase {
+    Items(Vec<ShowcaseItem>),
+    Screenshots(Vec<ShowcaseScreenshot>),
+    /// A showcase whose contents aren't parsed yet, with its translated title
+    Other {
+        title: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProfilePage {
+    /// [`None`] if no background is equipped
+    pub background: Option<ProfileBackground>,
+    pub showcases: Vec<Showcase>,
+}
+
+impl ProfilePage {
+    /// Drop the screenshot showcases, the only personal content besides the items
+    ///
+    /// Done for every profile page, if the client was built with
+    /// [`ClientBuilder::minimal_fields`](crate::ClientBuilder::minimal_fields)
+    pub fn minimize(&mut self) {
+        self.showcases
+            .retain(|showcase| !matches!(showcase, Showcase::Screenshots(_)));
+    }
+}
+
+/// The url in a `background-image: url( '..' );` style
+fn background_image_url(style: &str) -> Option<&str> {
+    let start = style.find("url(")? + "url(".len();
+    let end = start + style[start..].find(')')?;
+    let url = style[start..end]
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"');
+    (!url.is_empty()).then_some(url)
+}
+
+/// Parse `classinfo/{app_id}/{class_id}/{instance_id}`
+fn parse_economy_item(value: &str) -> Option<(u32, u64, u64)> {
+    let mut parts = value.strip_prefix("classinfo/")?.split('/');
+    let app_id = parts.next()?.parse().ok()?;
+    let class_id = parts.next()?.parse().ok()?;
+    let instance_id = parts.next().map_or(Some(0), |id| id.parse().ok())?;
+    Some((app_id, class_id, instance_id))
+}
+
+selectors! {
+    pub struct Parser -> Error {
+        background_image: "div.profile_background_image_content",
+        animated_background: "div.profile_animated_background>video",
+        video_source: "source",
+        showcase: "div.profile_customization",
+        header: "div.profile_customization_header",
+        item_showcase: "div.item_showcase",
+        item: "div.item_showcase_item",
+        screenshot_showcase: "div.screenshot_showcase",
+        screenshot: "a.screenshot_showcase_screenshot",
+        image: "img",
+    }
+}
+
+impl Parser {
+    fn image_url(&self, element: ElementRef) -> String {
+        element
+            .select(&self.image)
+            .next()
+            .and_then(|image| image.value().attr("src"))
+            .unwrap_or_default()
+            .to_owned()
+    }
+
+    fn parse_background(&self, html: &Html) -> Option<ProfileBackground> {
+        if let Some(video) = html.select(&self.animated_background).next() {
+            let videos = video
+                .select(&self.video_source)
+                .filter_map(|source| source.value().attr("src"))
+                .map(str::to_owned)
+                .collect();
+            let poster = video.value().attr("poster").unwrap_or_default().to_owned();
+            return Some(ProfileBackground::Animated { poster, videos });
+        }
+        let style = html
+            .select(&self.background_image)
+            .next()?
+            .value()
+            .attr("style")?;
+        background_image_url(style).map(|url| ProfileBackground::Image(url.to_owned()))
+    }
+
+    fn parse_items(&self, showcase: ElementRef) -> Vec<ShowcaseItem> {
+        showcase
+            .select(&self.item)
+            .filter_map(|item| {
+                let (app_id, class_id, instance_id) =
+                    parse_economy_item(item.value().attr("data-economy-item")?)?;
+                Some(ShowcaseItem {
+                    app_id,
+                    class_id,
+                    instance_id,
+                    image_url: self.image_url(item),
+                })
+            })
+            .collect()
+    }
+
+    fn parse_screenshots(&self, showcase: ElementRef) -> Vec<ShowcaseScreenshot> {
+        showcase
+            .select(&self.screenshot)
+            .filter_map(|screenshot| {
+                let href = screenshot.value().attr("href")?;
+                let (_, id) = href.split_once("?id=")?;
+                Some(ShowcaseScreenshot {
+                    published_file_id: id.split('&').next()?.parse().ok()?,
+                    image_url: self.image_url(screenshot),
+                })
+            })
+            .collect()
+    }
+
+    /// The kind of a showcase is told by the structure of its contents,
+    /// the title depends on the language of the page
+    fn parse_showcase(&self, showcase: ElementRef) -> Showcase {
+        if let Some(items) = showcase.select(&self.item_showcase).next() {
+            return Showcase::Items(self.parse_items(items));
+        }
+        if let Some(screenshots) = showcase.select(&self.screenshot_showcase).next() {
+            return Showcase::Screenshots(self.parse_screenshots(screenshots));
+        }
+        let title = showcase
+            .select(&self.header)
+            .next()
+            .map(|header| header.text().collect::<String>().trim().to_owned())
+            .unwrap_or_default();
+        Showcase::Other { title }
+    }
+
+    pub fn parse(&self, html: &str) -> ProfilePage {
+        let html = Html::parse_document(html);
+        ProfilePage {
+            background: self.parse_background(&html),
+            showcases: html
+                .select(&self.showcase)
+                .map(|showcase| self.parse_showcase(showcase))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{background_image_url, parse_economy_item};
+
+    #[test]
+    fn parses_attributes() {
+        assert_eq!(
+            background_image_url("background-image: url( 'https://a/b.jpg' );"),
+            Some("https://a/b.jpg")
+        );
+        assert_eq!(background_image_url("background-image: url( '' );"), None);
+        assert_eq!(
+            parse_economy_item("classinfo/440/101785959/11040578"),
+            Some((440, 101785959, 11040578))
+        );
+        assert_eq!(
+            parse_economy_item("classinfo/753/667924416"),
+            Some((753, 667924416, 0))
+        );
+        assert_eq!(parse_economy_item("classinfo/753/x/0"), None);
+    }
+}
@@ -1,28 +1,44 @@
-//! Parse the HTML payload for user search requests
+//! Parse the HTML payload for user and app search requests
 
-use scraper::{ElementRef, Html, Selector};
-use serde::Serialize;
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::constants::PROFILE_URL_ID64_PREFIX;
+use crate::constants::{APP_HUB_URL_PREFIX, PROFILE_URL_ID64_PREFIX};
+use crate::model::html::{RowSnippet, SelectorError};
 use crate::model::SteamId;
 
 #[derive(Debug, Error)]
 pub enum Error {
     /// Couldn't parse the profile-info from a row in the html-payload
-    #[error("no profile info")]
-    NoProfileInfo,
+    #[error("no profile info ({0})")]
+    NoProfileInfo(RowSnippet),
 
     /// Couldn't parse the profile-avatar from a row in the html-payload
-    #[error("no profile avatar")]
-    NoProfileAvatar,
+    #[error("no profile avatar ({0})")]
+    NoProfileAvatar(RowSnippet),
 
-    #[error("couldn't construct the html parser")]
-    InvalidSelector(#[from] scraper::error::SelectorErrorKind<'static>),
+    /// Couldn't parse the app id from the link of a row in the html-payload
+    #[error("no app id ({0})")]
+    NoAppId(RowSnippet),
+
+    /// Couldn't parse the app name and link from a row in the html-payload
+    #[error("no app name ({0})")]
+    NoAppName(RowSnippet),
+
+    /// Couldn't parse the app icon from a row in the html-payload
+    #[error("no app icon ({0})")]
+    NoAppIcon(RowSnippet),
+
+    #[error(transparent)]
+    InvalidSelector(#[from] SelectorError),
 }
 type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Serialize, Debug, Clone)]
+/// What the custom url of a match is shown after, without the scheme
+const CUSTOM_URL_PREFIX: &str = "steamcommunity.com/id/";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserSearchEntry {
     pub persona_name: String,
     pub profile_url: String,
@@ -31,6 +47,11 @@ pub struct UserSearchEntry {
 }
 
 impl UserSearchEntry {
+    /// Clear the avatar, the only personal field besides the names
+    pub fn minimize(&mut self) {
+        self.avatar_full.clear();
+    }
+
     /// Get the [`SteamId`] from the URL if possible
     ///
     /// # Example
@@ -42,56 +63,60 @@ impl UserSearchEntry {
     }
 }
 
-pub struct Parser {
-    row: Selector,
-    info: Selector,
-    alias_outer: Selector,
-    alias_inner: Selector,
-    profile_pic: Selector,
+/// A game hub found with the `apps` search filter
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AppSearchEntry {
+    pub app_id: u32,
+    pub name: String,
+    pub icon_url: String,
 }
 
-impl Parser {
-    pub fn new() -> Result<Self> {
-        Ok(Self {
-            row: Selector::parse("div.search_row")?,
-            info: Selector::parse("a.searchPersonaName")?,
-            alias_outer: Selector::parse("div.search_match_info>div")?,
-            alias_inner: Selector::parse("span")?,
-            profile_pic: Selector::parse("div.avatarMedium>a>img")?,
-        })
+selectors! {
+    pub struct Parser -> Error {
+        row: "div.search_row",
+        info: "a.searchPersonaName",
+        alias_outer: "div.search_match_info>div",
+        alias_inner: "span",
+        profile_pic: "div.avatarMedium>a>img",
     }
+}
 
+impl Parser {
     fn parse_row(&self, row: ElementRef) -> Result<UserSearchEntry> {
         const AVATAR_MEDIUM_SUFFIX: &str = "_medium.jpg";
         const AVATAR_FULL_SUFFIX: &str = "_full.jpg";
 
         let (profile_url, persona_name) = {
             let Some(info) = row.select(&self.info).next() else {
-                return Err(Error::NoProfileInfo);
+                return Err(Error::NoProfileInfo(RowSnippet::new(row)));
             };
             let profile_url = match info.value().attr("href") {
                 Some(href) => href.to_owned(),
-                None => return Err(Error::NoProfileInfo),
+                None => return Err(Error::NoProfileInfo(RowSnippet::new(row))),
             };
             (profile_url, info.inner_html())
         };
 
         let avatar_full = {
-            let Some(image) = row.select(&self.profile_pic).next() else {
-                return Err(Error::NoProfileAvatar);
-            };
-            let mut avatar_medium = match image.value().attr("src") {
-                Some(avatar) => (avatar[..avatar.len() - AVATAR_MEDIUM_SUFFIX.len()]).to_owned(),
-                None => return Err(Error::NoProfileAvatar),
+            let avatar = row
+                .select(&self.profile_pic)
+                .next()
+                .and_then(|image| image.value().attr("src"))
+                .and_then(|avatar| avatar.strip_suffix(AVATAR_MEDIUM_SUFFIX));
+            let Some(avatar) = avatar else {
+                return Err(Error::NoProfileAvatar(RowSnippet::new(row)));
             };
+            let mut avatar_medium = avatar.to_owned();
             avatar_medium.push_str(AVATAR_FULL_SUFFIX);
             avatar_medium
         };
 
+        // the labels depend on the language of the session, so the custom url is told apart
+        // by the url in front of its span and every other match lists aliases
         let mut aliases = Vec::new();
         for inner_div in row.select(&self.alias_outer) {
-            let div_inner = inner_div.inner_html();
-            if !div_inner.trim_start().starts_with("Also known as") {
+            let label = inner_div.text().next().unwrap_or_default();
+            if label.trim_end().ends_with(CUSTOM_URL_PREFIX) {
                 continue;
             }
             for inner_span in inner_div.select(&self.alias_inner) {
@@ -107,6 +132,41 @@ impl Parser {
         })
     }
 
+    fn parse_app_row(&self, row: ElementRef) -> Result<AppSearchEntry> {
+        let Some(info) = row.select(&self.info).next() else {
+            return Err(Error::NoAppName(RowSnippet::new(row)));
+        };
+        let app_id = info
+            .value()
+            .attr("href")
+            .and_then(|href| href.strip_prefix(APP_HUB_URL_PREFIX))
+            .and_then(|id| id.trim_end_matches('/').parse().ok())
+            .ok_or_else(|| Error::NoAppId(RowSnippet::new(row)))?;
+        let name = info.text().collect::<String>();
+
+        let icon_url = row
+            .select(&self.profile_pic)
+            .next()
+            .and_then(|image| image.value().attr("src"));
+        let Some(icon_url) = icon_url else {
+            return Err(Error::NoAppIcon(RowSnippet::new(row)));
+        };
+
+        Ok(AppSearchEntry {
+            app_id,
+            name,
+            icon_url: icon_url.to_owned(),
+        })
+    }
+
+    /// Parse the results of a search with the `apps` filter
+    pub fn parse_apps(&self, html: &str) -> Result<Vec<AppSearchEntry>> {
+        let html = Html::parse_fragment(html);
+        html.select(&self.row)
+            .map(|row| self.parse_app_row(row))
+            .collect()
+    }
+
     pub fn parse(&self, html: &str) -> Result<Vec<UserSearchEntry>> {
         let html = Html::parse_fragment(html);
         html.select(&self.row)
@@ -114,3 +174,36 @@ impl Parser {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Parser};
+
+    #[test]
+    fn parses_aliases_in_any_language() {
+        let html = r#"<div class="search_row"><div class="avatarMedium"><a href="https://steamcommunity.com/id/sauce"><img src="https://avatars.akamai.steamstatic.com/abc_medium.jpg"></a></div><div class="searchPersonaInfo"><a class="searchPersonaName" href="https://steamcommunity.com/id/sauce">Sauce</a></div><div class="search_match_info"><div>Benutzerdefinierte URL: steamcommunity.com/id/<span style="color: whitesmoke">sauce</span></div><div>Auch bekannt als: <span style="color: whitesmoke">Soße</span>, <span style="color: whitesmoke">Sauce</span></div></div></div>"#;
+        let entries = Parser::new().unwrap().parse(html).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].aliases, ["Soße", "Sauce"]);
+    }
+
+    #[test]
+    fn reports_failing_rows() {
+        let html = r#"<div class="search_row"><div class="avatarMedium"><a href="https://steamcommunity.com/id/sauce"><img src="https://avatars.akamai.steamstatic.com/abc.png"></a></div><div class="searchPersonaInfo"><a class="searchPersonaName" href="https://steamcommunity.com/id/sauce">Sauce</a></div></div>"#;
+        let Err(Error::NoProfileAvatar(snippet)) = Parser::new().unwrap().parse(html) else {
+            panic!("avatar without the medium suffix parsed");
+        };
+        assert!(snippet.html.starts_with(r#"<div class="search_row">"#));
+        assert!(snippet.html.contains("abc.png"));
+    }
+
+    #[test]
+    fn reports_failing_app_rows() {
+        let parser = Parser::new().unwrap();
+        let html = r#"<div class="search_row"><div class="avatarMedium"><a href="https://steamcommunity.com/app/440"><img src="https://cdn.akamai.steamstatic.com/steam/apps/440/icon.jpg"></a></div></div>"#;
+        assert!(matches!(parser.parse_apps(html), Err(Error::NoAppName(_))));
+
+        let html = r#"<div class="search_row"><div class="searchPersonaInfo"><a class="searchPersonaName" href="https://steamcommunity.com/app/440">Team Fortress 2</a></div></div>"#;
+        assert!(matches!(parser.parse_apps(html), Err(Error::NoAppIcon(_))));
+    }
+}
@@ -4,8 +4,23 @@ mod primitives;
 pub use primitives::*;
 
 pub mod steam_id;
-pub use steam_id::{SteamId, SteamIdQueryExt, SteamIdStr};
+pub use steam_id::{
+    convert, ConvertError, IdReport, LobbyId, LobbyIdError, SteamId, SteamIdChunks,
+    SteamIdQueryExt, SteamIdStr,
+};
 
 pub mod html;
 
+pub mod xml;
+
 pub mod constants;
+
+pub mod query;
+
+mod privacy;
+pub use privacy::ProfilePrivacy;
+
+mod endpoint;
+pub use endpoint::{Endpoint, Host, RateClass};
+
+mod serde_util;
@@ -1,7 +1,11 @@
+use std::fmt;
+
+use serde::Serialize;
+
 use super::EnumError;
 
 /// <https://developer.valvesoftware.com/wiki/SteamID#Types_of_Steam_Accounts>
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
 pub enum AccountType {
     Invalid,
     Individual,
@@ -37,18 +41,46 @@ impl TryFrom<u64> for AccountType {
 }
 
 impl AccountType {
+    /// Every account type with its letter in steam id 3 and its name,
+    /// indexed by [`AccountType::as_u64`]
+    pub const TABLE: [(AccountType, Option<char>, &'static str); 11] = [
+        (AccountType::Invalid, Some('I'), "Invalid"),
+        (AccountType::Individual, Some('U'), "Individual"),
+        (AccountType::Multiseat, Some('M'), "Multiseat"),
+        (AccountType::GameServer, Some('G'), "Game Server"),
+        (
+            AccountType::AnonGameServer,
+            Some('A'),
+            "Anonymous Game Server",
+        ),
+        (AccountType::Pending, Some('P'), "Pending"),
+        (AccountType::ContentServer, Some('C'), "Content Server"),
+        (AccountType::Clan, Some('g'), "Clan"),
+        (AccountType::Chat, None, "Chat"),
+        (AccountType::SuperSeeder, None, "P2P Super Seeder"),
+        (AccountType::AnonUser, Some('a'), "Anonymous User"),
+    ];
+
     pub const fn to_letter(self) -> Option<char> {
-        match self {
-            AccountType::Invalid => Some('I'),
-            AccountType::Individual => Some('U'),
-            AccountType::Multiseat => Some('M'),
-            AccountType::GameServer => Some('G'),
-            AccountType::AnonGameServer => Some('A'),
-            AccountType::Pending => Some('P'),
-            AccountType::ContentServer => Some('C'),
-            AccountType::Clan => Some('g'),
-            AccountType::Chat | AccountType::SuperSeeder => None,
-            AccountType::AnonUser => Some('a'),
+        Self::TABLE[self.as_u64() as usize].1
+    }
+    /// The name to show to users, e.g. `Game Server`
+    pub const fn name(self) -> &'static str {
+        Self::TABLE[self.as_u64() as usize].2
+    }
+    /// Inverse of [`AccountType::to_letter`]
+    pub const fn from_letter(letter: char) -> Option<AccountType> {
+        match letter {
+            'I' => Some(AccountType::Invalid),
+            'U' => Some(AccountType::Individual),
+            'M' => Some(AccountType::Multiseat),
+            'G' => Some(AccountType::GameServer),
+            'A' => Some(AccountType::AnonGameServer),
+            'P' => Some(AccountType::Pending),
+            'C' => Some(AccountType::ContentServer),
+            'g' => Some(AccountType::Clan),
+            'a' => Some(AccountType::AnonUser),
+            _ => None,
         }
     }
     pub const fn as_u64(self) -> u64 {
@@ -67,3 +99,26 @@ impl AccountType {
         }
     }
 }
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccountType;
+
+    #[test]
+    fn table_matches_variants() {
+        for (i, (account_type, letter, name)) in AccountType::TABLE.into_iter().enumerate() {
+            assert_eq!(AccountType::try_from(i as u64).ok(), Some(account_type));
+            assert_eq!(account_type.to_letter(), letter);
+            assert_eq!(account_type.to_string(), name);
+            if let Some(letter) = letter {
+                assert_eq!(AccountType::from_letter(letter), Some(account_type));
+            }
+        }
+    }
+}
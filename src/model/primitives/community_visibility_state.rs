@@ -1,10 +1,10 @@
 use serde::de::{self, Unexpected, Visitor};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::EnumError;
 
 /// <https://developer.valvesoftware.com/wiki/Steam_Web_API#Public_Data>
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CommunityVisibilityState {
     Private = 1,
     FriendsOnly = 2,
@@ -23,6 +23,34 @@ impl TryFrom<i64> for CommunityVisibilityState {
     }
 }
 
+impl CommunityVisibilityState {
+    const fn variant_name(self) -> &'static str {
+        match self {
+            CommunityVisibilityState::Private => "Private",
+            CommunityVisibilityState::FriendsOnly => "FriendsOnly",
+            CommunityVisibilityState::Public => "Public",
+        }
+    }
+}
+
+impl Serialize for CommunityVisibilityState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let index = *self as u32 - 1;
+            serializer.serialize_unit_variant(
+                "CommunityVisibilityState",
+                index,
+                self.variant_name(),
+            )
+        } else {
+            serializer.serialize_i64(*self as i64)
+        }
+    }
+}
+
 struct CommunityVisibilityStateVisitor;
 
 impl<'de> Visitor<'de> for CommunityVisibilityStateVisitor {
@@ -83,4 +111,15 @@ mod test {
         assert_eq!(states.next(), Some(CommunityVisibilityState::Public));
         assert_eq!(states.next(), None);
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let states = vec![
+            CommunityVisibilityState::Private,
+            CommunityVisibilityState::Public,
+        ];
+        let decoded = assert_bincode_round_trip!(states, Vec<CommunityVisibilityState>);
+        assert_eq!(decoded, states);
+    }
 }
@@ -0,0 +1,123 @@
+use serde::de::{self, Unexpected, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::EnumError;
+
+/// The Steam Deck compatibility category of an app, as shown on its store page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DeckCompatibility {
+    /// Valve hasn't tested the app yet
+    Unknown = 0,
+    Unsupported = 1,
+    /// Works, but may need tweaks like using the touch screen
+    Playable = 2,
+    Verified = 3,
+}
+
+impl TryFrom<i64> for DeckCompatibility {
+    type Error = EnumError<i64>;
+    fn try_from(value: i64) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DeckCompatibility::Unknown),
+            1 => Ok(DeckCompatibility::Unsupported),
+            2 => Ok(DeckCompatibility::Playable),
+            3 => Ok(DeckCompatibility::Verified),
+            _ => Err(EnumError::Unknown(value)),
+        }
+    }
+}
+
+impl DeckCompatibility {
+    const fn variant_name(self) -> &'static str {
+        match self {
+            DeckCompatibility::Unknown => "Unknown",
+            DeckCompatibility::Unsupported => "Unsupported",
+            DeckCompatibility::Playable => "Playable",
+            DeckCompatibility::Verified => "Verified",
+        }
+    }
+
+    /// Whether the app is known to run on the Deck, i.e. it's playable or verified
+    pub const fn is_playable(self) -> bool {
+        matches!(
+            self,
+            DeckCompatibility::Playable | DeckCompatibility::Verified
+        )
+    }
+}
+
+impl Serialize for DeckCompatibility {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_unit_variant(
+                "DeckCompatibility",
+                *self as u32,
+                self.variant_name(),
+            )
+        } else {
+            serializer.serialize_i64(*self as i64)
+        }
+    }
+}
+
+struct DeckCompatibilityVisitor;
+
+impl<'de> Visitor<'de> for DeckCompatibilityVisitor {
+    type Value = DeckCompatibility;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("deck compatibility category as an integer")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        DeckCompatibility::try_from(v)
+            .map_err(|_| de::Error::invalid_value(Unexpected::Signed(v), &self))
+    }
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let signed = i64::try_from(v)
+            .map_err(|_| de::Error::invalid_value(Unexpected::Unsigned(v), &self))?;
+        self.visit_i64(signed)
+    }
+}
+
+impl<'de> Deserialize<'de> for DeckCompatibility {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_i64(DeckCompatibilityVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DeckCompatibility;
+
+    #[test]
+    fn deserialize_deck_compatibility() {
+        let parsed: Vec<DeckCompatibility> = serde_json::from_str("[0, 1, 2, 3]").unwrap();
+        assert_eq!(
+            parsed,
+            [
+                DeckCompatibility::Unknown,
+                DeckCompatibility::Unsupported,
+                DeckCompatibility::Playable,
+                DeckCompatibility::Verified,
+            ]
+        );
+        assert!(serde_json::from_str::<DeckCompatibility>("4").is_err());
+        assert_eq!(
+            serde_json::to_string(&DeckCompatibility::Playable).unwrap(),
+            "\"Playable\""
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use serde::de::{self, Unexpected, Visitor};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::EnumError;
 
 /// Undocumented 👻
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EconomyBan {
     None,
     Probation,
@@ -23,6 +23,48 @@ impl<'a> TryFrom<&'a str> for EconomyBan {
     }
 }
 
+impl EconomyBan {
+    /// The value used by the API
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            EconomyBan::None => "none",
+            EconomyBan::Probation => "probation",
+            EconomyBan::Banned => "banned",
+        }
+    }
+    const fn variant_name(&self) -> &'static str {
+        match self {
+            EconomyBan::None => "None",
+            EconomyBan::Probation => "Probation",
+            EconomyBan::Banned => "Banned",
+        }
+    }
+    const fn variant_index(&self) -> u32 {
+        match self {
+            EconomyBan::None => 0,
+            EconomyBan::Probation => 1,
+            EconomyBan::Banned => 2,
+        }
+    }
+}
+
+impl Serialize for EconomyBan {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_unit_variant(
+                "EconomyBan",
+                self.variant_index(),
+                self.variant_name(),
+            )
+        } else {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+}
+
 struct EconomyBanVisitor;
 
 impl<'de> Visitor<'de> for EconomyBanVisitor {
@@ -74,4 +116,12 @@ mod test {
         assert_eq!(states.next(), Some(EconomyBan::Banned));
         assert_eq!(states.next(), None);
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let bans = vec![EconomyBan::None, EconomyBan::Probation, EconomyBan::Banned];
+        let decoded = assert_bincode_round_trip!(bans, Vec<EconomyBan>);
+        assert_eq!(decoded, bans);
+    }
 }
@@ -1,3 +1,10 @@
+//! Enums and timestamps of the api responses.
+//!
+//! Their `Serialize` impls are written by hand: human readable formats like json get the
+//! names of the variants and dates, compact formats like bincode (`util::snapshot`) get the
+//! raw values Steam sends, which is what the `Deserialize` impls parse, so snapshots can be
+//! decoded again.
+
 pub enum EnumError<T> {
     Unknown(T),
 }
@@ -11,6 +18,9 @@ pub use economy_ban::EconomyBan;
 mod persona_state;
 pub use persona_state::PersonaState;
 
+mod persona_state_flags;
+pub use persona_state_flags::{ClientType, PersonaStateFlags};
+
 mod profile_state;
 pub use profile_state::ProfileState;
 
@@ -22,3 +32,6 @@ pub use universe::Universe;
 
 mod steam_time;
 pub use steam_time::SteamTime;
+
+mod deck_compatibility;
+pub use deck_compatibility::DeckCompatibility;
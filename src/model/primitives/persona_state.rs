@@ -1,10 +1,10 @@
 use serde::de::{self, Unexpected, Visitor};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::EnumError;
 
 /// <https://developer.valvesoftware.com/wiki/Steam_Web_API#Public_Data>
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PersonaState {
     Offline = 0,
     Online = 1,
@@ -33,6 +33,34 @@ impl TryFrom<i64> for PersonaState {
     }
 }
 
+impl PersonaState {
+    const fn variant_name(self) -> &'static str {
+        match self {
+            PersonaState::Offline => "Offline",
+            PersonaState::Online => "Online",
+            PersonaState::Busy => "Busy",
+            PersonaState::Away => "Away",
+            PersonaState::Snooze => "Snooze",
+            PersonaState::LookingToTrade => "LookingToTrade",
+            PersonaState::LookingToPlay => "LookingToPlay",
+            PersonaState::Invisible => "Invisible",
+        }
+    }
+}
+
+impl Serialize for PersonaState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_unit_variant("PersonaState", *self as u32, self.variant_name())
+        } else {
+            serializer.serialize_i64(*self as i64)
+        }
+    }
+}
+
 struct PersonaStateVisitor;
 
 impl<'de> Visitor<'de> for PersonaStateVisitor {
@@ -98,4 +126,12 @@ mod test {
         assert_eq!(states.next(), Some(PersonaState::Invisible));
         assert_eq!(states.next(), None);
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let states = vec![PersonaState::Offline, PersonaState::LookingToPlay];
+        let decoded = assert_bincode_round_trip!(states, Vec<PersonaState>);
+        assert_eq!(decoded, states);
+    }
 }
@@ -0,0 +1,82 @@
+use std::ops::BitOr;
+
+use serde::{Deserialize, Serialize};
+
+/// <https://partner.steamgames.com/doc/api/ISteamFriends#EPersonaStateFlag>
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(transparent)]
+pub struct PersonaStateFlags(pub u64);
+
+impl PersonaStateFlags {
+    pub const HAS_RICH_PRESENCE: Self = Self(1);
+    pub const IN_JOINABLE_GAME: Self = Self(2);
+    pub const GOLDEN: Self = Self(4);
+    pub const REMOTE_PLAY_TOGETHER: Self = Self(8);
+    pub const CLIENT_TYPE_WEB: Self = Self(256);
+    pub const CLIENT_TYPE_MOBILE: Self = Self(512);
+    /// Big Picture mode
+    pub const CLIENT_TYPE_TENFOOT: Self = Self(1024);
+    pub const CLIENT_TYPE_VR: Self = Self(2048);
+    pub const LAUNCH_TYPE_GAMEPAD: Self = Self(4096);
+    pub const LAUNCH_TYPE_COMPAT_TOOL: Self = Self(8192);
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Whether all flags of `other` are set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The kind of client the user is logged in with, the desktop client sets no flag
+    pub const fn client_type(self) -> ClientType {
+        // only one is set at a time, the more specific ones win just in case
+        if self.contains(Self::CLIENT_TYPE_VR) {
+            ClientType::Vr
+        } else if self.contains(Self::CLIENT_TYPE_TENFOOT) {
+            ClientType::BigPicture
+        } else if self.contains(Self::CLIENT_TYPE_MOBILE) {
+            ClientType::Mobile
+        } else if self.contains(Self::CLIENT_TYPE_WEB) {
+            ClientType::Web
+        } else {
+            ClientType::Desktop
+        }
+    }
+}
+
+impl BitOr for PersonaStateFlags {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// See [`PersonaStateFlags::client_type`]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClientType {
+    Desktop,
+    Web,
+    Mobile,
+    BigPicture,
+    Vr,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClientType, PersonaStateFlags};
+
+    #[test]
+    fn detects_client_type() {
+        assert_eq!(PersonaStateFlags(0).client_type(), ClientType::Desktop);
+        assert_eq!(PersonaStateFlags(512).client_type(), ClientType::Mobile);
+        let flags = PersonaStateFlags::HAS_RICH_PRESENCE | PersonaStateFlags::CLIENT_TYPE_TENFOOT;
+        assert_eq!(flags.client_type(), ClientType::BigPicture);
+        assert!(flags.contains(PersonaStateFlags::HAS_RICH_PRESENCE));
+        assert!(!flags.contains(PersonaStateFlags::GOLDEN));
+
+        let parsed: PersonaStateFlags = serde_json::from_str("2304").unwrap();
+        assert_eq!(parsed.client_type(), ClientType::Vr);
+    }
+}
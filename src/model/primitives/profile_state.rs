@@ -1,12 +1,30 @@
 use serde::de::{self, Unexpected, Visitor};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ProfileState {
     Configured,
     NotConfigured,
 }
 
+impl Serialize for ProfileState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match (serializer.is_human_readable(), self) {
+            (true, ProfileState::Configured) => {
+                serializer.serialize_unit_variant("ProfileState", 0, "Configured")
+            }
+            (true, ProfileState::NotConfigured) => {
+                serializer.serialize_unit_variant("ProfileState", 1, "NotConfigured")
+            }
+            (false, ProfileState::Configured) => serializer.serialize_some(&1_i64),
+            (false, ProfileState::NotConfigured) => serializer.serialize_none(),
+        }
+    }
+}
+
 struct ProfileStateVisitor;
 
 impl<'de> Visitor<'de> for ProfileStateVisitor {
@@ -93,4 +111,12 @@ mod test {
         let state = parsed.profile_state;
         assert_eq!(state, ProfileState::NotConfigured);
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let states = vec![ProfileState::Configured, ProfileState::NotConfigured];
+        let decoded = assert_bincode_round_trip!(states, Vec<ProfileState>);
+        assert_eq!(decoded, states);
+    }
 }
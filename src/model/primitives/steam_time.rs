@@ -2,10 +2,9 @@ use std::ops::Deref;
 
 use chrono::{DateTime, Local, TimeZone, Utc};
 use serde::de::{self, Unexpected, Visitor};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Serialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(transparent)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SteamTime {
     inner: DateTime<Local>,
 }
@@ -16,6 +15,14 @@ impl SteamTime {
     }
 }
 
+impl<Tz: TimeZone> From<DateTime<Tz>> for SteamTime {
+    fn from(value: DateTime<Tz>) -> Self {
+        SteamTime {
+            inner: value.with_timezone(&Local),
+        }
+    }
+}
+
 impl Deref for SteamTime {
     type Target = DateTime<Local>;
     fn deref(&self) -> &Self::Target {
@@ -23,6 +30,19 @@ impl Deref for SteamTime {
     }
 }
 
+impl Serialize for SteamTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            self.inner.serialize(serializer)
+        } else {
+            serializer.serialize_i64(self.inner.timestamp())
+        }
+    }
+}
+
 struct SteamTimeVisitor;
 
 impl<'de> Visitor<'de> for SteamTimeVisitor {
@@ -87,4 +107,12 @@ mod test {
         let time = parsed.time.into_inner();
         assert_eq!(time, expected);
     }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let time: SteamTime = serde_json::from_str("1681963569").unwrap();
+        let decoded = assert_bincode_round_trip!(time, SteamTime);
+        assert_eq!(decoded, time);
+    }
 }
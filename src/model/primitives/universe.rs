@@ -1,7 +1,11 @@
+use std::fmt;
+
+use serde::Serialize;
+
 use super::EnumError;
 
 /// <https://developer.valvesoftware.com/wiki/SteamID#Universes_Available_for_Steam_Accounts>
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Copy)]
 pub enum Universe {
     Invalid,
     Public,
@@ -27,6 +31,20 @@ impl TryFrom<u64> for Universe {
 }
 
 impl Universe {
+    /// Every universe with its name, indexed by [`Universe::as_u64`]
+    pub const TABLE: [(Universe, &'static str); 6] = [
+        (Universe::Invalid, "Invalid"),
+        (Universe::Public, "Public"),
+        (Universe::Beta, "Beta"),
+        (Universe::Internal, "Internal"),
+        (Universe::Dev, "Dev"),
+        (Universe::Rc, "RC"),
+    ];
+
+    /// The name to show to users, e.g. `Public`
+    pub const fn name(self) -> &'static str {
+        Self::TABLE[self.as_u64() as usize].1
+    }
     pub const fn as_u64(self) -> u64 {
         match self {
             Universe::Invalid => 0,
@@ -38,3 +56,22 @@ impl Universe {
         }
     }
 }
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Universe;
+
+    #[test]
+    fn table_matches_variants() {
+        for (i, (universe, name)) in Universe::TABLE.into_iter().enumerate() {
+            assert_eq!(Universe::try_from(i as u64).ok(), Some(universe));
+            assert_eq!(universe.to_string(), name);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Classify how much of a profile is visible from the different signals the API gives
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{FriendsList, PlayerBan, PlayerSummary};
+use crate::CommunityVisibilityState;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfilePrivacy {
+    Public,
+    FriendsOnly,
+    Private,
+    /// The profile has no summary
+    Deleted,
+    /// The profile has been banned from the community, its summary can't be trusted
+    CommunityBanned,
+}
+
+impl ProfilePrivacy {
+    /// Combine the signals into one classification, checked in this order
+    ///
+    /// 1. `ban` is community banned → [`ProfilePrivacy::CommunityBanned`]
+    /// 2. There is no `summary` → [`ProfilePrivacy::Deleted`]
+    /// 3. The summary is public → [`ProfilePrivacy::Public`]
+    /// 4. The summary is friends only, or it is private but `friends` could be read
+    ///    (the API reports friends only profiles as private to non-friends) →
+    ///    [`ProfilePrivacy::FriendsOnly`]
+    /// 5. Otherwise → [`ProfilePrivacy::Private`]
+    ///
+    /// `friends` is the result of a [`Client::get_player_friends`](crate::Client::get_player_friends)
+    /// attempt, which is private if the request failed with `401 Unauthorized`.
+    pub fn classify(
+        summary: Option<&PlayerSummary>,
+        ban: Option<&PlayerBan>,
+        friends: Option<&FriendsList>,
+    ) -> Self {
+        if ban.is_some_and(|ban| ban.community_banned) {
+            return ProfilePrivacy::CommunityBanned;
+        }
+        let Some(summary) = summary else {
+            return ProfilePrivacy::Deleted;
+        };
+
+        let friends_visible = friends.is_some_and(|friends| friends.as_inner_ref().is_some());
+        match summary.community_visibility_state() {
+            CommunityVisibilityState::Public => ProfilePrivacy::Public,
+            CommunityVisibilityState::FriendsOnly => ProfilePrivacy::FriendsOnly,
+            CommunityVisibilityState::Private if friends_visible => ProfilePrivacy::FriendsOnly,
+            CommunityVisibilityState::Private => ProfilePrivacy::Private,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ProfilePrivacy;
+    use crate::api::{FriendsList, PlayerBan, PlayerSummary};
+    use crate::test_util::{player_ban, player_summary};
+
+    fn summary(visibility: i64) -> PlayerSummary {
+        player_summary(json!({ "communityvisibilitystate": visibility }))
+    }
+
+    fn ban(community_banned: bool) -> PlayerBan {
+        player_ban(json!({ "CommunityBanned": community_banned }))
+    }
+
+    #[test]
+    fn classifies() {
+        let public = summary(3);
+        let private = summary(1);
+        let visible: FriendsList = serde_json::from_value(json!({})).unwrap();
+        let hidden: FriendsList = serde_json::from_value(json!(null)).unwrap();
+
+        let cases = [
+            (
+                Some(&public),
+                Some(ban(true)),
+                None,
+                ProfilePrivacy::CommunityBanned,
+            ),
+            (None, Some(ban(false)), None, ProfilePrivacy::Deleted),
+            (
+                Some(&public),
+                Some(ban(false)),
+                None,
+                ProfilePrivacy::Public,
+            ),
+            (Some(&public), None, Some(&hidden), ProfilePrivacy::Public),
+            (Some(&summary(2)), None, None, ProfilePrivacy::FriendsOnly),
+            (
+                Some(&private),
+                None,
+                Some(&visible),
+                ProfilePrivacy::FriendsOnly,
+            ),
+            (Some(&private), None, Some(&hidden), ProfilePrivacy::Private),
+            (Some(&private), None, None, ProfilePrivacy::Private),
+        ];
+        for (summary, ban, friends, expected) in cases {
+            let privacy = ProfilePrivacy::classify(summary, ban.as_ref(), friends);
+            assert_eq!(privacy, expected);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Format query parameters of user-defined request structs like the built-in methods do,
+//! for [`Client::get_api_json_with`](crate::Client::get_api_json_with).
+//!
+//! The modules work with `#[serde(with = "..")]`, lists are joined with `,`.
+//!
+//! ```
+//! use serde::Serialize;
+//! use steam_api_concurrent::query;
+//! use steam_api_concurrent::SteamId;
+//!
+//! #[derive(Serialize)]
+//! struct Request {
+//!     #[serde(rename = "steamids", with = "query::steam_ids")]
+//!     steam_ids: Vec<SteamId>,
+//!     #[serde(rename = "appids", with = "query::app_ids")]
+//!     app_ids: Vec<u32>,
+//! }
+//!
+//! let request = Request {
+//!     steam_ids: vec![SteamId(76561197960287930), SteamId(76561198196615742)],
+//!     app_ids: vec![440, 730],
+//! };
+//! assert_eq!(
+//!     query::to_pairs(&request).unwrap(),
+//!     [
+//!         ("steamids".to_string(), "76561197960287930,76561198196615742".to_string()),
+//!         ("appids".to_string(), "440,730".to_string()),
+//!     ]
+//! );
+//! ```
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
+
+/// Turn `query` into the pairs of a query string
+pub fn to_pairs<Q: Serialize>(
+    query: &Q,
+) -> Result<Vec<(String, String)>, serde_urlencoded::ser::Error> {
+    let encoded = serde_urlencoded::to_string(query)?;
+    // the encoded string always parses again
+    Ok(serde_urlencoded::from_str(&encoded).unwrap_or_default())
+}
+
+/// The query of a request in a form that doesn't depend on the order of the parameters
+/// or the api key, for keys of caches or deduplication and for audit logs.
+///
+/// The parameters are sorted by name, parameters with the same name keep their order
+/// since it can matter. The values of [`CanonicalQuery::REDACTED`] parameters are replaced
+/// with `redacted`, so the key of a request stays the same after rotating api keys.
+///
+/// ```
+/// use steam_api_concurrent::query::CanonicalQuery;
+///
+/// let a = CanonicalQuery::new([("steamid", "76561197960287930"), ("key", "secret")]);
+/// let b = CanonicalQuery::new([("key", "other"), ("steamid", "76561197960287930")]);
+/// assert_eq!(a, b);
+/// assert_eq!(a.as_str(), "key=redacted&steamid=76561197960287930");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalQuery(String);
+
+impl CanonicalQuery {
+    /// Parameters whose values are secrets
+    pub const REDACTED: [&'static str; 3] = ["key", "access_token", "sessionid"];
+
+    pub fn new<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut pairs = pairs
+            .into_iter()
+            .map(|(key, value)| {
+                let key = key.as_ref().to_string();
+                let value = match Self::REDACTED.contains(&key.as_str()) {
+                    true => "redacted".to_string(),
+                    false => value.as_ref().to_string(),
+                };
+                (key, value)
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        // pairs of strings always serialize
+        Self(serde_urlencoded::to_string(pairs).unwrap_or_default())
+    }
+
+    /// The query of the url and the form body of `request`, if it has one
+    pub fn from_request(request: &reqwest::Request) -> Self {
+        let form = request
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .is_some_and(|mime| mime == "application/x-www-form-urlencoded")
+            .then(|| request.body().and_then(reqwest::Body::as_bytes))
+            .flatten()
+            .and_then(|body| serde_urlencoded::from_bytes::<Vec<(String, String)>>(body).ok())
+            .unwrap_or_default();
+        Self::new(
+            request
+                .url()
+                .query_pairs()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .chain(form),
+        )
+    }
+
+    /// A key for requests that return the same response, the method, the url without
+    /// its query and the [`CanonicalQuery`]
+    pub fn request_key(request: &reqwest::Request) -> String {
+        let url = request.url();
+        format!(
+            "{} {}://{}{}?{}",
+            request.method(),
+            url.scheme(),
+            url.host_str().unwrap_or_default(),
+            url.path(),
+            Self::from_request(request)
+        )
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CanonicalQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Join the items with `,`
+pub fn serialize_comma_separated<T, S>(items: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    let joined = items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    serializer.serialize_str(&joined)
+}
+
+/// Split at `,`, an empty string is an empty list
+pub fn deserialize_comma_separated<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let joined = String::deserialize(deserializer)?;
+    joined
+        .split(',')
+        .filter(|item| !item.is_empty())
+        .map(|item| item.trim().parse().map_err(de::Error::custom))
+        .collect()
+}
+
+/// Lists of [`SteamId`](crate::SteamId)s, like the `steamids` of
+/// [`Endpoint::PlayerSummaries`](crate::Endpoint::PlayerSummaries)
+pub mod steam_ids {
+    use serde::{Deserializer, Serializer};
+
+    use crate::model::SteamIdQueryExt;
+    use crate::SteamId;
+
+    pub fn serialize<S>(ids: &[SteamId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&ids.iter().to_steam_id_string(","))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<SteamId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_comma_separated(deserializer)
+    }
+}
+
+/// Lists of app ids
+pub mod app_ids {
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(ids: &[u32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        super::serialize_comma_separated(ids, serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::deserialize_comma_separated(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::CanonicalQuery;
+    use crate::SteamId;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Request {
+        #[serde(with = "super::steam_ids")]
+        steamids: Vec<SteamId>,
+        #[serde(with = "super::app_ids")]
+        appids: Vec<u32>,
+    }
+
+    #[test]
+    fn round_trips() {
+        let request = Request {
+            steamids: vec![SteamId(76561197960287930)],
+            appids: vec![],
+        };
+        let encoded = serde_urlencoded::to_string(&request).unwrap();
+        assert_eq!(encoded, "steamids=76561197960287930&appids=");
+        assert_eq!(
+            serde_urlencoded::from_str::<Request>(&encoded).unwrap(),
+            request
+        );
+        assert!(serde_urlencoded::from_str::<Request>("steamids=x&appids=1").is_err());
+    }
+
+    #[test]
+    fn canonicalizes() {
+        let client = reqwest::Client::new();
+        let a = client
+            .get("https://api.steampowered.com/ISteamUser/GetFriendList/v1/")
+            .query(&[("steamid", "1"), ("key", "a"), ("relationship", "all")])
+            .build()
+            .unwrap();
+        let b = client
+            .get("https://api.steampowered.com/ISteamUser/GetFriendList/v1/")
+            .query(&[("relationship", "all"), ("key", "b"), ("steamid", "1")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            CanonicalQuery::from_request(&a),
+            CanonicalQuery::from_request(&b)
+        );
+        assert_eq!(
+            CanonicalQuery::request_key(&a),
+            "GET https://api.steampowered.com/ISteamUser/GetFriendList/v1/\
+             ?key=redacted&relationship=all&steamid=1"
+        );
+
+        let form = client
+            .post("https://steamcommunity.com/openid/login")
+            .query(&[("b", "x y")])
+            .form(&[("publishedfileids[1]", "2"), ("publishedfileids[0]", "1")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            CanonicalQuery::from_request(&form).as_str(),
+            "b=x+y&publishedfileids%5B0%5D=1&publishedfileids%5B1%5D=2"
+        );
+
+        let repeated = CanonicalQuery::new([("id", "2"), ("a", "1"), ("id", "1")]);
+        assert_eq!(repeated.as_str(), "a=1&id=2&id=1");
+    }
+}
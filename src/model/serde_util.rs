@@ -0,0 +1,88 @@
+//! `deserialize_with` helpers shared by the api models.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StrOrNumber {
+    Str(String),
+    Number(serde_json::Number),
+}
+
+impl StrOrNumber {
+    fn parse<T>(&self) -> Result<T, T::Err>
+    where
+        T: FromStr,
+    {
+        match self {
+            Self::Str(str) => str.parse(),
+            Self::Number(num) => num.to_string().parse(),
+        }
+    }
+}
+
+/// A number Steam sends as a string, like ids that don't fit into a javascript number,
+/// or as a number in some responses
+pub(crate) fn from_str_or_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    StrOrNumber::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Like [`from_str_or_number`], but [`None`] if the field is null, of another type or
+/// not a valid number, use with `#[serde(default)]`
+pub(crate) fn from_str_or_number_lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value
+        .and_then(|value| StrOrNumber::deserialize(value).ok())
+        .and_then(|value| value.parse().ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Strict {
+        #[serde(deserialize_with = "super::from_str_or_number")]
+        id: u64,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Lenient {
+        #[serde(default, deserialize_with = "super::from_str_or_number_lenient")]
+        xp: Option<u64>,
+    }
+
+    #[test]
+    fn parses_strings_and_numbers() {
+        let strict = |json: &str| serde_json::from_str::<Strict>(json).map(|s| s.id);
+        assert_eq!(
+            strict(r#"{"id":"76561197960287930"}"#).unwrap(),
+            76561197960287930
+        );
+        assert_eq!(strict(r#"{"id":42}"#).unwrap(), 42);
+        assert!(strict(r#"{"id":"nope"}"#).is_err());
+        assert!(strict(r#"{"id":-1}"#).is_err());
+
+        let lenient = |json: &str| serde_json::from_str::<Lenient>(json).unwrap().xp;
+        assert_eq!(lenient(r#"{"xp":"1200"}"#), Some(1200));
+        assert_eq!(lenient(r#"{"xp":1200}"#), Some(1200));
+        assert_eq!(lenient(r#"{"xp":"nope"}"#), None);
+        assert_eq!(lenient(r#"{"xp":1.5}"#), None);
+        assert_eq!(lenient(r#"{"xp":null}"#), None);
+        assert_eq!(lenient("{}"), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Recognize ids in any common format and convert them into every other format
+
+use std::fmt::Display;
+
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+
+use super::SteamId;
+use crate::constants::{PROFILE_URL_ID64_PREFIX, PROFILE_URL_VANITY_PREFIX};
+use crate::model::{AccountType, Universe};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The input is a vanity url, which has to be resolved with
+    /// [`Client::resolve_vanity_url`](crate::Client::resolve_vanity_url)
+    #[error("'{0}' is a vanity url and has to be resolved")]
+    Vanity(String),
+
+    #[error("'{0}' is not a known id format")]
+    Unrecognized(String),
+}
+
+/// An id in every representation, see [`convert`]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct IdReport {
+    pub id64: SteamId,
+    /// `STEAM_X:Y:Z`
+    pub steam_id: Option<String>,
+    /// `[U:1:W]`
+    pub steam_id_3: Option<String>,
+    /// `W`, the lower `32` bits of the id
+    pub account_id: u32,
+    /// Friend code of the Steam client, only available with the `friend_code` feature
+    pub friend_code: Option<String>,
+    /// CS:GO friend code, only available with the `friend_code` feature
+    pub csgo_friend_code: Option<String>,
+    pub profile_url: String,
+    /// `https://s.team/p/xxxx-xxxx`
+    pub invite_link: Option<String>,
+    /// Serialized as [`Universe::name`]
+    #[serde(serialize_with = "serialize_name")]
+    pub universe: Option<Universe>,
+    /// Serialized as [`AccountType::name`]
+    #[serde(serialize_with = "serialize_name")]
+    pub account_type: Option<AccountType>,
+    pub instance: u64,
+}
+
+/// The report is meant to be read, so the enums are shown by their names
+fn serialize_name<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl From<SteamId> for IdReport {
+    fn from(id: SteamId) -> Self {
+        #[cfg(feature = "friend_code")]
+        let (friend_code, csgo_friend_code) = (id.to_friend_code(), id.to_csgo_friend_code());
+        #[cfg(not(feature = "friend_code"))]
+        let (friend_code, csgo_friend_code) = (None, None);
+
+        IdReport {
+            id64: id,
+            steam_id: id.to_steam_id(),
+            steam_id_3: id.to_steam_id_3(),
+            account_id: id.account_id(),
+            friend_code,
+            csgo_friend_code,
+            profile_url: format!("{}{}", PROFILE_URL_ID64_PREFIX, id),
+            invite_link: id.to_invite_link(),
+            universe: id.universe(),
+            account_type: id.acc_type(),
+            instance: id.instance(),
+        }
+    }
+}
+
+/// Strip the scheme and `www.` so urls can be matched regardless of how they were copied
+fn strip_url(input: &str) -> &str {
+    let input = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    input.strip_prefix("www.").unwrap_or(input)
+}
+
+impl SteamId {
+    /// Parse `input` in any of the formats in [`IdReport`].
+    ///
+    /// Plain numbers that fit into `32` bits are treated as account ids of individual accounts.
+    pub fn parse_any(input: &str) -> Result<SteamId, ConvertError> {
+        let input = input.trim();
+        let unrecognized = || ConvertError::Unrecognized(input.to_string());
+
+        let url = strip_url(input);
+        let profiles = strip_url(PROFILE_URL_ID64_PREFIX);
+        let vanity = strip_url(PROFILE_URL_VANITY_PREFIX);
+        if let Some(id) = url.strip_prefix(profiles) {
+            let id = id.split(['/', '?', '#']).next().unwrap_or_default();
+            return id.parse().map_err(|_| unrecognized());
+        }
+        if let Some(name) = url.strip_prefix(vanity) {
+            let name = name.split(['/', '?', '#']).next().unwrap_or_default();
+            if name.is_empty() {
+                return Err(unrecognized());
+            }
+            return Err(ConvertError::Vanity(name.to_string()));
+        }
+        if let Some(code) = url.strip_prefix("s.team/p/") {
+            return SteamId::from_invite_code(code).ok_or_else(unrecognized);
+        }
+
+        if input.starts_with("STEAM_") {
+            return SteamId::from_steam_id(input).ok_or_else(unrecognized);
+        }
+        if input.starts_with('[') {
+            return SteamId::from_steam_id_3(input).ok_or_else(unrecognized);
+        }
+        if let Ok(num) = input.parse::<u64>() {
+            return Ok(u32::try_from(num).map_or_else(|_| SteamId(num), SteamId::from_account_id));
+        }
+
+        #[cfg(feature = "friend_code")]
+        if let Some(id) = SteamId::from_csgo_friend_code(input) {
+            return Ok(id);
+        }
+        SteamId::from_steam_id_3(input)
+            .or_else(|| SteamId::from_invite_code(input))
+            .ok_or_else(unrecognized)
+    }
+}
+
+/// Recognize the id in `input` and return it in every representation.
+///
+/// ```
+/// let report = steam_api_concurrent::convert("STEAM_1:0:11101").unwrap();
+/// assert_eq!(report.id64.as_u64(), 76561197960287930);
+/// assert_eq!(report.steam_id_3.as_deref(), Some("[U:1:22202]"));
+/// ```
+pub fn convert(input: &str) -> Result<IdReport, ConvertError> {
+    SteamId::parse_any(input).map(IdReport::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert, ConvertError, SteamId};
+
+    const ID: SteamId = SteamId(76561197960287930);
+
+    #[test]
+    fn parses_every_format() {
+        let inputs = [
+            "76561197960287930",
+            "22202",
+            "STEAM_1:0:11101",
+            "STEAM_0:0:11101",
+            "[U:1:22202]",
+            "U:1:22202",
+            "https://steamcommunity.com/profiles/76561197960287930",
+            "steamcommunity.com/profiles/76561197960287930/",
+            "https://s.team/p/hj-qp",
+            "hj-qp",
+        ];
+        for input in inputs {
+            assert_eq!(SteamId::parse_any(input), Ok(ID), "{}", input);
+        }
+    }
+
+    #[cfg(feature = "friend_code")]
+    #[test]
+    fn parses_friend_code() {
+        assert_eq!(SteamId::parse_any("SUCVS-FADA"), Ok(ID));
+    }
+
+    #[test]
+    fn detects_vanity() {
+        assert_eq!(
+            SteamId::parse_any("https://steamcommunity.com/id/GabeLoganNewell/"),
+            Err(ConvertError::Vanity("GabeLoganNewell".to_string()))
+        );
+        assert!(matches!(
+            SteamId::parse_any("whatever"),
+            Err(ConvertError::Unrecognized(_))
+        ));
+    }
+
+    #[test]
+    fn reports_every_format() {
+        let report = convert("76561197960287930").unwrap();
+        assert_eq!(report.steam_id.as_deref(), Some("STEAM_1:0:11101"));
+        assert_eq!(report.steam_id_3.as_deref(), Some("[U:1:22202]"));
+        assert_eq!(report.account_id, 22202);
+        assert_eq!(
+            report.profile_url,
+            "https://steamcommunity.com/profiles/76561197960287930"
+        );
+        assert_eq!(
+            report.invite_link.as_deref(),
+            Some("https://s.team/p/hj-qp")
+        );
+        assert_eq!(report.instance, 1);
+    }
+}
@@ -1,7 +1,7 @@
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::model::SteamId;
-use crate::util::bit_chunks::{BitChunks, ChunksU4, ChunksU5};
+use crate::model::{AccountType, SteamId};
+use crate::util::bit_chunks::{ChunksU4, ChunksU5};
 
 const fn u32x2_to_u64(low: u32, high: u32) -> u64 {
     ((high as u64) << 32) | (low as u64)
@@ -28,14 +28,14 @@ const fn from_symbol(sym: u8) -> Option<u8> {
 }
 
 fn base32_encode_u64(num: u64) -> Option<[u8; 15]> {
-    let mut chunks = ChunksU5(num.swap_bytes());
+    let mut chunks = ChunksU5::new(num.swap_bytes());
     let mut enc_buf = [0u8; ChunksU5::MAX_CHUNKS + 2];
 
     for (i, enc) in enc_buf.iter_mut().enumerate() {
         if i == 4 || i == 10 {
             *enc = b'-';
         } else {
-            *enc = to_symbol(chunks.next().unwrap_or(0))?;
+            *enc = to_symbol(chunks.next().unwrap_or(0) as u8)?;
         }
     }
 
@@ -73,8 +73,9 @@ impl SteamId {
         LittleEndian::read_u32(&digest.0)
     }
 
-    pub fn to_friend_code(self) -> Option<String> {
-        let mut chunks = ChunksU4(self.0);
+    /// The friend code used by CS:GO and CS2, e.g. `SUCVS-FADA`
+    pub fn to_csgo_friend_code(self) -> Option<String> {
+        let mut chunks = ChunksU4::new(self.0);
 
         let mut hash = self.hash();
         let mut r = 0u64;
@@ -91,7 +92,8 @@ impl SteamId {
             .map(|s| s.to_string())
     }
 
-    pub fn from_friend_code(code: &str) -> Option<SteamId> {
+    /// Inverse of [`SteamId::to_csgo_friend_code`], always yields an individual account
+    pub fn from_csgo_friend_code(code: &str) -> Option<SteamId> {
         const DEFAULT_STEAM_ID: u64 = 0x0110_0001_0000_0000;
 
         let code = code.as_bytes();
@@ -104,48 +106,79 @@ impl SteamId {
         buf[5..].copy_from_slice(code);
 
         let decoded = base32_decode_u64(buf)?;
-        let mut chunks = ChunksU5(decoded);
+        let mut chunks = ChunksU5::new(decoded);
 
         let mut steam_id = 0u64;
         for _ in 0..8 {
             let chunk = chunks.next().unwrap_or(0);
             steam_id <<= 4;
-            steam_id |= ((chunk & 0b0001_1110) >> 1) as u64;
+            steam_id |= (chunk & 0b0001_1110) >> 1;
         }
         steam_id |= DEFAULT_STEAM_ID;
 
         Some(SteamId(steam_id))
     }
+
+    /// The friend code the Steam client shows under "Add a Friend", which is just the
+    /// account id of an individual account, e.g. `22202`.
+    ///
+    /// For a code that can be shared as a link, see [`SteamId::to_invite_code`].
+    ///
+    /// This name used to return the CS:GO code, which is [`SteamId::to_csgo_friend_code`] now.
+    pub fn to_friend_code(self) -> Option<String> {
+        if self.acc_type() != Some(AccountType::Individual) || self.account_id() == 0 {
+            return None;
+        }
+        Some(self.account_id().to_string())
+    }
+
+    /// Inverse of [`SteamId::to_friend_code`]
+    pub fn from_friend_code(code: &str) -> Option<SteamId> {
+        let account_id = code.trim().parse::<u32>().ok().filter(|&id| id != 0)?;
+        Some(SteamId::from_account_id(account_id))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::{from_symbol, to_symbol, SteamId};
 
     #[test]
-    fn to_friend_code_works() {
+    fn to_csgo_friend_code_works() {
         let id = SteamId(76561197960287930);
-        assert_eq!(id.to_friend_code(), Some("SUCVS-FADA".to_string()));
+        assert_eq!(id.to_csgo_friend_code(), Some("SUCVS-FADA".to_string()));
 
         let id = SteamId(76561199006131828);
-        assert_eq!(id.to_friend_code(), Some("SBPVY-4MQJ".to_string()));
+        assert_eq!(id.to_csgo_friend_code(), Some("SBPVY-4MQJ".to_string()));
     }
 
     #[test]
-    fn from_friend_code_works() {
+    fn from_csgo_friend_code_works() {
         let code = "SUCVS-FADA";
         assert_eq!(
-            SteamId::from_friend_code(code),
+            SteamId::from_csgo_friend_code(code),
             Some(SteamId(76561197960287930))
         );
 
         let code = "SBPVY-4MQJ";
         assert_eq!(
-            SteamId::from_friend_code(code),
+            SteamId::from_csgo_friend_code(code),
             Some(SteamId(76561199006131828))
         );
     }
 
+    #[test]
+    fn friend_code() {
+        let id = SteamId(76561197960287930);
+        assert_eq!(id.to_friend_code().as_deref(), Some("22202"));
+        assert_eq!(SteamId::from_friend_code("22202"), Some(id));
+        assert_eq!(SteamId::from_friend_code("0"), None);
+        assert_eq!(SteamId::from_friend_code("SUCVS-FADA"), None);
+        assert_eq!(SteamId(103582791429521412).to_friend_code(), None);
+    }
+
     #[test]
     fn from_symbol_offsets() {
         assert_eq!(0, from_symbol(b'A').unwrap());
@@ -176,4 +209,18 @@ mod tests {
 
         assert_eq!(to_symbol(32), None);
     }
+
+    proptest! {
+        #[test]
+        fn csgo_friend_code_round_trips(w in any::<u32>()) {
+            let id = SteamId::from_parts(1, 1, 1, (w >> 1) as u64, (w & 1) as u64);
+            let code = id.to_csgo_friend_code().unwrap();
+            prop_assert_eq!(SteamId::from_csgo_friend_code(&code), Some(id));
+        }
+
+        #[test]
+        fn symbols_round_trip(index in 0_u8..32) {
+            prop_assert_eq!(to_symbol(index).and_then(from_symbol), Some(index));
+        }
+    }
 }
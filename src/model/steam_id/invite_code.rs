@@ -0,0 +1,106 @@
+//! Quick invite codes as used in `https://s.team/p/xxxx-xxxx` links.
+//!
+//! The code is the account id in hex, with the hex digits replaced by letters.
+//!
+//! <https://github.com/xPaw/SteamID.php/blob/master/SteamID.php>
+
+use super::SteamId;
+use crate::model::AccountType;
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const INVITE: &[u8; 16] = b"bcdfghjkmnpqrtvw";
+
+pub const INVITE_URL_PREFIX: &str = "https://s.team/p/";
+
+impl SteamId {
+    /// The code used in quick invite links, e.g. `qpn-pmn` for `[U:1:12229257]`.
+    ///
+    /// Only individual accounts have invite codes.
+    pub fn to_invite_code(self) -> Option<String> {
+        if self.acc_type()? != AccountType::Individual {
+            return None;
+        }
+
+        let hex = format!("{:x}", self.w());
+        let mut code = hex
+            .bytes()
+            .filter_map(|c| HEX.iter().position(|&h| h == c))
+            .map(|i| INVITE[i] as char)
+            .collect::<String>();
+        if code.len() > 3 {
+            code.insert(code.len() / 2, '-');
+        }
+        Some(code)
+    }
+
+    /// Inverse of [`SteamId::to_invite_code`], yields an individual account.
+    ///
+    /// Also accepts the whole invite link, e.g. `https://s.team/p/cv-dgb`.
+    pub fn from_invite_code(code: &str) -> Option<SteamId> {
+        let code = code.strip_prefix(INVITE_URL_PREFIX).unwrap_or(code);
+        // Links may have a token appended, e.g. `/p/cv-dgb/TOKEN`
+        let code = code.split('/').next()?;
+
+        let mut account_id = 0_u64;
+        let mut digits = 0_usize;
+        for c in code.bytes().filter(|&c| c != b'-') {
+            let digit = INVITE.iter().position(|&i| i == c)? as u64;
+            account_id = (account_id << 4) | digit;
+            digits += 1;
+        }
+        if digits == 0 || digits > 8 {
+            return None;
+        }
+
+        Some(SteamId::from_account_id(account_id as u32))
+    }
+
+    /// The link that opens the "add friend" dialog for this account
+    pub fn to_invite_link(self) -> Option<String> {
+        let code = self.to_invite_code()?;
+        Some(format!("{}{}", INVITE_URL_PREFIX, code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SteamId;
+
+    #[test]
+    fn to_invite_code_works() {
+        let id = SteamId::from_account_id(12229257);
+        assert_eq!(id.to_invite_code().as_deref(), Some("qpn-pmn"));
+        assert_eq!(
+            id.to_invite_link().as_deref(),
+            Some("https://s.team/p/qpn-pmn")
+        );
+        let id = SteamId::from_account_id(123456);
+        assert_eq!(id.to_invite_code().as_deref(), Some("cv-dgb"));
+        assert_eq!(
+            SteamId::from_account_id(1).to_invite_code().as_deref(),
+            Some("c")
+        );
+    }
+
+    #[test]
+    fn from_invite_code_works() {
+        let id = SteamId::from_account_id(123456);
+        assert_eq!(SteamId::from_invite_code("cv-dgb"), Some(id));
+        assert_eq!(
+            SteamId::from_invite_code("https://s.team/p/cv-dgb"),
+            Some(id)
+        );
+        assert_eq!(
+            SteamId::from_invite_code("https://s.team/p/cv-dgb/ABCD"),
+            Some(id)
+        );
+        assert_eq!(SteamId::from_invite_code("ca-xyz"), None);
+        assert_eq!(SteamId::from_invite_code(""), None);
+    }
+
+    #[test]
+    fn clans_have_no_invite_code() {
+        let id = SteamId(103582791429521412);
+        assert_eq!(id.to_invite_code(), None);
+    }
+}
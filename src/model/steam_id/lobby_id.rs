@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use super::SteamId;
+use crate::model::AccountType;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LobbyIdError {
+    #[error(transparent)]
+    Parse(#[from] std::num::ParseIntError),
+
+    /// The id isn't a chat account with the lobby flag set
+    #[error("not a lobby id: {0}")]
+    NotALobby(u64),
+}
+
+/// The id of a matchmaking lobby, a [`SteamId`] of a chat account with
+/// [`LobbyId::INSTANCE_FLAG`] set in its instance.
+///
+/// (De-)serializes from and to a string, like [`SteamIdStr`](super::SteamIdStr).
+///
+/// ```
+/// use steam_api_concurrent::{LobbyId, SteamId};
+///
+/// let lobby: LobbyId = "109775241046845834".parse().unwrap();
+/// assert_eq!(lobby.steam_id(), SteamId(109775241046845834));
+/// assert!(LobbyId::new(SteamId(76561197960287930)).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LobbyId(SteamId);
+
+impl LobbyId {
+    /// Set in the instance of every lobby id
+    pub const INSTANCE_FLAG: u64 = 1 << 18;
+
+    /// [`None`], if `id` isn't a lobby id
+    pub fn new(id: SteamId) -> Option<LobbyId> {
+        let is_lobby =
+            id.acc_type() == Some(AccountType::Chat) && id.instance() & Self::INSTANCE_FLAG != 0;
+        is_lobby.then_some(LobbyId(id))
+    }
+
+    pub const fn steam_id(self) -> SteamId {
+        self.0
+    }
+}
+
+impl TryFrom<SteamId> for LobbyId {
+    type Error = LobbyIdError;
+    fn try_from(value: SteamId) -> Result<Self, Self::Error> {
+        LobbyId::new(value).ok_or(LobbyIdError::NotALobby(value.0))
+    }
+}
+
+impl From<LobbyId> for SteamId {
+    fn from(value: LobbyId) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for LobbyId {
+    type Err = LobbyIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SteamId::from_str(s)?.try_into()
+    }
+}
+
+impl fmt::Display for LobbyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for LobbyId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for LobbyId {
+    fn deserialize<D>(deserializer: D) -> Result<LobbyId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let str = <Cow<'de, str>>::deserialize(deserializer)?;
+        LobbyId::from_str(&str).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LobbyId, LobbyIdError};
+    use crate::SteamId;
+
+    #[test]
+    fn validates() {
+        let id = SteamId::from_parts(1, 8, LobbyId::INSTANCE_FLAG, 61728394, 1);
+        assert_eq!(LobbyId::new(id).map(LobbyId::steam_id), Some(id));
+        // a chat room without the lobby flag
+        assert_eq!(
+            LobbyId::new(SteamId::from_parts(1, 8, 0, 61728394, 1)),
+            None
+        );
+        assert_eq!(
+            "76561197960287930".parse::<LobbyId>(),
+            Err(LobbyIdError::NotALobby(76561197960287930))
+        );
+        assert!(matches!(
+            "lobby".parse::<LobbyId>(),
+            Err(LobbyIdError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let lobby: LobbyId = serde_json::from_str("\"109775241046845834\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&lobby).unwrap(),
+            "\"109775241046845834\""
+        );
+    }
+}
@@ -1,14 +1,22 @@
 mod query_ext;
-pub use query_ext::SteamIdQueryExt;
-use serde::{Deserialize, Serialize};
-
+pub use query_ext::{SteamIdChunks, SteamIdQueryExt};
 #[cfg(feature = "friend_code")]
 mod friend_code;
 
+mod invite_code;
+
+mod lobby_id;
+pub use invite_code::INVITE_URL_PREFIX;
+pub use lobby_id::{LobbyId, LobbyIdError};
+
+mod convert;
 use std::fmt;
 use std::fmt::Write;
+use std::ops::Range;
 use std::str::FromStr;
 
+pub use convert::{convert, ConvertError, IdReport};
+
 use crate::model::{AccountType, Universe};
 
 /// Wrapper for [`SteamId`]s that is implemented according to [`Valve`](https://developer.valvesoftware.com/wiki/SteamID)
@@ -27,10 +35,17 @@ use crate::model::{AccountType, Universe};
 /// - `X` represents the universe the steam account belongs to.
 /// - `Y` is part of the ID number for the account, it is either `0` or `1`.
 /// - `Z` is the account number.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// Serializes to a string in human-readable formats like json, which can't hold a [`u64`]
+/// losslessly in every parser, and to a [`u64`] in compact formats like bincode.
+/// Human-readable formats deserialize from both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SteamId(pub u64);
 
-/// Essentially the same as [`SteamId`] but serializes to a string and deserializes from a string.
+/// The same as [`SteamId`], for fields the api sends as a string.
+///
+/// Both serialize the same way since they respect
+/// [`Serializer::is_human_readable`](serde::Serializer::is_human_readable).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SteamIdStr(pub u64);
 
@@ -82,39 +97,92 @@ impl FromStr for SteamId {
 }
 
 pub mod ser {
-
     use serde::{Serialize, Serializer};
 
-    use super::SteamIdStr;
+    use super::{SteamId, SteamIdStr};
+
+    impl Serialize for SteamId {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                serializer.serialize_u64(self.0)
+            }
+        }
+    }
 
     impl Serialize for SteamIdStr {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: Serializer,
         {
-            serializer.serialize_str(&self.steam_id().to_string())
+            self.steam_id().serialize(serializer)
         }
     }
 }
 
 pub mod de {
-    use std::borrow::Cow;
-    use std::str::FromStr;
+    use std::fmt;
 
+    use serde::de::{self, Unexpected, Visitor};
     use serde::{Deserialize, Deserializer};
 
-    use super::SteamIdStr;
-    use crate::SteamId;
+    use super::{SteamId, SteamIdStr};
+
+    struct SteamIdVisitor;
+
+    impl Visitor<'_> for SteamIdVisitor {
+        type Value = SteamId;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a steam id as an integer or a string")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(SteamId(v))
+        }
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u64::try_from(v)
+                .map(SteamId)
+                .map_err(|_| de::Error::invalid_value(Unexpected::Signed(v), &self))
+        }
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse()
+                .map_err(|_| de::Error::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for SteamId {
+        fn deserialize<D>(deserializer: D) -> Result<SteamId, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(SteamIdVisitor)
+            } else {
+                deserializer.deserialize_u64(SteamIdVisitor)
+            }
+        }
+    }
 
     impl<'de> Deserialize<'de> for SteamIdStr {
         fn deserialize<D>(deserializer: D) -> Result<SteamIdStr, D::Error>
         where
             D: Deserializer<'de>,
         {
-            let str = <Cow<'de, str>>::deserialize(deserializer)?;
-            SteamId::from_str(&str)
-                .map_err(serde::de::Error::custom)
-                .map(SteamIdStr::from)
+            SteamId::deserialize(deserializer).map(SteamIdStr::from)
         }
     }
 }
@@ -175,6 +243,59 @@ impl SteamId {
         self.0
     }
 
+    /// The account id, also known as `W` or the lower `32` bits of the id
+    pub const fn account_id(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// The id of the individual account in the public universe with the given account id
+    pub const fn from_account_id(account_id: u32) -> SteamId {
+        let account_id = account_id as u64;
+        Self::from_parts(1, 1, 1, account_id >> 1, account_id & Self::Y_MASK)
+    }
+
+    /// The ids of the individual accounts in the public universe with an account id in
+    /// `accounts`, like [`SteamId::from_account_id`].
+    ///
+    /// Account id `0` isn't a valid account and is skipped.
+    ///
+    /// ```
+    /// use steam_api_concurrent::SteamId;
+    ///
+    /// let ids = SteamId::iter_range(22202..22205).collect::<Vec<_>>();
+    /// assert_eq!(ids[0], SteamId(76561197960287930));
+    /// assert_eq!(ids.len(), 3);
+    /// ```
+    pub fn iter_range(
+        accounts: Range<u32>,
+    ) -> impl DoubleEndedIterator<Item = SteamId> + ExactSizeIterator {
+        (accounts.start.max(1)..accounts.end).map(SteamId::from_account_id)
+    }
+
+    /// A random individual account in the public universe, with an account id in `1..2^31`
+    /// since higher ones aren't handed out yet
+    #[cfg(feature = "rand")]
+    pub fn random_individual<R: rand::Rng + ?Sized>(rng: &mut R) -> SteamId {
+        SteamId::from_account_id(rng.random_range(1..(1 << 31)))
+    }
+
+    /// Assemble a [`SteamId`] from its parts, truncating each part to its bit width
+    pub const fn from_parts(
+        universe: u64,
+        acc_type: u64,
+        instance: u64,
+        acc_nr: u64,
+        y: u64,
+    ) -> SteamId {
+        SteamId(
+            ((universe & Self::UNIVERSE_MASK) << Self::UNIVERSE_SHIFT)
+                | ((acc_type & Self::TYPE_MASK) << Self::TYPE_SHIFT)
+                | ((instance & Self::INSTANCE_MASK) << Self::INSTANCE_SHIFT)
+                | ((acc_nr & Self::ACC_NR_MASK) << Self::ACC_NR_SHIFT)
+                | ((y & Self::Y_MASK) << Self::Y_SHIFT),
+        )
+    }
+
     /// <https://developer.valvesoftware.com/wiki/SteamID#As_Represented_Textually>
     pub fn to_steam_id(&self) -> Option<String> {
         let x = self.universe()?.as_u64();
@@ -187,14 +308,66 @@ impl SteamId {
     /// <https://developer.valvesoftware.com/wiki/SteamID#Steam_ID_as_a_Steam_Community_ID>
     pub fn to_steam_id_3(&self) -> Option<String> {
         let letter = self.acc_type()?.to_letter()?;
+        let universe = self.universe()?.as_u64();
         let mut buf = String::with_capacity("[X:1:XXXXXXXXXX]".len());
-        write!(buf, "[{}:1:{}]", letter, self.w()).unwrap();
+        write!(buf, "[{}:{}:{}]", letter, universe, self.w()).unwrap();
         Some(buf)
     }
+
+    /// Inverse of [`SteamId::to_steam_id`], always yields an individual account.
+    ///
+    /// Some games use `0` for the public universe, so `STEAM_0:Y:Z` is treated like `STEAM_1:Y:Z`.
+    pub fn from_steam_id(s: &str) -> Option<SteamId> {
+        let mut parts = s.strip_prefix("STEAM_")?.splitn(3, ':');
+        let x = parts.next()?.parse::<u64>().ok()?;
+        let y = parts.next()?.parse::<u64>().ok()?;
+        let z = parts.next()?.parse::<u64>().ok()?;
+        if y > Self::Y_MASK || z > Self::ACC_NR_MASK {
+            return None;
+        }
+
+        let universe = Universe::try_from(x.max(1)).ok()?;
+        Some(Self::from_parts(
+            universe.as_u64(),
+            AccountType::Individual.as_u64(),
+            1,
+            z,
+            y,
+        ))
+    }
+
+    /// Inverse of [`SteamId::to_steam_id_3`], also accepts the id without brackets.
+    ///
+    /// Individual accounts get the default instance `1`, every other type gets `0`.
+    pub fn from_steam_id_3(s: &str) -> Option<SteamId> {
+        let s = s.trim_start_matches('[').trim_end_matches(']');
+        let mut parts = s.splitn(3, ':');
+
+        let mut letter = parts.next()?.chars();
+        let acc_type = AccountType::from_letter(letter.next()?)?;
+        if letter.next().is_some() {
+            return None;
+        }
+        let universe = Universe::try_from(parts.next()?.parse::<u64>().ok()?).ok()?;
+        let w = parts.next()?.parse::<u32>().ok()? as u64;
+
+        let instance = match acc_type {
+            AccountType::Individual => 1,
+            _ => 0,
+        };
+        Some(Self::from_parts(
+            universe.as_u64(),
+            acc_type.as_u64(),
+            instance,
+            w >> 1,
+            w & Self::Y_MASK,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use serde::{Deserialize, Serialize};
 
     use super::SteamId;
@@ -261,6 +434,47 @@ mod tests {
         assert_eq!(serialized, r#""76561198805665689""#);
     }
 
+    #[test]
+    fn serializes_by_format() {
+        let id = SteamId(76561198805665689);
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            r#""76561198805665689""#
+        );
+        assert_eq!(
+            serde_json::to_string(&SteamIdStr::from(id)).unwrap(),
+            r#""76561198805665689""#
+        );
+        assert_eq!(
+            serde_json::from_str::<SteamId>(r#""76561198805665689""#).unwrap(),
+            id
+        );
+        assert!(serde_json::from_str::<SteamId>(r#""7656x""#).is_err());
+        assert!(serde_json::from_str::<SteamId>("-1").is_err());
+
+        // json object keys are strings either way
+        let map = std::collections::HashMap::from([(id, 1)]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(json, r#"{"76561198805665689":1}"#);
+        assert_eq!(
+            serde_json::from_str::<std::collections::HashMap<SteamId, i32>>(&json).unwrap(),
+            map
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn serializes_compact() {
+        let id = SteamId(76561198805665689);
+        let encoded = bincode::serialize(&id).unwrap();
+        assert_eq!(encoded, id.0.to_le_bytes());
+        assert_eq!(bincode::serialize(&SteamIdStr::from(id)).unwrap(), encoded);
+        assert_eq!(
+            bincode::deserialize::<SteamIdStr>(&encoded).unwrap(),
+            id.into()
+        );
+    }
+
     #[test]
     fn deserialize_steam_id_str() {
         #[derive(Serialize, Deserialize)]
@@ -288,4 +502,88 @@ mod tests {
         let id = SteamId(76561198805665689);
         assert_eq!(id.to_steam_id_3().unwrap(), "[U:1:845399961]");
     }
+
+    #[test]
+    fn from_steam_id() {
+        let id = SteamId(76561198805665689);
+        assert_eq!(SteamId::from_steam_id("STEAM_1:1:422699980"), Some(id));
+        assert_eq!(SteamId::from_steam_id("STEAM_0:1:422699980"), Some(id));
+        assert_eq!(SteamId::from_steam_id("STEAM_1:2:422699980"), None);
+        assert_eq!(SteamId::from_steam_id("STEAM_1:1"), None);
+    }
+
+    #[test]
+    fn from_steam_id_3() {
+        let id = SteamId(76561198805665689);
+        assert_eq!(SteamId::from_steam_id_3("[U:1:845399961]"), Some(id));
+        assert_eq!(SteamId::from_steam_id_3("U:1:845399961"), Some(id));
+        assert_eq!(SteamId::from_steam_id_3("[X:1:845399961]"), None);
+        assert_eq!(
+            SteamId::from_steam_id_3("[g:1:4]"),
+            Some(SteamId(103582791429521412))
+        );
+    }
+
+    #[test]
+    fn iter_range() {
+        let ids = SteamId::iter_range(0..4).collect::<Vec<_>>();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], SteamId(76561197960265729));
+        assert!(ids.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+        assert_eq!(SteamId::iter_range(10..10).count(), 0);
+        assert_eq!(
+            SteamId::iter_range(u32::MAX - 1..u32::MAX).next_back(),
+            Some(SteamId::from_account_id(u32::MAX - 1))
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_individual() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        use crate::model::{AccountType, Universe};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let id = SteamId::random_individual(&mut rng);
+            assert_eq!(id.universe(), Some(Universe::Public));
+            assert_eq!(id.acc_type(), Some(AccountType::Individual));
+            assert_eq!(id.instance(), 1);
+            assert_ne!(id.account_id(), 0);
+            assert_eq!(SteamId::from_steam_id(&id.to_steam_id().unwrap()), Some(id));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn steam_id_round_trips(universe in 1_u64..=5, acc_nr in 0_u64..(1 << 31), y in 0_u64..2) {
+            let id = SteamId::from_parts(universe, 1, 1, acc_nr, y);
+            prop_assert_eq!(SteamId::from_steam_id(&id.to_steam_id().unwrap()), Some(id));
+            prop_assert_eq!(SteamId::from_steam_id_3(&id.to_steam_id_3().unwrap()), Some(id));
+        }
+
+        #[test]
+        fn from_parts_round_trips(universe in 0_u64..=5, acc_type in 0_u64..=10, instance in 0_u64..(1 << 20), acc_nr in 0_u64..(1 << 31), y in 0_u64..2) {
+            let id = SteamId::from_parts(universe, acc_type, instance, acc_nr, y);
+            prop_assert_eq!(id.universe().map(|u| u.as_u64()), Some(universe));
+            prop_assert_eq!(id.acc_type().map(|t| t.as_u64()), Some(acc_type));
+            prop_assert_eq!(id.instance(), instance);
+            prop_assert_eq!(id.acc_nr(), acc_nr);
+            prop_assert_eq!(id.y(), y);
+        }
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn round_trips_bincode() {
+        let ids = vec![SteamId(76561198805665689), SteamId(76561197992321696)];
+        let decoded = assert_bincode_round_trip!(ids, Vec<SteamId>);
+        assert_eq!(decoded, ids);
+
+        let ids = vec![SteamIdStr(76561198805665689), SteamIdStr(76561197992321696)];
+        let decoded = assert_bincode_round_trip!(ids, Vec<SteamIdStr>);
+        assert_eq!(decoded, ids);
+    }
 }
@@ -51,9 +51,84 @@ pub trait SteamIdQueryExt: Iterator {
         }
         buf
     }
+
+    /// Splits the iterator into query values of at most `chunk_size` ids each,
+    /// joined with `sep`, e.g. for endpoints with a
+    /// [`Endpoint::max_ids_per_request`](crate::Endpoint::max_ids_per_request).
+    ///
+    /// Every value is allocated like in [`SteamIdQueryExt::to_steam_id_string`].
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is `0`.
+    fn to_steam_id_chunks<T>(self, chunk_size: usize, sep: &str) -> SteamIdChunks<'_, Self>
+    where
+        Self: Sized + Iterator<Item = T>,
+        T: Borrow<SteamId>,
+    {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        SteamIdChunks {
+            iter: self.peekable(),
+            chunk_size,
+            sep,
+        }
+    }
+
+    /// Builds a json array of the ids as strings, like POST endpoints
+    /// expect them to not lose precision in javascript, e.g. `["76561197960287930"]`.
+    ///
+    /// Allocates like [`SteamIdQueryExt::to_steam_id_string`].
+    fn to_steam_id_json_array<T>(mut self) -> String
+    where
+        Self: Sized + Iterator<Item = T>,
+        T: Borrow<SteamId>,
+    {
+        let (lower, _) = self.size_hint();
+        let cap = 2 + lower * (SteamId::MAX_DIGITS_FOR_U64 + 2) + lower.saturating_sub(1);
+        let mut buf = String::with_capacity(cap);
+        buf.push('[');
+        if let Some(id) = self.next() {
+            write!(buf, "\"{}\"", id.borrow()).unwrap();
+            for id in self {
+                write!(buf, ",\"{}\"", id.borrow()).unwrap();
+            }
+        }
+        buf.push(']');
+        buf
+    }
 }
 impl<T: Borrow<SteamId>, I: Iterator<Item = T>> SteamIdQueryExt for I {}
 
+/// See [`SteamIdQueryExt::to_steam_id_chunks`]
+pub struct SteamIdChunks<'a, I: Iterator> {
+    iter: std::iter::Peekable<I>,
+    chunk_size: usize,
+    sep: &'a str,
+}
+
+impl<T: Borrow<SteamId>, I: Iterator<Item = T>> Iterator for SteamIdChunks<'_, I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.peek()?;
+        let chunk_size = self.chunk_size;
+        Some(
+            self.iter
+                .by_ref()
+                .take(chunk_size)
+                .to_steam_id_string(self.sep),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.div_ceil(self.chunk_size),
+            upper.map(|upper| upper.div_ceil(self.chunk_size)),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{SteamId, SteamIdQueryExt};
@@ -101,6 +176,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_steam_id_chunks_works() {
+        let ids = (0..5)
+            .map(|id| SteamId(76561197960265728 + id))
+            .collect::<Vec<_>>();
+        let mut chunks = ids.iter().to_steam_id_chunks(2, ",");
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+
+        let first = chunks.next().unwrap();
+        assert_eq!(first, "76561197960265728,76561197960265729");
+        assert_eq!(first.capacity(), SteamId::MAX_DIGITS_FOR_U64 * 2 + 1);
+        assert_eq!(chunks.next().unwrap().split(',').count(), 2);
+        assert_eq!(chunks.next().unwrap(), "76561197960265732");
+        assert_eq!(chunks.next(), None);
+
+        assert_eq!(
+            std::iter::empty::<SteamId>()
+                .to_steam_id_chunks(2, ",")
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn to_steam_id_json_array_works() {
+        let slice = &[SteamId(76561197960287930), SteamId(76561197985607672)];
+        let result = slice.iter().to_steam_id_json_array();
+        assert_eq!(result, r#"["76561197960287930","76561197985607672"]"#);
+        assert_eq!(
+            result.capacity(),
+            2 + (SteamId::MAX_DIGITS_FOR_U64 + 2) * 2 + 1
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<String>>(&result).unwrap(),
+            ["76561197960287930", "76561197985607672"]
+        );
+        assert_eq!(std::iter::empty::<SteamId>().to_steam_id_json_array(), "[]");
+    }
+
     #[test]
     fn to_steam_id_string_with_works() {
         // 76561197960287930 => ([U:1:22202], STEAM_1:0:11101)
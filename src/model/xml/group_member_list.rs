@@ -0,0 +1,142 @@
+//! Parse a page of the member list of a group, `/gid/{group_id}/memberslistxml/?xml=1`
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::fields::{parse_number, Fields};
+use super::FieldError;
+use crate::model::SteamId;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid xml ({0})")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("invalid text encoding ({0})")]
+    Encoding(#[from] quick_xml::encoding::EncodingError),
+
+    /// Steam answered with an error instead of a member list, e.g. for groups that don't exist
+    #[error("steam returned an error ({0})")]
+    Steam(String),
+
+    /// The member list is missing a required element or its text isn't valid
+    #[error("invalid member list ({0})")]
+    Field(#[from] FieldError),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+/// The `groupDetails` of a member list
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupDetails {
+    pub name: String,
+    /// The part after `steamcommunity.com/groups/`
+    pub url_name: String,
+    pub headline: String,
+    /// Usually HTML
+    pub summary: String,
+    pub avatar_full: String,
+    pub member_count: u32,
+    pub members_in_chat: u32,
+    pub members_in_game: u32,
+    pub members_online: u32,
+}
+
+/// A single page of the member list, with up to `1000` members
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupMemberPage {
+    pub group_id: SteamId,
+    pub details: GroupDetails,
+    /// `1`-based
+    pub current_page: u32,
+    pub total_pages: u32,
+    pub members: Vec<SteamId>,
+}
+
+/// Parse a page of the member list
+pub fn parse(xml: &str) -> Result<GroupMemberPage> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut fields = Fields::default();
+    let mut members = Vec::new();
+    let mut element: Option<Vec<u8>> = None;
+
+    loop {
+        let text = match reader.read_event()? {
+            Event::Start(start) => {
+                let name = start.local_name().as_ref().to_vec();
+                // `memberCount` is both in `groupDetails` and the list itself
+                fields.start(&name);
+                element = Some(name);
+                continue;
+            }
+            Event::End(_) => {
+                element = None;
+                continue;
+            }
+            Event::Text(text) => text.unescape()?.into_owned(),
+            Event::CData(cdata) => cdata.decode()?.into_owned(),
+            Event::Eof => break,
+            _ => continue,
+        };
+        let Some(name) = element.as_deref() else {
+            continue;
+        };
+        match name {
+            b"steamID64" => members.push(SteamId(parse_number("steamID64", text)?)),
+            b"error" => return Err(Error::Steam(text)),
+            name => fields.push(name, &text),
+        }
+    }
+
+    Ok(GroupMemberPage {
+        group_id: SteamId(fields.number("groupID64")?),
+        details: GroupDetails {
+            name: fields.text("groupName")?,
+            url_name: fields.text("groupURL")?,
+            headline: fields.text("headline").unwrap_or_default(),
+            summary: fields.text("summary").unwrap_or_default(),
+            avatar_full: fields.text("avatarFull").unwrap_or_default(),
+            member_count: fields.number("memberCount")?,
+            members_in_chat: fields.number("membersInChat")?,
+            members_in_game: fields.number("membersInGame")?,
+            members_online: fields.number("membersOnline")?,
+        },
+        current_page: fields.number("currentPage")?,
+        total_pages: fields.number("totalPages")?,
+        members,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Error};
+    use crate::model::xml::FieldError;
+    use crate::SteamId;
+
+    #[test]
+    fn parses() {
+        let xml = load_test_str!("group_member_list.xml");
+        let page = parse(&xml).unwrap();
+        assert_eq!(page.group_id, SteamId(103582791429521412));
+        assert_eq!(page.details.name, "Valve");
+        assert_eq!(page.details.headline, "Valve & friends");
+        assert_eq!(page.details.member_count, 5);
+        assert_eq!(page.details.members_online, 2);
+        assert_eq!((page.current_page, page.total_pages), (1, 2));
+        assert_eq!(page.members.len(), 3);
+        assert_eq!(page.members[0], SteamId(76561197960287930));
+    }
+
+    #[test]
+    fn fails_on_errors() {
+        let xml = "<response><error><![CDATA[The specified group could not be found.]]></error></response>";
+        assert!(matches!(parse(xml), Err(Error::Steam(msg)) if msg.contains("could not be found")));
+        assert!(matches!(
+            parse("<memberList></memberList>"),
+            Err(Error::Field(FieldError::Missing("groupID64")))
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(feature = "xml")]
+pub mod group_member_list;
+#[cfg(feature = "xml")]
+pub mod profile;
+#[cfg(feature = "xml")]
+pub mod rss;
+
+#[cfg(feature = "xml")]
+pub use fields::FieldError;
+
+#[cfg(feature = "xml")]
+mod fields {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use thiserror::Error;
+
+    /// A required element of a document is missing or its text isn't valid
+    #[derive(Debug, Clone, PartialEq, Eq, Error)]
+    pub enum FieldError {
+        #[error("missing `{0}`")]
+        Missing(&'static str),
+
+        #[error("invalid number in `{field}` ('{value}')")]
+        InvalidNumber { field: &'static str, value: String },
+
+        #[error("unknown value in `{field}` ('{value}')")]
+        Unknown { field: &'static str, value: String },
+    }
+
+    /// The text of the elements of a document, by local name, for flat documents
+    /// like the ones of `?xml=1` pages
+    #[derive(Default)]
+    pub(crate) struct Fields(HashMap<Vec<u8>, String>);
+
+    impl Fields {
+        /// Called for every start tag, an element that appears twice keeps the text of the last one
+        pub(crate) fn start(&mut self, name: &[u8]) {
+            self.0.insert(name.to_vec(), String::new());
+        }
+
+        /// Text and CDATA of an element can come in several parts
+        pub(crate) fn push(&mut self, name: &[u8], text: &str) {
+            self.0.entry(name.to_vec()).or_default().push_str(text);
+        }
+
+        pub(crate) fn text(&mut self, name: &'static str) -> Result<String, FieldError> {
+            self.0
+                .remove(name.as_bytes())
+                .ok_or(FieldError::Missing(name))
+        }
+
+        /// The text of `name`, [`None`] if it's missing or empty
+        pub(crate) fn optional(&mut self, name: &'static str) -> Option<String> {
+            self.text(name).ok().filter(|text| !text.is_empty())
+        }
+
+        pub(crate) fn number<T: FromStr>(&mut self, name: &'static str) -> Result<T, FieldError> {
+            let value = self.text(name)?;
+            parse_number(name, value)
+        }
+
+        /// The number in `name`, [`None`] if it's missing or empty
+        pub(crate) fn optional_number<T: FromStr>(
+            &mut self,
+            name: &'static str,
+        ) -> Result<Option<T>, FieldError> {
+            self.optional(name)
+                .map(|value| parse_number(name, value))
+                .transpose()
+        }
+    }
+
+    /// Numbers like hours are formatted with thousands separators, e.g. `1,234.5`
+    pub(crate) fn parse_number<T: FromStr>(
+        field: &'static str,
+        value: String,
+    ) -> Result<T, FieldError> {
+        value
+            .trim()
+            .replace(',', "")
+            .parse()
+            .map_err(|_| FieldError::InvalidNumber { field, value })
+    }
+}
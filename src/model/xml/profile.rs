@@ -0,0 +1,330 @@
+//! Parse the xml version of a community profile, `/profiles/{steam_id}/?xml=1`
+//!
+//! Some of it isn't available from the api, e.g. the date the account was created for
+//! private profiles, the most played games and the groups.
+
+use chrono::NaiveDate;
+use quick_xml::events::attributes::AttrError;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::fields::Fields;
+use super::FieldError;
+use crate::model::{CommunityVisibilityState, EconomyBan, SteamId};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid xml ({0})")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("invalid xml attribute ({0})")]
+    Attribute(#[from] AttrError),
+
+    #[error("invalid text encoding ({0})")]
+    Encoding(#[from] quick_xml::encoding::EncodingError),
+
+    /// Steam answered with an error instead of a profile, e.g. for profiles that don't exist
+    #[error("steam returned an error ({0})")]
+    Steam(String),
+
+    /// The profile is missing a required element or its text isn't valid
+    #[error("invalid profile ({0})")]
+    Field(#[from] FieldError),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+/// A game of the recent activity of a profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MostPlayedGame {
+    pub name: String,
+    /// `https://steamcommunity.com/app/{app_id}`
+    pub link: String,
+    pub hours_played_2wk: f64,
+    pub hours_on_record: f64,
+    /// The name of the stats page of the game, if it has one
+    pub stats_name: Option<String>,
+}
+
+impl MostPlayedGame {
+    /// The app id in [`MostPlayedGame::link`]
+    pub fn app_id(&self) -> Option<u32> {
+        self.link
+            .trim_end_matches('/')
+            .rsplit_once("/app/")
+            .and_then(|(_, id)| id.parse().ok())
+    }
+}
+
+/// A group the profile is a member of
+///
+/// Only some of the groups come with their details, usually the primary one.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProfileGroup {
+    pub group_id: SteamId,
+    pub primary: bool,
+    pub name: Option<String>,
+    /// The part after `steamcommunity.com/groups/`
+    pub url_name: Option<String>,
+    pub member_count: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProfileXml {
+    pub steam_id: SteamId,
+    pub persona_name: String,
+    /// `online`, `offline`, `in-game`, ...
+    pub online_state: String,
+    /// The status shown below the name, may contain html
+    pub state_message: String,
+    pub visibility: CommunityVisibilityState,
+    pub avatar_full: String,
+    pub vac_banned: bool,
+    pub trade_ban: EconomyBan,
+    pub limited_account: bool,
+    /// The part after `steamcommunity.com/id/`
+    pub custom_url: Option<String>,
+    /// The name of the game the profile is playing right now
+    pub in_game: Option<String>,
+    /// In the language of the request, see [`ProfileXml::member_since_date`]
+    pub member_since: Option<String>,
+    pub hours_played_2wk: Option<f64>,
+    pub headline: Option<String>,
+    pub location: Option<String>,
+    pub real_name: Option<String>,
+    /// May contain html
+    pub summary: Option<String>,
+    /// Empty for profiles that aren't public
+    pub most_played_games: Vec<MostPlayedGame>,
+    /// Empty for profiles that aren't public
+    pub groups: Vec<ProfileGroup>,
+}
+
+impl ProfileXml {
+    /// [`ProfileXml::member_since`] for english pages, e.g. `March 19, 2013`
+    pub fn member_since_date(&self) -> Option<NaiveDate> {
+        let date = self.member_since.as_deref()?;
+        NaiveDate::parse_from_str(date.trim(), "%B %d, %Y").ok()
+    }
+
+    /// Clear the personal fields, i.e. the real name, the location and the avatar
+    ///
+    /// Done for every profile, if the client was built with
+    /// [`ClientBuilder::minimal_fields`](crate::ClientBuilder::minimal_fields)
+    pub fn minimize(&mut self) {
+        self.real_name = None;
+        self.location = None;
+        self.avatar_full.clear();
+    }
+}
+
+/// The element whose children are being read
+enum Item {
+    MostPlayedGame,
+    Group { primary: bool },
+}
+
+/// Where the text of the element on top of `stack` goes
+fn target<'a>(
+    stack: &[Vec<u8>],
+    item: &'a mut Option<(Item, Fields)>,
+    profile: &'a mut Fields,
+    in_game: &'a mut Fields,
+) -> Option<&'a mut Fields> {
+    if let Some((_, fields)) = item {
+        return Some(fields);
+    }
+    match stack {
+        [_, _] => Some(profile),
+        [_, parent, _] if parent == b"inGameInfo" => Some(in_game),
+        _ => None,
+    }
+}
+
+fn build_game(mut fields: Fields) -> Result<MostPlayedGame> {
+    Ok(MostPlayedGame {
+        name: fields.text("gameName")?,
+        link: fields.text("gameLink")?,
+        hours_played_2wk: fields.optional_number("hoursPlayed")?.unwrap_or_default(),
+        hours_on_record: fields.optional_number("hoursOnRecord")?.unwrap_or_default(),
+        stats_name: fields.optional("statsName"),
+    })
+}
+
+fn build_group(mut fields: Fields, primary: bool) -> Result<ProfileGroup> {
+    Ok(ProfileGroup {
+        group_id: SteamId(fields.number("groupID64")?),
+        primary,
+        name: fields.optional("groupName"),
+        url_name: fields.optional("groupURL"),
+        member_count: fields.optional_number("memberCount")?,
+    })
+}
+
+/// `0` or `1`
+fn flag(fields: &mut Fields, name: &'static str) -> Result<bool> {
+    Ok(fields.optional_number::<u8>(name)?.unwrap_or_default() != 0)
+}
+
+/// Parse a profile
+pub fn parse(xml: &str) -> Result<ProfileXml> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut stack: Vec<Vec<u8>> = Vec::new();
+    let mut profile = Fields::default();
+    let mut in_game = Fields::default();
+    let mut item: Option<(Item, Fields)> = None;
+    let mut games = Vec::new();
+    let mut groups = Vec::new();
+
+    loop {
+        let text = match reader.read_event()? {
+            Event::Start(start) => {
+                let name = start.local_name().as_ref().to_vec();
+                match name.as_slice() {
+                    b"mostPlayedGame" => item = Some((Item::MostPlayedGame, Fields::default())),
+                    b"group" => {
+                        let primary = start
+                            .try_get_attribute("isPrimary")?
+                            .is_some_and(|attr| attr.value.as_ref() == b"1");
+                        item = Some((Item::Group { primary }, Fields::default()));
+                    }
+                    _ => {}
+                }
+                stack.push(name);
+                if let Some(fields) = target(&stack, &mut item, &mut profile, &mut in_game) {
+                    fields.start(stack.last().expect("just pushed"));
+                }
+                continue;
+            }
+            Event::End(end) => {
+                stack.pop();
+                if matches!(end.local_name().as_ref(), b"mostPlayedGame" | b"group") {
+                    match item.take() {
+                        Some((Item::MostPlayedGame, fields)) => games.push(build_game(fields)?),
+                        Some((Item::Group { primary }, fields)) => {
+                            groups.push(build_group(fields, primary)?);
+                        }
+                        None => {}
+                    }
+                }
+                continue;
+            }
+            Event::Text(text) => text.unescape()?.into_owned(),
+            Event::CData(cdata) => cdata.decode()?.into_owned(),
+            Event::Eof => break,
+            _ => continue,
+        };
+        if stack.last().is_some_and(|name| name == b"error") {
+            return Err(Error::Steam(text));
+        }
+        if let Some(fields) = target(&stack, &mut item, &mut profile, &mut in_game) {
+            fields.push(stack.last().expect("text is inside an element"), &text);
+        }
+    }
+
+    let visibility = profile.number::<i64>("visibilityState")?;
+    let visibility =
+        CommunityVisibilityState::try_from(visibility).map_err(|_| FieldError::Unknown {
+            field: "visibilityState",
+            value: visibility.to_string(),
+        })?;
+    let trade_ban = profile.text("tradeBanState")?;
+    let trade_ban =
+        EconomyBan::try_from(trade_ban.to_ascii_lowercase().as_str()).map_err(|_| {
+            FieldError::Unknown {
+                field: "tradeBanState",
+                value: trade_ban.clone(),
+            }
+        })?;
+
+    Ok(ProfileXml {
+        steam_id: SteamId(profile.number("steamID64")?),
+        persona_name: profile.text("steamID")?,
+        online_state: profile.text("onlineState")?,
+        state_message: profile.text("stateMessage").unwrap_or_default(),
+        visibility,
+        avatar_full: profile.text("avatarFull").unwrap_or_default(),
+        vac_banned: flag(&mut profile, "vacBanned")?,
+        trade_ban,
+        limited_account: flag(&mut profile, "isLimitedAccount")?,
+        custom_url: profile.optional("customURL"),
+        in_game: in_game.optional("gameName"),
+        member_since: profile.optional("memberSince"),
+        hours_played_2wk: profile.optional_number("hoursPlayed2Wk")?,
+        headline: profile.optional("headline"),
+        location: profile.optional("location"),
+        real_name: profile.optional("realname"),
+        summary: profile.optional("summary"),
+        most_played_games: games,
+        groups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{parse, Error};
+    use crate::{CommunityVisibilityState, EconomyBan, SteamId};
+
+    #[test]
+    fn parses_public() {
+        let xml = load_test_str!("profile.xml");
+        let profile = parse(&xml).unwrap();
+        assert_eq!(profile.steam_id, SteamId(76561198089612262));
+        assert_eq!(profile.persona_name, "Sauce & Co");
+        assert_eq!(profile.visibility, CommunityVisibilityState::Public);
+        assert!(profile.vac_banned);
+        assert_eq!(profile.trade_ban, EconomyBan::None);
+        assert_eq!(profile.custom_url.as_deref(), Some("sauce"));
+        assert_eq!(profile.in_game.as_deref(), Some("Team Fortress 2"));
+        assert_eq!(profile.headline, None);
+        assert_eq!(
+            profile.member_since_date(),
+            NaiveDate::from_ymd_opt(2013, 3, 19)
+        );
+        assert_eq!(profile.hours_played_2wk, Some(12.4));
+
+        let games = &profile.most_played_games;
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].app_id(), Some(440));
+        assert_eq!(games[0].hours_on_record, 1482.0);
+        assert_eq!(games[1].stats_name, None);
+
+        assert_eq!(profile.groups.len(), 2);
+        assert!(profile.groups[0].primary);
+        assert_eq!(profile.groups[0].name.as_deref(), Some("Valve"));
+        assert_eq!(profile.groups[0].member_count, Some(5));
+        assert!(!profile.groups[1].primary);
+        assert_eq!(profile.groups[1].group_id, SteamId(103582791434672565));
+        assert_eq!(profile.groups[1].name, None);
+    }
+
+    #[test]
+    fn parses_private() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><profile>
+            <steamID64>76561198196615742</steamID64>
+            <steamID><![CDATA[private]]></steamID>
+            <onlineState>offline</onlineState>
+            <stateMessage><![CDATA[Offline]]></stateMessage>
+            <privacyState>private</privacyState>
+            <visibilityState>1</visibilityState>
+            <avatarFull><![CDATA[https://avatars.fastly.steamstatic.com/full.jpg]]></avatarFull>
+            <vacBanned>0</vacBanned>
+            <tradeBanState>Probation</tradeBanState>
+            <isLimitedAccount>1</isLimitedAccount>
+        </profile>"#;
+        let profile = parse(xml).unwrap();
+        assert_eq!(profile.visibility, CommunityVisibilityState::Private);
+        assert_eq!(profile.trade_ban, EconomyBan::Probation);
+        assert!(profile.limited_account);
+        assert_eq!(profile.member_since, None);
+        assert!(profile.most_played_games.is_empty());
+
+        let xml = "<response><error><![CDATA[The specified profile could not be found.]]></error></response>";
+        assert!(matches!(parse(xml), Err(Error::Steam(_))));
+    }
+}
@@ -0,0 +1,230 @@
+//! Parse RSS and Atom feeds, e.g. the announcements of a Steam group
+
+use chrono::DateTime;
+use quick_xml::events::attributes::AttrError;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::SteamTime;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid xml ({0})")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("invalid xml attribute ({0})")]
+    Attribute(#[from] AttrError),
+
+    #[error("invalid text encoding ({0})")]
+    Encoding(#[from] quick_xml::encoding::EncodingError),
+
+    /// An item is missing a required element
+    #[error("item is missing `{0}`")]
+    MissingField(&'static str),
+
+    /// The publication date is neither RFC 2822 (RSS) nor RFC 3339 (Atom)
+    #[error("invalid date '{0}'")]
+    InvalidDate(String),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeedEntry {
+    pub title: String,
+    pub author: Option<String>,
+    pub published: SteamTime,
+    pub link: String,
+    /// The description (RSS) or content (Atom) of the entry, usually HTML
+    pub body_html: String,
+}
+
+/// The element of an entry the current text belongs to
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Author,
+    Published,
+    Updated,
+    Link,
+    Summary,
+    Content,
+}
+
+impl Field {
+    fn from_local_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"title" => Some(Field::Title),
+            b"author" | b"name" | b"creator" => Some(Field::Author),
+            b"pubDate" | b"published" => Some(Field::Published),
+            b"updated" => Some(Field::Updated),
+            b"link" => Some(Field::Link),
+            b"description" | b"summary" => Some(Field::Summary),
+            b"content" => Some(Field::Content),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct EntryBuilder {
+    title: String,
+    author: String,
+    published: String,
+    updated: String,
+    link: String,
+    summary: String,
+    content: String,
+}
+
+impl EntryBuilder {
+    fn push(&mut self, field: Field, text: &str) {
+        let buf = match field {
+            Field::Title => &mut self.title,
+            Field::Author => &mut self.author,
+            Field::Published => &mut self.published,
+            Field::Updated => &mut self.updated,
+            Field::Link => &mut self.link,
+            Field::Summary => &mut self.summary,
+            Field::Content => &mut self.content,
+        };
+        buf.push_str(text);
+    }
+
+    /// Atom links are empty elements with the url in `href`
+    fn link_href(&mut self, link: &BytesStart) -> Result<()> {
+        if !self.link.is_empty() {
+            return Ok(());
+        }
+        if let Some(href) = link.try_get_attribute("href")? {
+            self.link = href.unescape_value()?.into_owned();
+        }
+        Ok(())
+    }
+
+    fn build(self) -> Result<FeedEntry> {
+        let date = if self.published.is_empty() {
+            self.updated
+        } else {
+            self.published
+        };
+        if date.is_empty() {
+            return Err(Error::MissingField("pubDate"));
+        }
+        let published = DateTime::parse_from_rfc2822(date.trim())
+            .or_else(|_| DateTime::parse_from_rfc3339(date.trim()))
+            .map_err(|_| Error::InvalidDate(date.clone()))?;
+
+        if self.title.is_empty() {
+            return Err(Error::MissingField("title"));
+        }
+
+        Ok(FeedEntry {
+            title: self.title,
+            author: Some(self.author).filter(|author| !author.is_empty()),
+            published: published.into(),
+            link: self.link,
+            body_html: if self.content.is_empty() {
+                self.summary
+            } else {
+                self.content
+            },
+        })
+    }
+}
+
+/// Parse the `item`s of an RSS feed or the `entry`s of an Atom feed
+pub fn parse(xml: &str) -> Result<Vec<FeedEntry>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut entries = Vec::new();
+    let mut entry: Option<EntryBuilder> = None;
+    let mut field: Option<Field> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => match start.local_name().as_ref() {
+                b"item" | b"entry" => entry = Some(EntryBuilder::default()),
+                name => {
+                    field = Field::from_local_name(name);
+                    if let (Some(entry), Some(Field::Link)) = (entry.as_mut(), field) {
+                        entry.link_href(&start)?;
+                    }
+                }
+            },
+            Event::Empty(empty) => {
+                if let (Some(entry), b"link") = (entry.as_mut(), empty.local_name().as_ref()) {
+                    entry.link_href(&empty)?;
+                }
+            }
+            Event::Text(text) => {
+                if let (Some(entry), Some(field)) = (entry.as_mut(), field) {
+                    entry.push(field, &text.unescape()?);
+                }
+            }
+            Event::CData(cdata) => {
+                if let (Some(entry), Some(field)) = (entry.as_mut(), field) {
+                    entry.push(field, &cdata.decode()?);
+                }
+            }
+            Event::End(end) => {
+                field = None;
+                if matches!(end.local_name().as_ref(), b"item" | b"entry") {
+                    if let Some(entry) = entry.take() {
+                        entries.push(entry.build()?);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_rss() {
+        let xml = load_test_str!("group_rss.xml");
+        let entries = parse(&xml).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let fst = &entries[0];
+        assert_eq!(fst.title, "Steam Next Fest: October 2023 Edition");
+        assert_eq!(fst.author.as_deref(), Some("Valve"));
+        assert_eq!(fst.published.timestamp(), 1696870800);
+        assert!(fst.body_html.starts_with("Steam Next Fest is back! <br>"));
+        assert!(fst.link.ends_with("/detail/3664556036349497155"));
+
+        let snd = &entries[1];
+        assert_eq!(snd.title, "Steam Summer Sale & Events");
+        assert_eq!(snd.author, None);
+    }
+
+    #[test]
+    fn parses_atom() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+                <title>Feed</title>
+                <entry>
+                    <title>Patch notes</title>
+                    <link href="https://example.com/1"/>
+                    <author><name>Dev</name></author>
+                    <updated>2023-10-09T17:00:00Z</updated>
+                    <content type="html">&lt;p&gt;Fixed bugs&lt;/p&gt;</content>
+                </entry>
+            </feed>"#;
+        let entries = parse(xml).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].link, "https://example.com/1");
+        assert_eq!(entries[0].author.as_deref(), Some("Dev"));
+        assert_eq!(entries[0].body_html, "<p>Fixed bugs</p>");
+        assert_eq!(entries[0].published.timestamp(), 1696870800);
+    }
+}
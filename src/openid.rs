@@ -0,0 +1,401 @@
+//! Log users in with their Steam account, using Steam as an OpenID 2.0 provider.
+//!
+//! 1. Redirect the user to [`login_url`], Steam asks them to sign in.
+//! 2. Steam redirects back to `return_to` with the assertion in the query.
+//! 3. [`Client::verify_openid`] checks the assertion with Steam and returns the [`SteamId`]
+//!    of the user.
+//!
+//! ```no_run
+//! # async fn run(client: steam_api_concurrent::Client, query: &str) {
+//! use steam_api_concurrent::openid;
+//!
+//! const RETURN_TO: &str = "https://example.com/auth/steam";
+//!
+//! // redirect the user here
+//! let url = openid::login_url("https://example.com/", RETURN_TO);
+//!
+//! // and verify the query of the request to `RETURN_TO`
+//! match client.verify_openid(query, RETURN_TO).await {
+//!     Ok(id) => println!("logged in as {}", id),
+//!     Err(err) => println!("login failed: {}", err),
+//! }
+//! # }
+//! ```
+//!
+//! Nothing here stops a verified query from being replayed. Keep the
+//! [`Assertion::response_nonce`] of every login until its timestamp is too old to be accepted
+//! and reject assertions with a nonce that was seen before.
+
+use thiserror::Error;
+
+use crate::{Client, Endpoint, SteamId};
+
+/// The url of Steam's OpenID provider, the users are sent there
+pub const PROVIDER: &str = Endpoint::OpenIdLogin.url();
+
+const NS: &str = "http://specs.openid.net/auth/2.0";
+const IDENTIFIER_SELECT: &str = "http://specs.openid.net/auth/2.0/identifier_select";
+/// The claimed ids are this followed by the 64-bit id
+const CLAIMED_ID_PREFIX: &str = "https://steamcommunity.com/openid/id/";
+
+/// The fields that have to be signed by Steam, or they could be swapped out
+const SIGNED_FIELDS: [&str; 5] = [
+    "op_endpoint",
+    "claimed_id",
+    "identity",
+    "return_to",
+    "response_nonce",
+];
+
+#[derive(Debug, Error)]
+pub enum OpenIdError {
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("invalid query ({0})")]
+    Query(#[from] serde_urlencoded::de::Error),
+
+    #[error("missing `openid.{0}`")]
+    MissingField(&'static str),
+
+    /// An `openid.*` field is in the query more than once, which copy Steam checks is unknown
+    #[error("duplicate `{0}`")]
+    DuplicateField(String),
+
+    /// The mode isn't `id_res`, e.g. `cancel` if the user didn't sign in
+    #[error("unexpected mode '{0}'")]
+    Mode(String),
+
+    /// A field doesn't have the value of a login for this site, e.g. a `return_to` of another site
+    #[error("unexpected `openid.{field}` '{value}'")]
+    Unexpected { field: &'static str, value: String },
+
+    #[error("`openid.{0}` isn't signed")]
+    Unsigned(&'static str),
+
+    #[error("'{0}' is not a steam id")]
+    ClaimedId(String),
+
+    /// Steam didn't confirm the assertion, it's forged or was already verified
+    #[error("steam rejected the assertion")]
+    Rejected,
+}
+type Result<T> = std::result::Result<T, OpenIdError>;
+
+/// The url to send users to, Steam redirects them back to `return_to` after they signed in.
+///
+/// `realm` is shown to the user and `return_to` has to be below it,
+/// e.g. `https://example.com/` and `https://example.com/auth/steam`.
+pub fn login_url(realm: &str, return_to: &str) -> String {
+    let query = [
+        ("openid.ns", NS),
+        ("openid.mode", "checkid_setup"),
+        ("openid.return_to", return_to),
+        ("openid.realm", realm),
+        ("openid.identity", IDENTIFIER_SELECT),
+        ("openid.claimed_id", IDENTIFIER_SELECT),
+    ];
+    let query = serde_urlencoded::to_string(query).expect("strings are always encodable");
+    format!("{}?{}", PROVIDER, query)
+}
+
+/// An assertion Steam redirected a user back with, not verified yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertion {
+    /// The `openid.*` fields of the query
+    params: Vec<(String, String)>,
+    steam_id: SteamId,
+}
+
+/// The url without the query and fragment
+fn strip_query(url: &str) -> &str {
+    url.split(['?', '#']).next().unwrap_or_default()
+}
+
+impl Assertion {
+    /// Check the fields of `query`, the query of the request to `return_to`.
+    ///
+    /// `return_to` has to be the same as for [`login_url`], except for its query.
+    pub fn from_query(query: &str, return_to: &str) -> Result<Self> {
+        let params = serde_urlencoded::from_str::<Vec<(String, String)>>(query)?
+            .into_iter()
+            .filter(|(key, _)| key.starts_with("openid."))
+            .collect::<Vec<_>>();
+        for (i, (key, _)) in params.iter().enumerate() {
+            if params[..i].iter().any(|(other, _)| other == key) {
+                return Err(OpenIdError::DuplicateField(key.clone()));
+            }
+        }
+        let field = |name: &'static str| {
+            params
+                .iter()
+                .find(|(key, _)| key.strip_prefix("openid.") == Some(name))
+                .map(|(_, value)| value.as_str())
+                .ok_or(OpenIdError::MissingField(name))
+        };
+        let expect = |name: &'static str, expected: &str| {
+            let value = field(name)?;
+            if value != expected {
+                return Err(OpenIdError::Unexpected {
+                    field: name,
+                    value: value.to_string(),
+                });
+            }
+            Ok(())
+        };
+
+        let mode = field("mode")?;
+        if mode != "id_res" {
+            return Err(OpenIdError::Mode(mode.to_string()));
+        }
+        expect("ns", NS)?;
+        expect("op_endpoint", PROVIDER)?;
+        let returned_to = field("return_to")?;
+        if strip_query(returned_to) != strip_query(return_to) {
+            return Err(OpenIdError::Unexpected {
+                field: "return_to",
+                value: returned_to.to_string(),
+            });
+        }
+        let claimed_id = field("claimed_id")?;
+        expect("identity", claimed_id)?;
+
+        let signed = field("signed")?.split(',').collect::<Vec<_>>();
+        if let Some(unsigned) = SIGNED_FIELDS
+            .into_iter()
+            .find(|name| !signed.contains(name))
+        {
+            return Err(OpenIdError::Unsigned(unsigned));
+        }
+
+        field("response_nonce")?;
+        let steam_id = claimed_id
+            .strip_prefix(CLAIMED_ID_PREFIX)
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| OpenIdError::ClaimedId(claimed_id.to_string()))?;
+
+        Ok(Self { params, steam_id })
+    }
+
+    /// The id the assertion claims, only trustworthy after [`Client::verify_assertion`]
+    pub const fn claimed_steam_id(&self) -> SteamId {
+        self.steam_id
+    }
+
+    /// `openid.response_nonce`, a timestamp followed by a unique part, e.g.
+    /// `2023-10-09T17:00:00ZabcdEFGH`
+    ///
+    /// Apps have to remember the nonces of verified logins and reject them when they come
+    /// again, or a query that leaked can be used to log in again.
+    pub fn response_nonce(&self) -> &str {
+        self.params
+            .iter()
+            .find(|(key, _)| key == "openid.response_nonce")
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    }
+}
+
+impl Client {
+    /// Ask Steam whether it issued `assertion`, the [`SteamId`] of the user if so
+    ///
+    /// Exactly the fields checked by [`Assertion::from_query`] are sent to Steam.
+    /// Replays aren't detected, see [`Assertion::response_nonce`].
+    ///
+    /// Steam answers for an assertion only once, so the check isn't retried, a retry would
+    /// turn a valid login into [`OpenIdError::Rejected`].
+    ///
+    /// Uses [`Endpoint::OpenIdLogin`]
+    pub async fn verify_assertion(&self, assertion: &Assertion) -> Result<SteamId> {
+        let form = assertion
+            .params
+            .iter()
+            .map(|(key, value)| match key.as_str() {
+                "openid.mode" => (key.as_str(), "check_authentication"),
+                _ => (key.as_str(), value.as_str()),
+            })
+            .collect::<Vec<_>>();
+        let body = self
+            .post_form_text_once(Endpoint::OpenIdLogin, &form)
+            .await?;
+
+        // key-value form, one `key:value` per line
+        let valid = body
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .any(|(key, value)| key.trim() == "is_valid" && value.trim() == "true");
+        if !valid {
+            return Err(OpenIdError::Rejected);
+        }
+        Ok(assertion.steam_id)
+    }
+
+    /// [`Assertion::from_query`] and [`Client::verify_assertion`] in one go
+    ///
+    /// Use the two steps to remember the [`Assertion::response_nonce`] of logins.
+    ///
+    /// Uses [`Endpoint::OpenIdLogin`]
+    pub async fn verify_openid(&self, query: &str, return_to: &str) -> Result<SteamId> {
+        let assertion = Assertion::from_query(query, return_to)?;
+        self.verify_assertion(&assertion).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{login_url, Assertion, OpenIdError};
+    use crate::test_util::openid_query as query;
+    use crate::SteamId;
+
+    const RETURN_TO: &str = "https://example.com/auth/steam";
+    const ID: SteamId = SteamId(76561197960287930);
+
+    #[test]
+    fn builds_login_urls() {
+        let url = login_url("https://example.com/", RETURN_TO);
+        assert!(url.starts_with("https://steamcommunity.com/openid/login?openid.ns="));
+        assert!(url.contains("&openid.mode=checkid_setup&"));
+        assert!(url.contains("&openid.return_to=https%3A%2F%2Fexample.com%2Fauth%2Fsteam&"));
+    }
+
+    #[test]
+    fn checks_assertions() {
+        let assertion = Assertion::from_query(&query(&[]), RETURN_TO).unwrap();
+        assert_eq!(assertion.claimed_steam_id(), ID);
+        assert_eq!(assertion.response_nonce(), "2023-10-09T17:00:00ZabcdEFGH");
+        // extra parameters of the app in `return_to` are fine
+        let with_state = format!("{}?state=abc", RETURN_TO);
+        let assertion = query(&[("openid.return_to", &with_state), ("state", "abc")]);
+        assert!(Assertion::from_query(&assertion, RETURN_TO).is_ok());
+        assert!(Assertion::from_query(&assertion, &with_state).is_ok());
+
+        let check =
+            |overrides: &[(&str, &str)]| Assertion::from_query(&query(overrides), RETURN_TO);
+        assert!(matches!(
+            check(&[("openid.mode", "cancel")]),
+            Err(OpenIdError::Mode(mode)) if mode == "cancel"
+        ));
+        assert!(matches!(
+            check(&[("openid.return_to", "https://evil.example.com/auth/steam")]),
+            Err(OpenIdError::Unexpected {
+                field: "return_to",
+                ..
+            })
+        ));
+        assert!(matches!(
+            check(&[(
+                "openid.op_endpoint",
+                "https://evil.example.com/openid/login"
+            )]),
+            Err(OpenIdError::Unexpected {
+                field: "op_endpoint",
+                ..
+            })
+        ));
+        assert!(matches!(
+            check(&[("openid.identity", "https://steamcommunity.com/openid/id/1")]),
+            Err(OpenIdError::Unexpected {
+                field: "identity",
+                ..
+            })
+        ));
+        assert!(matches!(
+            check(&[(
+                "openid.signed",
+                "signed,op_endpoint,identity,return_to,response_nonce"
+            )]),
+            Err(OpenIdError::Unsigned("claimed_id"))
+        ));
+        assert!(matches!(
+            check(&[
+                ("openid.claimed_id", "https://example.com/id/1"),
+                ("openid.identity", "https://example.com/id/1"),
+            ]),
+            Err(OpenIdError::ClaimedId(_))
+        ));
+        assert!(matches!(
+            Assertion::from_query("", RETURN_TO),
+            Err(OpenIdError::MissingField("mode"))
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_fields() {
+        // the ids of a victim in front of a valid assertion of the attacker
+        let victim = "https://steamcommunity.com/openid/id/76561198196615742";
+        let attacker = query(&[]);
+        let forged = format!(
+            "{}&{}",
+            serde_urlencoded::to_string([
+                ("openid.claimed_id", victim),
+                ("openid.identity", victim),
+            ])
+            .unwrap(),
+            attacker
+        );
+        assert!(matches!(
+            Assertion::from_query(&forged, RETURN_TO),
+            Err(OpenIdError::DuplicateField(key)) if key == "openid.claimed_id"
+        ));
+
+        // parameters of the app can repeat
+        let with_app_params = format!("{}&tag=a&tag=b", attacker);
+        assert!(Assertion::from_query(&with_app_params, RETURN_TO).is_ok());
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn verifies_with_steam() {
+        use wiremock::matchers::body_string_contains;
+        use wiremock::{Mock, ResponseTemplate};
+
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        assert_eq!(
+            client.verify_openid(&query(&[]), RETURN_TO).await.unwrap(),
+            ID
+        );
+        let requests = steam.server().received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests.last().unwrap().body).into_owned();
+        assert!(body.contains("openid.mode=check_authentication"));
+        assert!(body.contains("openid.sig=c2lnbmF0dXJl"));
+        let sent = serde_urlencoded::from_str::<Vec<(String, String)>>(&body).unwrap();
+        assert_eq!(sent.len(), 10);
+        assert!(sent.iter().all(|(key, _)| key.starts_with("openid.")));
+
+        Mock::given(body_string_contains("already_used"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("ns:http://specs.openid.net/auth/2.0\nis_valid:false\n"),
+            )
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        let replayed = query(&[("openid.response_nonce", "already_used")]);
+        assert!(matches!(
+            client.verify_openid(&replayed, RETURN_TO).await,
+            Err(OpenIdError::Rejected)
+        ));
+        assert_eq!(steam.received(Endpoint::OpenIdLogin).await, 2);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn checks_with_steam_once() {
+        use crate::testing::MockSteam;
+        use crate::Endpoint;
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::OpenIdLogin, 500, 4).await;
+        let client = steam.client().await;
+        assert!(matches!(
+            client.verify_openid(&query(&[]), RETURN_TO).await,
+            Err(OpenIdError::Reqwest(_))
+        ));
+        assert_eq!(steam.received(Endpoint::OpenIdLogin).await, 1);
+        assert_eq!(client.total_retries(), 0);
+    }
+}
@@ -0,0 +1,323 @@
+//! Encode and decode protobuf messages for the service methods of the api.
+//!
+//! Newer methods, e.g. the ones of `IAuthenticationService`, take their parameters as a
+//! protobuf message in `input_protobuf_encoded` and answer with one. There's no code
+//! generation, messages implement [`Message`] by hand with an [`Encoder`] and a [`Decoder`],
+//! which only know the wire format.
+//!
+//! ```
+//! use steam_api_concurrent::protobuf::{Decoder, Encoder, Message, ProtobufError};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Request {
+//!     account_name: String,
+//! }
+//!
+//! impl Message for Request {
+//!     fn encode(&self, encoder: &mut Encoder) {
+//!         encoder.string(1, &self.account_name);
+//!     }
+//!     fn decode(bytes: &[u8]) -> Result<Self, ProtobufError> {
+//!         let mut account_name = String::new();
+//!         for field in Decoder::new(bytes) {
+//!             let (number, value) = field?;
+//!             if number == 1 {
+//!                 account_name = value.as_str()?.to_string();
+//!             }
+//!         }
+//!         Ok(Self { account_name })
+//!     }
+//! }
+//!
+//! let request = Request { account_name: "gaben".to_string() };
+//! assert_eq!(Request::decode(&request.to_bytes()).unwrap(), request);
+//! ```
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ProtobufError {
+    #[error("message ended in the middle of a field")]
+    UnexpectedEnd,
+
+    #[error("varint is longer than 64 bits")]
+    VarintOverflow,
+
+    /// Groups and unknown wire types can't be skipped
+    #[error("unsupported wire type {0}")]
+    WireType(u64),
+
+    /// A field has another wire type than the message expects
+    #[error("field has the wrong wire type")]
+    FieldType,
+
+    #[error("string field is not valid utf-8")]
+    Utf8(#[from] std::str::Utf8Error),
+}
+type Result<T> = std::result::Result<T, ProtobufError>;
+
+/// A message that can be sent to or received from a service method
+pub trait Message: Sized {
+    fn encode(&self, encoder: &mut Encoder);
+
+    /// Unknown fields should be skipped, Valve adds fields to messages over time
+    fn decode(bytes: &[u8]) -> Result<Self>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        self.encode(&mut encoder);
+        encoder.into_bytes()
+    }
+}
+
+/// Messages without fields, for methods that take no parameters or answer with nothing
+impl Message for () {
+    fn encode(&self, _encoder: &mut Encoder) {}
+    fn decode(_bytes: &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+const VARINT: u64 = 0;
+const FIXED64: u64 = 1;
+const LENGTH_DELIMITED: u64 = 2;
+const FIXED32: u64 = 5;
+
+/// Writes the fields of a message
+#[derive(Debug, Default, Clone)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u64) {
+        self.varint(u64::from(field) << 3 | wire_type);
+    }
+
+    /// `uint32`, `uint64`, `int64` and enums
+    pub fn uint64(&mut self, field: u32, value: u64) {
+        self.key(field, VARINT);
+        self.varint(value);
+    }
+
+    /// `int32`, negative values take ten bytes like in every other encoder
+    pub fn int32(&mut self, field: u32, value: i32) {
+        self.uint64(field, i64::from(value) as u64);
+    }
+
+    pub fn bool(&mut self, field: u32, value: bool) {
+        self.uint64(field, u64::from(value));
+    }
+
+    /// `fixed64`, which Steam uses for steam ids
+    pub fn fixed64(&mut self, field: u32, value: u64) {
+        self.key(field, FIXED64);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn fixed32(&mut self, field: u32, value: u32) {
+        self.key(field, FIXED32);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.key(field, LENGTH_DELIMITED);
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    pub fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    /// An embedded message
+    pub fn message(&mut self, field: u32, value: &impl Message) {
+        self.bytes(field, &value.to_bytes());
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// The value of a field as it's on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    /// Strings, bytes, embedded messages and packed repeated fields
+    LengthDelimited(&'a [u8]),
+}
+
+impl<'a> FieldValue<'a> {
+    /// Varints and fixed integers
+    pub const fn as_u64(self) -> Result<u64> {
+        match self {
+            FieldValue::Varint(value) | FieldValue::Fixed64(value) => Ok(value),
+            FieldValue::Fixed32(value) => Ok(value as u64),
+            FieldValue::LengthDelimited(_) => Err(ProtobufError::FieldType),
+        }
+    }
+
+    pub const fn as_i32(self) -> Result<i32> {
+        match self.as_u64() {
+            Ok(value) => Ok(value as i32),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub const fn as_bool(self) -> Result<bool> {
+        match self.as_u64() {
+            Ok(value) => Ok(value != 0),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub const fn as_bytes(self) -> Result<&'a [u8]> {
+        match self {
+            FieldValue::LengthDelimited(bytes) => Ok(bytes),
+            _ => Err(ProtobufError::FieldType),
+        }
+    }
+
+    pub fn as_str(self) -> Result<&'a str> {
+        Ok(std::str::from_utf8(self.as_bytes()?)?)
+    }
+
+    /// Decode an embedded message
+    pub fn as_message<M: Message>(self) -> Result<M> {
+        M::decode(self.as_bytes()?)
+    }
+}
+
+/// Iterates over the fields of a message as `(number, value)`, repeated fields show up
+/// once per element
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self
+                .bytes
+                .split_first()
+                .ok_or(ProtobufError::UnexpectedEnd)?;
+            self.bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ProtobufError::VarintOverflow)
+    }
+
+    const fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(ProtobufError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn field(&mut self) -> Result<(u32, FieldValue<'a>)> {
+        let key = self.varint()?;
+        let number = (key >> 3) as u32;
+        let value = match key & 0b111 {
+            VARINT => FieldValue::Varint(self.varint()?),
+            FIXED64 => {
+                let bytes = self.take(8)?.try_into().expect("took 8 bytes");
+                FieldValue::Fixed64(u64::from_le_bytes(bytes))
+            }
+            LENGTH_DELIMITED => {
+                let len = self.varint()?;
+                let len = usize::try_from(len).map_err(|_| ProtobufError::UnexpectedEnd)?;
+                FieldValue::LengthDelimited(self.take(len)?)
+            }
+            FIXED32 => {
+                let bytes = self.take(4)?.try_into().expect("took 4 bytes");
+                FieldValue::Fixed32(u32::from_le_bytes(bytes))
+            }
+            wire_type => return Err(ProtobufError::WireType(wire_type)),
+        };
+        Ok((number, value))
+    }
+}
+
+impl<'a> Iterator for Decoder<'a> {
+    type Item = Result<(u32, FieldValue<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let field = self.field();
+        if field.is_err() {
+            // don't read garbage after an error
+            self.bytes = &[];
+        }
+        Some(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoder, Encoder, FieldValue, ProtobufError};
+
+    #[test]
+    fn round_trips() {
+        let mut encoder = Encoder::default();
+        encoder.uint64(1, 300);
+        encoder.string(2, "gaben");
+        encoder.fixed64(3, 76561197960287930);
+        encoder.int32(4, -1);
+        encoder.bool(5, true);
+        encoder.fixed32(6, 7);
+        let bytes = encoder.into_bytes();
+        // 300 is the example of the protobuf docs
+        assert_eq!(bytes[..3], [0x08, 0xac, 0x02]);
+
+        let fields = Decoder::new(&bytes).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[0], (1, FieldValue::Varint(300)));
+        assert_eq!(fields[1].1.as_str().unwrap(), "gaben");
+        assert_eq!(fields[2].1.as_u64().unwrap(), 76561197960287930);
+        assert_eq!(fields[3].1.as_i32().unwrap(), -1);
+        assert!(fields[4].1.as_bool().unwrap());
+        assert_eq!(fields[5], (6, FieldValue::Fixed32(7)));
+    }
+
+    #[test]
+    fn rejects_truncated() {
+        let mut encoder = Encoder::default();
+        encoder.string(1, "gaben");
+        let bytes = encoder.into_bytes();
+
+        let mut decoder = Decoder::new(&bytes[..4]);
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(ProtobufError::UnexpectedEnd))
+        ));
+        assert!(decoder.next().is_none());
+        assert!(matches!(
+            FieldValue::Varint(1).as_str(),
+            Err(ProtobufError::FieldType)
+        ));
+    }
+}
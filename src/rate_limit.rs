@@ -0,0 +1,283 @@
+//! Space requests out according to a [`RateSchedule`], e.g. to crawl faster at night when
+//! a shared api key isn't used by anyone else.
+//!
+//! ```
+//! use chrono::NaiveTime;
+//! use steam_api_concurrent::rate_limit::RateSchedule;
+//!
+//! // 5 requests per second, 20 per second between 00:00 and 06:00 UTC
+//! let schedule = RateSchedule::new(5.0).window(
+//!     NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+//!     NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+//!     20.0,
+//! );
+//! ```
+//!
+//! The limiter only knows about the requests of its own process. Processes that share a key
+//! can share a [`RateCoordinator`] as well, e.g. a [`FileCoordinator`] on the same path,
+//! so their combined rate follows the schedule.
+
+use std::fmt::Debug;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, NaiveTime, Utc};
+use tokio::time::Instant;
+
+/// A rate that applies between `start` and `end` UTC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateWindow {
+    pub start: NaiveTime,
+    /// If `end` is before `start`, the window wraps around midnight
+    pub end: NaiveTime,
+    /// Requests per second
+    pub per_second: f64,
+}
+
+impl RateWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateSchedule {
+    default: f64,
+    windows: Vec<RateWindow>,
+}
+
+impl RateSchedule {
+    /// Allow `per_second` requests per second at any time of the day
+    ///
+    /// Panics, if `per_second` isn't positive
+    pub fn new(per_second: f64) -> Self {
+        assert!(per_second > 0.0, "rate must be positive");
+        Self {
+            default: per_second,
+            windows: Vec::new(),
+        }
+    }
+
+    /// Allow `per_second` requests per second between `start` and `end` UTC instead.
+    /// If windows overlap, the one added first wins.
+    ///
+    /// Panics, if `per_second` isn't positive
+    pub fn window(mut self, start: NaiveTime, end: NaiveTime, per_second: f64) -> Self {
+        assert!(per_second > 0.0, "rate must be positive");
+        self.windows.push(RateWindow {
+            start,
+            end,
+            per_second,
+        });
+        self
+    }
+
+    pub fn windows(&self) -> &[RateWindow] {
+        &self.windows
+    }
+
+    /// Requests per second allowed at `at`
+    pub fn rate_at(&self, at: DateTime<Utc>) -> f64 {
+        let time = at.time();
+        self.windows
+            .iter()
+            .find(|window| window.contains(time))
+            .map_or(self.default, |window| window.per_second)
+    }
+}
+
+/// Hands out request slots to every process that shares it, e.g. through a file or a
+/// key-value store like Redis
+pub trait RateCoordinator: Debug + Send + Sync {
+    /// Reserve the next free slot and block the following `interval` for other requests,
+    /// returns when the reserved request may be sent
+    fn reserve(&self, interval: Duration) -> io::Result<SystemTime>;
+}
+
+/// Keeps the next free slot in a file, which is locked while a slot is reserved
+#[derive(Debug, Clone)]
+pub struct FileCoordinator {
+    path: PathBuf,
+}
+
+impl FileCoordinator {
+    /// The file is created if it doesn't exist, every process has to use the same path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl RateCoordinator for FileCoordinator {
+    fn reserve(&self, interval: Duration) -> io::Result<SystemTime> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        // released when the file is closed
+        file.lock()?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        // microseconds since the unix epoch, empty until the first reservation
+        let next = content
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|micros| UNIX_EPOCH + Duration::from_micros(micros));
+        let now = SystemTime::now();
+        let slot = next.map_or(now, |next| next.max(now));
+
+        let micros = (slot + interval)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", micros)?;
+        Ok(slot)
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    schedule: RateSchedule,
+    /// When the next request may be sent
+    next: Mutex<Option<Instant>>,
+    coordinator: Option<Arc<dyn RateCoordinator>>,
+}
+
+impl RateLimiter {
+    pub const fn new(schedule: RateSchedule) -> Self {
+        Self {
+            schedule,
+            next: Mutex::new(None),
+            coordinator: None,
+        }
+    }
+
+    /// Get the slots from `coordinator`, to share the schedule with other processes
+    pub fn with_coordinator(schedule: RateSchedule, coordinator: Arc<dyn RateCoordinator>) -> Self {
+        Self {
+            coordinator: Some(coordinator),
+            ..Self::new(schedule)
+        }
+    }
+
+    pub const fn schedule(&self) -> &RateSchedule {
+        &self.schedule
+    }
+
+    /// Wait until the schedule allows another request
+    ///
+    /// If the coordinator fails, e.g. because the file can't be opened,
+    /// the slot is taken from the limiter of this process instead
+    /// and the error of the coordinator is returned once that slot is reached.
+    pub async fn acquire(&self) -> io::Result<()> {
+        let interval = Duration::from_secs_f64(1.0 / self.schedule.rate_at(Utc::now()));
+        let mut failure = None;
+        if let Some(coordinator) = self.coordinator.clone() {
+            let reserved = tokio::task::spawn_blocking(move || coordinator.reserve(interval))
+                .await
+                .unwrap_or_else(|err| Err(io::Error::other(err)));
+            match reserved {
+                Ok(slot) => {
+                    let wait = slot.duration_since(SystemTime::now()).unwrap_or_default();
+                    tokio::time::sleep(wait).await;
+                    return Ok(());
+                }
+                Err(err) => failure = Some(err),
+            }
+        }
+        let slot = {
+            let mut next = self.next.lock().expect("rate limiter lock poisoned");
+            let now = Instant::now();
+            let slot = next.map_or(now, |next| next.max(now));
+            *next = Some(slot + interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{NaiveTime, TimeZone, Utc};
+    use tokio::time::Instant;
+
+    use super::{FileCoordinator, RateLimiter, RateSchedule};
+
+    fn time(hour: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn picks_window() {
+        let schedule = RateSchedule::new(5.0)
+            .window(time(22), time(6), 20.0)
+            .window(time(0), time(12), 10.0);
+        let at = |hour| Utc.with_ymd_and_hms(2023, 10, 13, hour, 0, 0).unwrap();
+
+        assert_eq!(schedule.rate_at(at(23)), 20.0);
+        assert_eq!(schedule.rate_at(at(3)), 20.0);
+        assert_eq!(schedule.rate_at(at(6)), 10.0);
+        assert_eq!(schedule.rate_at(at(12)), 5.0);
+        assert_eq!(schedule.rate_at(at(21)), 5.0);
+    }
+
+    #[tokio::test]
+    async fn spaces_requests() {
+        let limiter = RateLimiter::new(RateSchedule::new(20.0));
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn shares_slots_between_limiters() {
+        let path = std::env::temp_dir().join(format!("steam_api_rate_{}", std::process::id()));
+        let coordinator = Arc::new(FileCoordinator::new(&path));
+        // as if they were in different processes
+        let fst = RateLimiter::with_coordinator(RateSchedule::new(20.0), coordinator.clone());
+        let snd = RateLimiter::with_coordinator(RateSchedule::new(20.0), coordinator);
+
+        let start = Instant::now();
+        for _ in 0..2 {
+            fst.acquire().await.unwrap();
+            snd.acquire().await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(140));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn falls_back_when_the_coordinator_fails() {
+        // a directory can't be opened as the file
+        let coordinator = Arc::new(FileCoordinator::new(std::env::temp_dir()));
+        let limiter = RateLimiter::with_coordinator(RateSchedule::new(20.0), coordinator);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.acquire().await.is_err());
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,267 @@
+//! Render summaries and bans as a Markdown or HTML table, e.g. for chat bots or issue reports.
+//!
+//! ```
+//! use steam_api_concurrent::api::{PlayerBans, PlayerSummaries};
+//! use steam_api_concurrent::report::{Column, Report, ReportFormat};
+//!
+//! # fn render(summaries: &PlayerSummaries, bans: &PlayerBans) -> String {
+//! Report::new(ReportFormat::Markdown)
+//!     .columns(&[Column::Name, Column::VacBans, Column::GameBans])
+//!     .render(summaries, bans)
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::api::{PlayerBan, PlayerBans, PlayerSummaries, PlayerSummary};
+use crate::{EconomyBan, SteamId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportFormat {
+    /// A GitHub flavored Markdown table
+    Markdown,
+    /// A `<table>` without any styling
+    Html,
+}
+
+/// A column of a [`Report`], empty for profiles that have no summary or no bans entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column {
+    SteamId,
+    Name,
+    ProfileUrl,
+    RealName,
+    Country,
+    /// The date the account was created in utc, if the profile is public
+    Created,
+    VacBans,
+    GameBans,
+    DaysSinceLastBan,
+    CommunityBanned,
+    EconomyBan,
+}
+
+impl Column {
+    /// The columns of [`Report::new`]
+    pub const DEFAULT: [Column; 5] = [
+        Column::SteamId,
+        Column::Name,
+        Column::VacBans,
+        Column::GameBans,
+        Column::DaysSinceLastBan,
+    ];
+
+    pub const fn header(self) -> &'static str {
+        match self {
+            Column::SteamId => "Steam ID",
+            Column::Name => "Name",
+            Column::ProfileUrl => "Profile",
+            Column::RealName => "Real name",
+            Column::Country => "Country",
+            Column::Created => "Created",
+            Column::VacBans => "VAC bans",
+            Column::GameBans => "Game bans",
+            Column::DaysSinceLastBan => "Days since last ban",
+            Column::CommunityBanned => "Community banned",
+            Column::EconomyBan => "Trade ban",
+        }
+    }
+
+    fn value(
+        self,
+        id: SteamId,
+        summary: Option<&PlayerSummary>,
+        ban: Option<&PlayerBan>,
+    ) -> String {
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        match self {
+            Column::SteamId => Some(id.to_string()),
+            Column::Name => summary.map(|s| s.persona_name().to_string()),
+            Column::ProfileUrl => summary.map(|s| s.profile_url().to_string()),
+            Column::RealName => summary.and_then(|s| s.real_name()).map(str::to_string),
+            Column::Country => summary
+                .and_then(|s| s.local_country_code())
+                .map(str::to_string),
+            Column::Created => summary
+                .and_then(PlayerSummary::time_created)
+                .map(|time| time.to_utc().format("%Y-%m-%d").to_string()),
+            Column::VacBans => ban.map(|b| b.number_of_vac_bans.to_string()),
+            Column::GameBans => ban.map(|b| b.number_of_game_bans.to_string()),
+            // Steam reports `0` for profiles that were never banned
+            Column::DaysSinceLastBan => ban
+                .filter(|b| !b.is_clean())
+                .map(|b| b.days_since_last_ban.to_string()),
+            Column::CommunityBanned => ban.map(|b| yes_no(b.community_banned)),
+            Column::EconomyBan => ban.map(|b| {
+                match b.economy_ban {
+                    EconomyBan::None => "none",
+                    EconomyBan::Probation => "probation",
+                    EconomyBan::Banned => "banned",
+                }
+                .to_string()
+            }),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Renders one row per profile that is in the summaries or the bans, ordered by id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    format: ReportFormat,
+    columns: Vec<Column>,
+}
+
+impl Report {
+    /// A report with the [`Column::DEFAULT`] columns
+    pub fn new(format: ReportFormat) -> Self {
+        Self {
+            format,
+            columns: Column::DEFAULT.to_vec(),
+        }
+    }
+
+    /// Replace the columns, they're rendered in the given order
+    pub fn columns(&mut self, columns: &[Column]) -> &mut Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn render(&self, summaries: &PlayerSummaries, bans: &PlayerBans) -> String {
+        let ids = summaries.keys().chain(bans.keys()).collect::<BTreeSet<_>>();
+        let rows = ids.into_iter().map(|id| {
+            self.columns
+                .iter()
+                .map(|column| column.value(*id, summaries.get(id), bans.get(id)))
+                .collect::<Vec<_>>()
+        });
+        match self.format {
+            ReportFormat::Markdown => self.markdown(rows),
+            ReportFormat::Html => self.html(rows),
+        }
+    }
+
+    fn markdown(&self, rows: impl Iterator<Item = Vec<String>>) -> String {
+        fn line<'a>(out: &mut String, cells: impl Iterator<Item = &'a str>) {
+            for cell in cells {
+                let cell = cell.replace('|', "\\|").replace(['\r', '\n'], " ");
+                let _ = write!(out, "| {} ", cell);
+            }
+            out.push_str("|\n");
+        }
+
+        let mut out = String::new();
+        line(&mut out, self.columns.iter().map(|column| column.header()));
+        line(&mut out, self.columns.iter().map(|_| "---"));
+        for row in rows {
+            line(&mut out, row.iter().map(String::as_str));
+        }
+        out
+    }
+
+    fn html(&self, rows: impl Iterator<Item = Vec<String>>) -> String {
+        fn line<'a>(out: &mut String, tag: &str, cells: impl Iterator<Item = &'a str>) {
+            out.push_str("<tr>");
+            for cell in cells {
+                let _ = write!(out, "<{tag}>{}</{tag}>", escape_html(cell));
+            }
+            out.push_str("</tr>\n");
+        }
+
+        let mut out = String::from("<table>\n<thead>\n");
+        line(
+            &mut out,
+            "th",
+            self.columns.iter().map(|column| column.header()),
+        );
+        out.push_str("</thead>\n<tbody>\n");
+        for row in rows {
+            line(&mut out, "td", row.iter().map(String::as_str));
+        }
+        out.push_str("</tbody>\n</table>\n");
+        out
+    }
+}
+
+fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Column, Report, ReportFormat};
+    use crate::api::{PlayerBans, PlayerSummaries};
+    use crate::test_util::{player_ban_json, player_summary_json};
+
+    fn load() -> (PlayerSummaries, PlayerBans) {
+        let summaries = serde_json::from_value::<PlayerSummaries>(json!({
+            "76561198230177976": player_summary_json(json!({
+                "steamid": "76561198230177976",
+                "personaname": "a | <b>",
+                "profileurl": "https://steamcommunity.com/id/a/",
+                "timecreated": 1681963569,
+                "loccountrycode": "DE"
+            }))
+        }));
+        let bans = serde_json::from_value::<PlayerBans>(json!({
+            "76561198196615742": player_ban_json(json!({
+                "SteamId": "76561198196615742",
+                "VACBanned": true,
+                "NumberOfVACBans": 2,
+                "DaysSinceLastBan": 40
+            }))
+        }));
+        (summaries.unwrap(), bans.unwrap())
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let (summaries, bans) = load();
+        let report = Report::new(ReportFormat::Markdown).render(&summaries, &bans);
+        assert_eq!(
+            report,
+            "\
+| Steam ID | Name | VAC bans | Game bans | Days since last ban |
+| --- | --- | --- | --- | --- |
+| 76561198196615742 |  | 2 | 0 | 40 |
+| 76561198230177976 | a \\| <b> |  |  |  |
+"
+        );
+    }
+
+    #[test]
+    fn renders_html() {
+        let (summaries, bans) = load();
+        let report = Report::new(ReportFormat::Html)
+            .columns(&[Column::Name, Column::Country, Column::Created])
+            .render(&summaries, &bans);
+        assert_eq!(
+            report,
+            "\
+<table>
+<thead>
+<tr><th>Name</th><th>Country</th><th>Created</th></tr>
+</thead>
+<tbody>
+<tr><td></td><td></td><td></td></tr>
+<tr><td>a | &lt;b&gt;</td><td>DE</td><td>2023-04-20</td></tr>
+</tbody>
+</table>
+"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Detect when Steam sends fields the models don't know about yet.
+//!
+//! With the `schema_drift` feature, every json response is parsed a second time while
+//! watching for fields the model ignores. Their paths are collected per [`Endpoint`]
+//! in the [`SchemaDrift`] of the client, see [`Client::schema_drift`](crate::Client::schema_drift).
+//!
+//! Elements of arrays show up as `[]` and keys of maps like any other field,
+//! e.g. `response.players[].newfield`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde_ignored::Path;
+
+use crate::Endpoint;
+
+/// The unknown fields seen in responses, shared by all clients created with
+/// [`Client::scoped`](crate::Client::scoped)
+#[derive(Debug, Default)]
+pub struct SchemaDrift {
+    fields: Mutex<BTreeMap<Endpoint, BTreeSet<String>>>,
+}
+
+/// `serde_ignored` shows array indices like keys, which would record a path per element
+fn format_path(path: &Path<'_>, out: &mut String) {
+    match path {
+        Path::Root => {}
+        Path::Seq { parent, .. } => {
+            format_path(parent, out);
+            out.push_str("[]");
+        }
+        Path::Map { parent, key } => {
+            format_path(parent, out);
+            if !out.is_empty() {
+                out.push('.');
+            }
+            out.push_str(key);
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => format_path(parent, out),
+    }
+}
+
+impl SchemaDrift {
+    pub fn record(&self, endpoint: Endpoint, path: String) {
+        let mut fields = self.fields.lock().unwrap();
+        fields.entry(endpoint).or_default().insert(path);
+    }
+
+    /// Parse `body` as `T` and record the fields `T` ignores
+    pub(crate) fn scan<T: DeserializeOwned>(&self, endpoint: Endpoint, body: &[u8]) {
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        // invalid responses are reported by the actual parse
+        let _ = serde_ignored::deserialize::<_, _, T>(&mut deserializer, |path| {
+            let mut formatted = String::new();
+            format_path(&path, &mut formatted);
+            self.record(endpoint, formatted);
+        });
+    }
+
+    /// The unknown fields seen in responses from `endpoint`, sorted
+    pub fn unknown_fields(&self, endpoint: Endpoint) -> Vec<String> {
+        let fields = self.fields.lock().unwrap();
+        fields
+            .get(&endpoint)
+            .map(|paths| paths.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The unknown fields of every endpoint that had any
+    pub fn all(&self) -> BTreeMap<Endpoint, BTreeSet<String>> {
+        self.fields.lock().unwrap().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.lock().unwrap().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.fields.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::SchemaDrift;
+    use crate::Endpoint;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Player {
+        steamid: String,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Response {
+        players: Vec<Player>,
+    }
+
+    #[test]
+    fn records_unknown_fields() {
+        let drift = SchemaDrift::default();
+        let body = br#"{"players":[{"steamid":"1","new":1},{"steamid":"2","new":2}],"total":2}"#;
+        drift.scan::<Response>(Endpoint::PlayerSummaries, body);
+
+        assert_eq!(
+            drift.unknown_fields(Endpoint::PlayerSummaries),
+            ["players[].new", "total"]
+        );
+        assert!(drift.unknown_fields(Endpoint::PlayerBans).is_empty());
+
+        drift.clear();
+        assert!(drift.is_empty());
+    }
+
+    #[test]
+    fn ignores_known_fields() {
+        let drift = SchemaDrift::default();
+        drift.scan::<Response>(Endpoint::PlayerSummaries, br#"{"players":[]}"#);
+        assert!(drift.is_empty());
+    }
+
+    // `strict_parse` rejects the unknown field before it can be recorded
+    #[cfg(all(feature = "testing", not(feature = "strict_parse")))]
+    #[tokio::test]
+    async fn records_responses() {
+        use crate::testing::MockSteam;
+        use crate::SteamId;
+
+        let steam = MockSteam::start().await;
+        let mut json: serde_json::Value =
+            serde_json::from_str(crate::testing::fixtures::STEAM_LEVEL).unwrap();
+        json["response"]["player_xp"] = 1234.into();
+        steam.mount_json(Endpoint::PlayerSteamLevel, &json).await;
+
+        let client = steam.client().await;
+        let scoped = client.scoped("tenant", crate::tenant::Quota::per_minute(60));
+        scoped
+            .get_player_steam_level(SteamId(76561198196615742))
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .schema_drift()
+                .unknown_fields(Endpoint::PlayerSteamLevel),
+            ["response.player_xp"]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Free functions for scripts and examples, where building a [`Client`] is more code
+//! than the request itself
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), steam_api_concurrent::simple::SimpleError> {
+//! use steam_api_concurrent::simple;
+//!
+//! let resolution = simple::resolve("key", "gabelogannewell").await?;
+//! let steam_id = resolution.steam_id().expect("vanity url not found");
+//! let summaries = simple::summaries("key", &[steam_id]).await?;
+//! println!("{:?}", summaries[&steam_id]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every call builds its own client with the default settings and drops it afterwards:
+//!
+//! - building a client requests a session id from the community, one extra request per call
+//! - connections aren't reused between calls
+//! - retries, the error history and rate limits only cover a single call
+//!
+//! Anything that makes more than a handful of requests should keep one [`Client`] around.
+//!
+//! The clients share one cancellation token, [`shutdown`] stops the bulk calls of all of them.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use futures::{StreamExt, TryStreamExt};
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+use crate::model::api::{
+    Batched, PlayerSummaries, PlayerSummary, PlayerSummaryError, VanityResolution, VanityUrlError,
+};
+use crate::{Client, Endpoint, SteamId};
+
+#[derive(Debug, Error)]
+pub enum SimpleError {
+    /// The throwaway client couldn't be built
+    #[error(transparent)]
+    Client(#[from] crate::Error),
+
+    #[error(transparent)]
+    VanityUrl(#[from] VanityUrlError),
+
+    #[error(transparent)]
+    PlayerSummary(#[from] PlayerSummaryError),
+
+    /// [`shutdown`] was called before the call finished
+    #[error("cancelled")]
+    Cancelled,
+}
+type Result<T> = std::result::Result<T, SimpleError>;
+
+/// The cancellation token of every client built here
+static SHUTDOWN: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+
+async fn client(key: &str) -> Result<Client> {
+    Ok(Client::builder()
+        .api_key(key.to_string())
+        .cancellation_token(SHUTDOWN.clone())
+        .build()
+        .await?)
+}
+
+/// Stop the bulk calls of this module, e.g. [`summaries`], see [`Client::shutdown`]
+///
+/// Meant for the end of the program, bulk calls made afterwards stop right away
+/// with [`SimpleError::Cancelled`].
+pub fn shutdown() {
+    SHUTDOWN.cancel();
+}
+
+/// Resolve the vanity url `vanity` (the part after `steamcommunity.com/id/`),
+/// see [`Client::resolve_vanity_url`]
+pub async fn resolve(key: &str, vanity: &str) -> Result<VanityResolution> {
+    Ok(client(key).await?.resolve_vanity_url(vanity).await?)
+}
+
+/// Get the summaries of any number of profiles, see [`Client::get_player_summaries`]
+///
+/// Unlike the method this splits `ids` into chunks that fit in one request each.
+pub async fn summaries(key: &str, ids: &[SteamId]) -> Result<HashMap<SteamId, PlayerSummary>> {
+    let client = client(key).await?;
+    let chunks = futures::stream::iter(PlayerSummaries::chunks(ids))
+        .map(|chunk| client.get_player_summaries(chunk.into()))
+        .buffer_unordered(Endpoint::PlayerSummaries.concurrent_requests())
+        .try_collect::<Vec<_>>();
+    let chunks = client
+        .until_shutdown(chunks)
+        .await
+        .ok_or(SimpleError::Cancelled)??;
+    Ok(chunks
+        .into_iter()
+        .flat_map(PlayerSummaries::into_inner)
+        .collect())
+}
@@ -0,0 +1,145 @@
+//! Per-tenant request quotas for clients created with [`Client::scoped`](crate::Client::scoped).
+//!
+//! A scoped client shares the connection pool, session, [`Governor`](crate::governor::Governor)
+//! and [`RateLimiter`](crate::rate_limit::RateLimiter) with its parent, but counts its own
+//! requests and retries. Once a tenant used up its quota, its requests wait until the oldest
+//! request in the period expires instead of failing.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// At most `requests` requests in any `period`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub requests: usize,
+    pub period: Duration,
+}
+
+impl Quota {
+    /// Panics, if `requests` is zero
+    pub fn new(requests: usize, period: Duration) -> Self {
+        assert!(requests > 0, "quota must allow at least one request");
+        Self { requests, period }
+    }
+    pub fn per_minute(requests: usize) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+    pub fn per_hour(requests: usize) -> Self {
+        Self::new(requests, Duration::from_secs(60 * 60))
+    }
+}
+
+#[derive(Debug)]
+pub struct Tenant {
+    id: String,
+    quota: Quota,
+    /// When the requests in the current period were sent
+    sent: Mutex<VecDeque<Instant>>,
+    total_requests: AtomicUsize,
+}
+
+impl Tenant {
+    pub fn new(id: impl Into<String>, quota: Quota) -> Self {
+        Self {
+            id: id.into(),
+            quota,
+            sent: Mutex::new(VecDeque::with_capacity(quota.requests)),
+            total_requests: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+    pub const fn quota(&self) -> Quota {
+        self.quota
+    }
+    /// Requests sent by this tenant, including retries
+    pub fn total_requests(&self) -> usize {
+        self.total_requests.load(Ordering::SeqCst)
+    }
+    /// Requests that can be sent right now without waiting
+    pub fn remaining(&self) -> usize {
+        let mut sent = self.sent.lock().expect("tenant lock poisoned");
+        self.prune(&mut sent, Instant::now());
+        self.quota.requests.saturating_sub(sent.len())
+    }
+
+    /// Wait until the quota allows another request and count it
+    pub async fn acquire(&self) {
+        loop {
+            let wait_until = {
+                let mut sent = self.sent.lock().expect("tenant lock poisoned");
+                let now = Instant::now();
+                self.prune(&mut sent, now);
+                if sent.len() < self.quota.requests {
+                    sent.push_back(now);
+                    break;
+                }
+                sent[0] + self.quota.period
+            };
+            tokio::time::sleep_until(wait_until).await;
+        }
+        self.total_requests.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn prune(&self, sent: &mut VecDeque<Instant>, now: Instant) {
+        while let Some(at) = sent.front() {
+            if now.duration_since(*at) < self.quota.period {
+                break;
+            }
+            sent.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{Quota, Tenant};
+
+    #[tokio::test]
+    async fn waits_for_quota() {
+        let tenant = Tenant::new("a", Quota::new(2, Duration::from_millis(100)));
+        let start = Instant::now();
+        tenant.acquire().await;
+        tenant.acquire().await;
+        assert_eq!(tenant.remaining(), 0);
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        tenant.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(tenant.total_requests(), 3);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn scoped_clients_are_separate() {
+        use crate::testing::MockSteam;
+        use crate::{Endpoint, SteamId};
+
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        let scoped = client.scoped("a", Quota::new(1, Duration::from_millis(100)));
+        assert!(client.tenant().is_none());
+        assert_eq!(scoped.tenant().unwrap().id(), "a");
+
+        let ids = [SteamId(76561197960287930)];
+        steam.mount_status(Endpoint::PlayerBans, 429, 1).await;
+        let start = Instant::now();
+        scoped.get_player_bans(ids[..].into()).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        assert_eq!(scoped.tenant().unwrap().total_requests(), 2);
+        assert_eq!(scoped.endpoint_retries(Endpoint::PlayerBans), 1);
+        assert_eq!(client.total_retries(), 0);
+        assert_eq!(client.global_stats().total_retries(), 1);
+    }
+}
@@ -23,3 +23,131 @@ macro_rules! load_test_json {
         }
     }};
 }
+
+/// Load a file from `/test_resources/` as a string, for payloads that aren't json
+#[allow(unused_macros)]
+macro_rules! load_test_str {
+    ($filename:literal) => {{
+        let mut path = ::std::path::PathBuf::from(::std::env!("CARGO_MANIFEST_DIR"));
+        path.push("test_resources");
+        path.push($filename);
+        match ::std::fs::read_to_string(path) {
+            Err(err) => {
+                println!("couldn't open test resource file `{}`", $filename);
+                panic!("{:?}", err);
+            }
+            Ok(str) => str,
+        }
+    }};
+}
+
+/// Encode `$value` with [`crate::util::snapshot`], decode it as `$T` and make sure
+/// the decoded value serializes to the same json as the original (maps are unordered).
+#[cfg(feature = "bincode")]
+macro_rules! assert_bincode_round_trip {
+    ($value:expr, $T:ty) => {{
+        let bytes = crate::util::snapshot::to_bytes(&$value).unwrap();
+        let decoded: $T = crate::util::snapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            ::serde_json::to_value(&$value).unwrap(),
+            ::serde_json::to_value(&decoded).unwrap()
+        );
+        decoded
+    }};
+}
+
+/// The json of a public profile's summary as the api sends it, with the fields in
+/// `overrides` replaced or added
+#[allow(dead_code)]
+pub fn player_summary_json(overrides: serde_json::Value) -> serde_json::Value {
+    merge(
+        serde_json::json!({
+            "steamid": "76561197960287930",
+            "communityvisibilitystate": 3,
+            "profilestate": 1,
+            "personaname": "name",
+            "profileurl": "https://steamcommunity.com/id/name/",
+            "avatar": "",
+            "avatarmedium": "",
+            "avatarfull": "",
+            "avatarhash": "",
+            "personastate": 0
+        }),
+        overrides,
+    )
+}
+
+/// [`player_summary_json`] parsed
+#[allow(dead_code)]
+pub fn player_summary(overrides: serde_json::Value) -> crate::api::PlayerSummary {
+    serde_json::from_value(player_summary_json(overrides)).unwrap()
+}
+
+/// The json of a clean profile's bans as the api sends them, with the fields in
+/// `overrides` replaced or added
+#[allow(dead_code)]
+pub fn player_ban_json(overrides: serde_json::Value) -> serde_json::Value {
+    merge(
+        serde_json::json!({
+            "SteamId": "76561197960287930",
+            "CommunityBanned": false,
+            "VACBanned": false,
+            "NumberOfVACBans": 0,
+            "DaysSinceLastBan": 0,
+            "NumberOfGameBans": 0,
+            "EconomyBan": "none"
+        }),
+        overrides,
+    )
+}
+
+/// [`player_ban_json`] parsed
+#[allow(dead_code)]
+pub fn player_ban(overrides: serde_json::Value) -> crate::api::PlayerBan {
+    serde_json::from_value(player_ban_json(overrides)).unwrap()
+}
+
+/// The query of a successful OpenID login of `76561197960287930` to
+/// `https://example.com/auth/steam` like Steam sends it, with the fields in `overrides`
+/// replaced or added
+#[cfg(feature = "openid")]
+pub fn openid_query(overrides: &[(&str, &str)]) -> String {
+    let mut params = vec![
+        ("openid.ns", "http://specs.openid.net/auth/2.0"),
+        ("openid.mode", "id_res"),
+        (
+            "openid.op_endpoint",
+            "https://steamcommunity.com/openid/login",
+        ),
+        (
+            "openid.claimed_id",
+            "https://steamcommunity.com/openid/id/76561197960287930",
+        ),
+        (
+            "openid.identity",
+            "https://steamcommunity.com/openid/id/76561197960287930",
+        ),
+        ("openid.return_to", "https://example.com/auth/steam"),
+        ("openid.response_nonce", "2023-10-09T17:00:00ZabcdEFGH"),
+        ("openid.assoc_handle", "1234567890"),
+        (
+            "openid.signed",
+            "signed,op_endpoint,claimed_id,identity,return_to,response_nonce,assoc_handle",
+        ),
+        ("openid.sig", "c2lnbmF0dXJl"),
+    ];
+    for (key, value) in overrides {
+        match params.iter_mut().find(|(k, _)| k == key) {
+            Some(param) => param.1 = value,
+            None => params.push((key, value)),
+        }
+    }
+    serde_urlencoded::to_string(params).unwrap()
+}
+
+fn merge(mut base: serde_json::Value, overrides: serde_json::Value) -> serde_json::Value {
+    if let (Some(base), serde_json::Value::Object(overrides)) = (base.as_object_mut(), overrides) {
+        base.extend(overrides);
+    }
+    base
+}
@@ -0,0 +1,625 @@
+//! In-process mock of the Steam endpoints for integration tests.
+//!
+//! [`MockSteam`] serves the same recorded responses the crate uses for its own tests
+//! and hands out clients that are already pointed at it.
+//!
+//! ```no_run
+//! # async fn run() {
+//! use steam_api_concurrent::testing::MockSteam;
+//! use steam_api_concurrent::{Endpoint, SteamId};
+//!
+//! let steam = MockSteam::start().await;
+//! // the first request is answered with `429 Too Many Requests`
+//! steam.mount_status(Endpoint::PlayerBans, 429, 1).await;
+//!
+//! let client = steam.client().await;
+//! let ids = [SteamId(76561198196615742)];
+//! let bans = client.get_player_bans(ids[..].into()).await.unwrap();
+//! assert_eq!(client.total_retries(), 1);
+//! # }
+//! ```
+
+use reqwest::StatusCode;
+use wiremock::matchers::{method, path, path_regex, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::client::{Client, ClientBuilder, Error};
+use crate::Endpoint;
+
+/// The recorded responses from `test_resources/`
+pub mod fixtures {
+    pub const APP_LIST: &str = include_str!("../test_resources/app_list.json");
+    pub const APP_BETAS: &str = include_str!("../test_resources/app_betas.json");
+    pub const APP_BUILDS: &str = include_str!("../test_resources/app_builds.json");
+    pub const APP_SEARCH: &str = include_str!("../test_resources/app_search.json");
+    pub const ASSET_PRICES: &str = include_str!("../test_resources/asset_prices.json");
+    pub const CHEATING_REPORTS: &str = include_str!("../test_resources/cheating_reports.json");
+    pub const REPORT_PLAYER_CHEATING: &str =
+        include_str!("../test_resources/report_player_cheating.json");
+    pub const REQUEST_PLAYER_GAME_BAN: &str =
+        include_str!("../test_resources/request_player_game_ban.json");
+    pub const DECK_COMPATIBILITY: &str = include_str!("../test_resources/deck_compatibility.json");
+    pub const CM_LIST: &str = include_str!("../test_resources/cm_list.json");
+    pub const CM_LIST_FOR_CONNECT: &str =
+        include_str!("../test_resources/cm_list_for_connect.json");
+    pub const GROUP_RSS: &str = include_str!("../test_resources/group_rss.xml");
+    pub const GROUP_MEMBERS: &str = include_str!("../test_resources/group_members.html");
+    pub const GROUP_PAGE: &str = include_str!("../test_resources/group_page.html");
+    pub const GROUP_MEMBER_LIST: &str = include_str!("../test_resources/group_member_list.xml");
+    pub const OPENID_CHECK_AUTHENTICATION: &str =
+        include_str!("../test_resources/openid_check_authentication.txt");
+    pub const PLAYER_FRIENDS_ALL: &str = include_str!("../test_resources/player_friends_all.json");
+    pub const INVENTORY: &str = include_str!("../test_resources/inventory.json");
+    pub const ITEM_DEF_ARCHIVE: &str = include_str!("../test_resources/item_def_archive.json");
+    pub const ITEM_DEF_META: &str = include_str!("../test_resources/item_def_meta.json");
+    pub const LOBBY_DATA: &str = include_str!("../test_resources/lobby_data.json");
+    pub const MARKET_LISTINGS: &str = include_str!("../test_resources/market_listings.html");
+    pub const MINI_PROFILE: &str = include_str!("../test_resources/mini_profile.json");
+    pub const OWNED_GAMES: &str = include_str!("../test_resources/owned_games.json");
+    pub const OWNED_GAMES_PRIVATE: &str =
+        include_str!("../test_resources/owned_games_private.json");
+    pub const PLAYER_BANS: &str = include_str!("../test_resources/player_bans.json");
+    pub const PLAYER_FRIENDS_PRIVATE: &str =
+        include_str!("../test_resources/player_friends_private.json");
+    pub const PLAYER_FRIENDS_PUBLIC: &str =
+        include_str!("../test_resources/player_friends_public.json");
+    pub const PROFILE_PAGE: &str = include_str!("../test_resources/profile.html");
+    pub const PLAYER_SUMMARIES: &str = include_str!("../test_resources/player_summaries.json");
+    pub const PROFILE_ITEMS_EQUIPPED: &str =
+        include_str!("../test_resources/profile_items_equipped.json");
+    pub const PUBLISHED_FILE_DETAILS: &str =
+        include_str!("../test_resources/published_file_details.json");
+    pub const SERVER_INFO: &str = include_str!("../test_resources/server_info.json");
+    pub const SERVERS_AT_ADDRESS: &str = include_str!("../test_resources/servers_at_address.json");
+    pub const SUPPORTED_API_LIST: &str = include_str!("../test_resources/supported_api_list.json");
+    pub const STEAM_LEVEL: &str = include_str!("../test_resources/steam_level.json");
+    pub const STEAM_LEVEL_DELETED: &str =
+        include_str!("../test_resources/steam_level_deleted.json");
+    pub const USER_SEARCH: &str = include_str!("../test_resources/user_search.json");
+    pub const USER_STATS: &str = include_str!("../test_resources/user_stats.json");
+    pub const VANITY_URL: &str = include_str!("../test_resources/vanity_url.json");
+}
+
+/// The api-key used by clients from [`MockSteam::client`]
+pub const API_KEY: &str = "00000000000000000000000000000000";
+
+/// The publisher key used by clients from [`MockSteam::client`]
+pub const PUBLISHER_KEY: &str = "11111111111111111111111111111111";
+
+/// The session id handed out by the mock server
+pub const SESSION_ID: &str = "0123456789abcdef01234567";
+
+/// Priority of mocks that should win over the fixtures (lower is more important)
+const OVERRIDE_PRIORITY: u8 = 1;
+
+/// Matches the path of `endpoint`, including the parameters of endpoints like
+/// [`Endpoint::GroupAnnouncements`]
+fn endpoint_path(endpoint: Endpoint) -> String {
+    match endpoint {
+        // all three are below `/groups/{name}/`
+        Endpoint::GroupAnnouncements => format!("^{}[^/]+/rss/", endpoint.path()),
+        Endpoint::GroupMembers => format!("^{}[^/]+/members/", endpoint.path()),
+        Endpoint::GroupPage => format!("^{}[^/]+/?$", endpoint.path()),
+        _ => format!("^{}", endpoint.path()),
+    }
+}
+
+/// Whether a request to `path` went to `endpoint`, like [`endpoint_path`]
+fn is_endpoint_path(endpoint: Endpoint, path: &str) -> bool {
+    if endpoint == Endpoint::GroupPage {
+        return path
+            .strip_prefix(endpoint.path())
+            .is_some_and(|name| !name.trim_end_matches('/').contains('/'));
+    }
+    let suffix = match endpoint {
+        Endpoint::GroupAnnouncements => "/rss/",
+        Endpoint::GroupMembers => "/members/",
+        _ => "",
+    };
+    path.starts_with(endpoint.path()) && path.ends_with(suffix)
+}
+
+/// The fixture every endpoint is answered with by [`MockSteam::start`]
+const FIXTURES: [(Endpoint, &str); 37] = [
+    (Endpoint::PlayerBans, fixtures::PLAYER_BANS),
+    (Endpoint::PlayerFriends, fixtures::PLAYER_FRIENDS_PUBLIC),
+    (Endpoint::PlayerSummaries, fixtures::PLAYER_SUMMARIES),
+    (Endpoint::PlayerSteamLevel, fixtures::STEAM_LEVEL),
+    (Endpoint::ResolveVanityUrl, fixtures::VANITY_URL),
+    (Endpoint::OwnedGames, fixtures::OWNED_GAMES),
+    (
+        Endpoint::ProfileItemsEquipped,
+        fixtures::PROFILE_ITEMS_EQUIPPED,
+    ),
+    (
+        Endpoint::PublishedFileDetails,
+        fixtures::PUBLISHED_FILE_DETAILS,
+    ),
+    (Endpoint::ServerInfo, fixtures::SERVER_INFO),
+    (Endpoint::SupportedApiList, fixtures::SUPPORTED_API_LIST),
+    (Endpoint::AppList, fixtures::APP_LIST),
+    (Endpoint::CmList, fixtures::CM_LIST),
+    (Endpoint::CmListForConnect, fixtures::CM_LIST_FOR_CONNECT),
+    (Endpoint::AppBetas, fixtures::APP_BETAS),
+    (Endpoint::AppBuilds, fixtures::APP_BUILDS),
+    (
+        Endpoint::ReportPlayerCheating,
+        fixtures::REPORT_PLAYER_CHEATING,
+    ),
+    (
+        Endpoint::RequestPlayerGameBan,
+        fixtures::REQUEST_PLAYER_GAME_BAN,
+    ),
+    (Endpoint::CheatingReports, fixtures::CHEATING_REPORTS),
+    (Endpoint::Inventory, fixtures::INVENTORY),
+    (Endpoint::AddItem, fixtures::INVENTORY),
+    (Endpoint::ConsumeItem, fixtures::INVENTORY),
+    (Endpoint::ItemDefMeta, fixtures::ITEM_DEF_META),
+    (Endpoint::ItemDefArchive, fixtures::ITEM_DEF_ARCHIVE),
+    (Endpoint::UserStatsForGame, fixtures::USER_STATS),
+    (Endpoint::ServersAtAddress, fixtures::SERVERS_AT_ADDRESS),
+    (Endpoint::AssetPrices, fixtures::ASSET_PRICES),
+    (Endpoint::LobbyData, fixtures::LOBBY_DATA),
+    (Endpoint::UserSearch, fixtures::USER_SEARCH),
+    (Endpoint::GroupAnnouncements, fixtures::GROUP_RSS),
+    (Endpoint::GroupMembers, fixtures::GROUP_MEMBERS),
+    (Endpoint::GroupPage, fixtures::GROUP_PAGE),
+    (Endpoint::GroupMemberList, fixtures::GROUP_MEMBER_LIST),
+    (Endpoint::MiniProfile, fixtures::MINI_PROFILE),
+    (Endpoint::ProfilePage, fixtures::PROFILE_PAGE),
+    (Endpoint::MarketListings, fixtures::MARKET_LISTINGS),
+    (Endpoint::OpenIdLogin, fixtures::OPENID_CHECK_AUTHENTICATION),
+    (
+        Endpoint::DeckCompatibilityReport,
+        fixtures::DECK_COMPATIBILITY,
+    ),
+];
+
+/// A mock Steam server running in the background until dropped
+pub struct MockSteam {
+    server: MockServer,
+}
+
+impl MockSteam {
+    /// Start a server that hands out session ids and serves every fixture
+    pub async fn start() -> Self {
+        let steam = Self::start_empty().await;
+        for (endpoint, body) in FIXTURES {
+            steam.mount_fixture(endpoint, body).await;
+        }
+        steam
+    }
+
+    /// Start a server that only hands out session ids, so clients can be built
+    pub async fn start_empty() -> Self {
+        let server = MockServer::start().await;
+
+        // The client requests the search page without a session id to get one
+        Mock::given(method("GET"))
+            .and(path(Endpoint::UserSearch.path()))
+            .and(query_param_is_missing("sessionid"))
+            .respond_with(
+                ResponseTemplate::new(StatusCode::UNAUTHORIZED.as_u16()).insert_header(
+                    "set-cookie",
+                    format!("sessionid={}; Path=/; Secure; SameSite=None", SESSION_ID),
+                ),
+            )
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&server)
+            .await;
+
+        Self { server }
+    }
+
+    /// The base url of the server, used for every [`Host`](crate::Host)
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The underlying server, to mount custom mocks
+    pub const fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// A builder with the base urls pointed at this server and [`API_KEY`] set
+    pub fn client_builder(&self) -> ClientBuilder {
+        let mut builder = ClientBuilder::new();
+        builder
+            .api_base_url(self.uri())
+            .community_base_url(self.uri())
+            .partner_base_url(self.uri())
+            .store_base_url(self.uri())
+            .avatar_base_url(self.uri())
+            .api_key(API_KEY.to_string())
+            .publisher_key(PUBLISHER_KEY.to_string())
+            .retry_timeout_ms(0);
+        builder
+    }
+
+    /// Build a client from [`MockSteam::client_builder`]
+    pub async fn try_client(&self) -> Result<Client, Error> {
+        self.client_builder().build().await
+    }
+
+    /// Build a client from [`MockSteam::client_builder`]
+    ///
+    /// # Panics
+    ///
+    /// If the client can't be built, which means the server isn't handing out session ids
+    pub async fn client(&self) -> Client {
+        self.try_client()
+            .await
+            .expect("couldn't build client for mock server")
+    }
+
+    /// Answer requests to `endpoint` with `body`, json, xml or html depending on the endpoint
+    pub async fn mount_fixture(&self, endpoint: Endpoint, body: &str) {
+        let mime = match endpoint {
+            Endpoint::GroupAnnouncements => "application/rss+xml",
+            Endpoint::GroupMemberList => "text/xml",
+            Endpoint::OpenIdLogin => "text/plain",
+            Endpoint::GroupMembers
+            | Endpoint::GroupPage
+            | Endpoint::ProfilePage
+            | Endpoint::MarketListings => "text/html",
+            _ => "application/json",
+        };
+        Mock::given(method(endpoint.method()))
+            .and(path_regex(endpoint_path(endpoint)))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, mime))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer requests to `endpoint` with `value`, taking precedence over fixtures.
+    ///
+    /// Useful for partial batches or responses with edge cases.
+    pub async fn mount_json(&self, endpoint: Endpoint, value: &serde_json::Value) {
+        Mock::given(method(endpoint.method()))
+            .and(path_regex(endpoint_path(endpoint)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(value))
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer the next `times` requests to `endpoint` with `value`, taking precedence over fixtures
+    pub async fn mount_json_times(
+        &self,
+        endpoint: Endpoint,
+        value: &serde_json::Value,
+        times: u64,
+    ) {
+        Mock::given(method(endpoint.method()))
+            .and(path_regex(endpoint_path(endpoint)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(value))
+            .up_to_n_times(times)
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer the next `times` requests to `endpoint` with `status`, e.g. `429` to test retries
+    pub async fn mount_status(&self, endpoint: Endpoint, status: u16, times: u64) {
+        Mock::given(method(endpoint.method()))
+            .and(path_regex(endpoint_path(endpoint)))
+            .respond_with(ResponseTemplate::new(status))
+            .up_to_n_times(times)
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Answer the next `times` requests to `endpoint` with a body that isn't valid json
+    pub async fn mount_malformed(&self, endpoint: Endpoint, times: u64) {
+        Mock::given(method(endpoint.method()))
+            .and(path_regex(endpoint_path(endpoint)))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("{\"respon", "application/json"))
+            .up_to_n_times(times)
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// How many requests the server received for `endpoint`
+    pub async fn received(&self, endpoint: Endpoint) -> usize {
+        let requests = self.server.received_requests().await.unwrap_or_default();
+        requests
+            .iter()
+            .filter(|req| is_endpoint_path(endpoint, req.url.path()))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{MockSteam, SESSION_ID};
+    use crate::{Endpoint, SteamId};
+
+    #[tokio::test]
+    async fn serves_fixtures() {
+        let steam = MockSteam::start().await;
+        let client = steam.client().await;
+        assert_eq!(client.session_id(), SESSION_ID);
+
+        let ids = [SteamId(76561198196615742), SteamId(76561199159691884)];
+        let bans = client.get_player_bans(ids[..].into()).await.unwrap();
+        assert_eq!(bans.len(), 2);
+
+        let lvl = client.get_player_steam_level(ids[0]).await.unwrap();
+        assert_eq!(lvl.lvl(), Some(135));
+        assert_eq!(steam.received(Endpoint::PlayerBans).await, 1);
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limited() {
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 2).await;
+        let client = steam.client().await;
+
+        let ids = [SteamId(76561198196615742)];
+        let bans = client.get_player_bans(ids[..].into()).await.unwrap();
+        assert_eq!(bans.len(), 2);
+        assert_eq!(client.total_retries(), 2);
+        assert_eq!(client.endpoint_retries(Endpoint::PlayerBans), 2);
+    }
+
+    #[tokio::test]
+    async fn observes_retries() {
+        use std::sync::{Arc, Mutex};
+
+        use reqwest::StatusCode;
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 2).await;
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let observed = seen.clone();
+        let client = steam
+            .client_builder()
+            .on_retry(move |info| observed.lock().unwrap().push(*info))
+            .build()
+            .await
+            .unwrap();
+
+        let ids = [SteamId(76561198196615742)];
+        client.get_player_bans(ids[..].into()).await.unwrap();
+        let seen = seen.lock().unwrap().clone();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].endpoint, Endpoint::PlayerBans);
+        assert_eq!(seen[0].status, Some(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(
+            seen.iter().map(|info| info.attempt).collect::<Vec<_>>(),
+            [2, 3]
+        );
+
+        let errors = client.last_errors(5);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].status, Some(429));
+        assert_eq!(client.last_errors(1).len(), 1);
+        let histogram = client.stats().status_histogram();
+        assert_eq!(histogram[&429], 2);
+        assert_eq!(histogram[&200], 1);
+    }
+
+    #[tokio::test]
+    async fn summarizes_latencies_per_class() {
+        use crate::RateClass;
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 2).await;
+        let client = steam.client().await;
+
+        let ids = [SteamId(76561198196615742)];
+        client.get_player_bans(ids[..].into()).await.unwrap();
+        client
+            .get_market_listings(440, "Mann Co. Supply Crate Key")
+            .await
+            .unwrap();
+
+        let latencies = client.stats().latencies();
+        assert_eq!(
+            latencies.keys().copied().collect::<Vec<_>>(),
+            [RateClass::KeyedApi, RateClass::Community]
+        );
+        let api = latencies[&RateClass::KeyedApi];
+        assert_eq!(api.samples, 3);
+        assert!(api.p50 <= api.p95 && api.p95 <= api.max);
+        assert_eq!(client.stats().latency(RateClass::Store), None);
+
+        client.stats().reset();
+        assert!(client.stats().latencies().is_empty());
+        assert_eq!(
+            client.global_stats().latencies()[&RateClass::KeyedApi].samples,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_coordinator_failures() {
+        use std::sync::Arc;
+
+        use crate::rate_limit::{FileCoordinator, RateSchedule};
+
+        let steam = MockSteam::start().await;
+        // a directory can't be opened as the file
+        let coordinator = Arc::new(FileCoordinator::new(std::env::temp_dir()));
+        let client = steam
+            .client_builder()
+            .rate_schedule(RateSchedule::new(100.0))
+            .rate_coordinator(coordinator)
+            .build()
+            .await
+            .unwrap();
+
+        let ids = [SteamId(76561198196615742)];
+        client.get_player_bans(ids[..].into()).await.unwrap();
+        assert_eq!(client.coordinator_failures(), 1);
+        assert_eq!(client.global_stats().coordinator_failures(), 1);
+    }
+
+    #[tokio::test]
+    async fn adapts_concurrency() {
+        use crate::concurrency::ConcurrencyConfig;
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 1).await;
+        let client = steam
+            .client_builder()
+            .adaptive_concurrency(ConcurrencyConfig {
+                initial: Some(8),
+                increase_after: 1,
+                ..ConcurrencyConfig::default()
+            })
+            .build()
+            .await
+            .unwrap();
+        let concurrency = client.concurrency().unwrap();
+
+        let ids = [SteamId(76561198196615742)];
+        client.get_player_bans(ids[..].into()).await.unwrap();
+        // halved by the 429, then raised by the successful retry
+        assert_eq!(concurrency.limit(Endpoint::PlayerBans), 5);
+        assert_eq!(concurrency.in_flight(Endpoint::PlayerBans), 0);
+        assert_eq!(concurrency.limit(Endpoint::PlayerSummaries), 8);
+    }
+
+    #[cfg(feature = "body_capture")]
+    #[tokio::test]
+    async fn captures_bodies_that_fail_to_parse() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, ResponseTemplate};
+
+        let steam = MockSteam::start().await;
+        let body = format!(r#"{{"players": "{} changed"}}"#, super::API_KEY);
+        Mock::given(path(Endpoint::PlayerBans.path()))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        let client = steam
+            .client_builder()
+            .capture_body(24)
+            .build()
+            .await
+            .unwrap();
+
+        let ids = [SteamId(76561198196615742)];
+        assert!(client.get_player_bans(ids[..].into()).await.is_err());
+        let errors = client.last_errors(1);
+        assert_eq!(errors[0].status, Some(200));
+        assert_eq!(
+            errors[0].body.as_deref(),
+            Some(r#"{"players": "[redacted] "#)
+        );
+    }
+
+    #[cfg(feature = "user_search")]
+    #[tokio::test]
+    async fn requests_community_pages_in_language() {
+        use wiremock::matchers::{header, path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let steam = MockSteam::start().await;
+        Mock::given(path(Endpoint::UserSearch.path()))
+            .and(query_param("l", "german"))
+            .and(header("accept-language", "de"))
+            .respond_with(ResponseTemplate::new(500))
+            .with_priority(1)
+            .mount(steam.server())
+            .await;
+        let client = steam.client().await;
+        assert_eq!(client.language(), "english");
+        client.get_search_page("sauce", 1).await.unwrap();
+
+        let client = steam
+            .client_builder()
+            .language("german")
+            .build()
+            .await
+            .unwrap();
+        assert!(client.get_search_page("sauce", 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn takes_turns_between_local_addresses() {
+        use std::net::Ipv4Addr;
+
+        use crate::dns::IpVersion;
+
+        let steam = MockSteam::start().await;
+        let client = steam
+            .client_builder()
+            .ip_version(IpVersion::V4Only)
+            .local_address(Ipv4Addr::LOCALHOST.into())
+            .local_address(Ipv4Addr::new(127, 0, 0, 2).into())
+            .build()
+            .await
+            .unwrap();
+
+        let ids = [SteamId(76561198196615742)];
+        for _ in 0..3 {
+            client.get_player_bans(ids[..].into()).await.unwrap();
+        }
+        assert_eq!(steam.received(Endpoint::PlayerBans).await, 3);
+    }
+
+    #[tokio::test]
+    async fn validates_sessions() {
+        let steam = MockSteam::start().await;
+        let status = steam.client().await.validate_session().await.unwrap();
+        assert!(status.valid);
+
+        // nothing answers searches with a session id
+        let steam = MockSteam::start_empty().await;
+        let status = steam.client().await.validate_session().await.unwrap();
+        assert!(!status.valid);
+        assert_eq!(status.status, 404);
+    }
+
+    #[tokio::test]
+    async fn clones_count_separately() {
+        fn assert_handle<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+        let steam = MockSteam::start().await;
+        steam.mount_status(Endpoint::PlayerBans, 429, 1).await;
+        let client = steam.client().await;
+        let clone = client.clone();
+        assert_handle(&clone);
+
+        let ids = [SteamId(76561198196615742)];
+        tokio::spawn(async move { clone.get_player_bans(ids[..].into()).await })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(client.total_retries(), 0);
+        assert_eq!(client.global_stats().total_retries(), 1);
+        assert_eq!(
+            client.global_stats().endpoint_retries(Endpoint::PlayerBans),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_custom_json() {
+        let steam = MockSteam::start().await;
+        let body = json!({ "response": { "players": [] } });
+        steam.mount_json(Endpoint::PlayerSummaries, &body).await;
+        let client = steam.client().await;
+
+        let ids = [SteamId(76561198196615742)];
+        let summaries = client.get_player_summaries(ids[..].into()).await.unwrap();
+        assert!(summaries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn malformed_fails() {
+        let steam = MockSteam::start().await;
+        steam.mount_malformed(Endpoint::PlayerSteamLevel, 1).await;
+        let client = steam.client().await;
+
+        let id = SteamId(76561198196615742);
+        assert!(client.get_player_steam_level(id).await.is_err());
+    }
+}
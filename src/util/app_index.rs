@@ -0,0 +1,235 @@
+//! Resolve app names to app ids offline, using the list from
+//! [`Client::get_app_list`](crate::Client::get_app_list).
+//!
+//! ```
+//! use steam_api_concurrent::api::App;
+//! use steam_api_concurrent::util::app_index::AppIndex;
+//!
+//! let apps = vec![
+//!     App { app_id: 730, name: "Counter-Strike 2".to_string() },
+//!     App { app_id: 10, name: "Counter-Strike".to_string() },
+//! ];
+//! let index = AppIndex::new(apps);
+//! assert_eq!(index.get("counter-strike 2")[0].app_id, 730);
+//! assert_eq!(index.search("counter strik 2", 1)[0].0.app_id, 730);
+//! ```
+//!
+//! The index serializes as the list of apps, so it can be stored on disk
+//! and is rebuilt when it's deserialized.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::App;
+
+/// Minimum score of fuzzy matches that aren't prefix matches
+const MIN_TRIGRAM_SCORE: f32 = 0.3;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "Vec<App>", into = "Vec<App>")]
+pub struct AppIndex {
+    apps: Vec<App>,
+    /// Normalized name → indices into `apps`
+    names: HashMap<String, Vec<usize>>,
+    /// Trigram → indices into `apps`
+    trigrams: HashMap<[char; 3], Vec<usize>>,
+    /// Number of distinct trigrams of each app
+    trigram_counts: Vec<usize>,
+}
+
+/// Lowercase, replace everything that isn't alphanumeric with spaces and collapse them,
+/// so `Counter-Strike: Source` and `counter strike source` are the same
+fn normalize(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>();
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The distinct trigrams of a normalized name, padded so short names have some too
+fn trigrams(normalized: &str) -> Vec<[char; 3]> {
+    let padded = format!("  {} ", normalized).chars().collect::<Vec<_>>();
+    let mut trigrams = padded
+        .windows(3)
+        .map(|w| [w[0], w[1], w[2]])
+        .collect::<Vec<_>>();
+    trigrams.sort_unstable();
+    trigrams.dedup();
+    trigrams
+}
+
+impl AppIndex {
+    pub fn new(apps: Vec<App>) -> Self {
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut trigram_index: HashMap<[char; 3], Vec<usize>> = HashMap::new();
+        let mut trigram_counts = Vec::with_capacity(apps.len());
+
+        for (i, app) in apps.iter().enumerate() {
+            let normalized = normalize(&app.name);
+            let app_trigrams = trigrams(&normalized);
+            trigram_counts.push(app_trigrams.len());
+            for trigram in app_trigrams {
+                trigram_index.entry(trigram).or_default().push(i);
+            }
+            names.entry(normalized).or_default().push(i);
+        }
+
+        Self {
+            apps,
+            names,
+            trigrams: trigram_index,
+            trigram_counts,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.apps.len()
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.apps.is_empty()
+    }
+    pub fn apps(&self) -> &[App] {
+        &self.apps
+    }
+
+    /// Apps named exactly `name`, ignoring case and punctuation
+    pub fn get(&self, name: &str) -> Vec<&App> {
+        self.names
+            .get(&normalize(name))
+            .map(|indices| indices.iter().map(|&i| &self.apps[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Up to `limit` apps with a name similar to `query`, best match first.
+    ///
+    /// The score is `1.0` for exact matches, `0.9` for names starting with `query` and
+    /// the trigram similarity (`0.0..1.0`) otherwise.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(&App, f32)> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let query_trigrams = trigrams(&query);
+
+        let mut hits: HashMap<usize, usize> = HashMap::new();
+        for trigram in &query_trigrams {
+            for &i in self.trigrams.get(trigram).into_iter().flatten() {
+                *hits.entry(i).or_default() += 1;
+            }
+        }
+
+        let mut results = hits
+            .into_iter()
+            .filter_map(|(i, hits)| {
+                let name = normalize(&self.apps[i].name);
+                let score = if name == query {
+                    1.0
+                } else if name.starts_with(&query) {
+                    0.9
+                } else {
+                    let union = query_trigrams.len() + self.trigram_counts[i] - hits;
+                    hits as f32 / union as f32
+                };
+                (score >= MIN_TRIGRAM_SCORE).then_some((i, score))
+            })
+            .collect::<Vec<_>>();
+
+        // Shorter names first for equal scores, so `Portal` comes before `Portal 2`
+        results.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| self.apps[*a].name.len().cmp(&self.apps[*b].name.len()))
+                .then_with(|| self.apps[*a].app_id.cmp(&self.apps[*b].app_id))
+        });
+        results.truncate(limit);
+        results
+            .into_iter()
+            .map(|(i, score)| (&self.apps[i], score))
+            .collect()
+    }
+}
+
+impl From<Vec<App>> for AppIndex {
+    fn from(apps: Vec<App>) -> Self {
+        Self::new(apps)
+    }
+}
+
+impl From<AppIndex> for Vec<App> {
+    fn from(index: AppIndex) -> Self {
+        index.apps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, AppIndex};
+    use crate::api::App;
+
+    fn index() -> AppIndex {
+        let apps = [
+            (10, "Counter-Strike"),
+            (240, "Counter-Strike: Source"),
+            (730, "Counter-Strike 2"),
+            (440, "Team Fortress 2"),
+            (400, "Portal"),
+            (620, "Portal 2"),
+        ];
+        let apps = apps
+            .into_iter()
+            .map(|(app_id, name)| App {
+                app_id,
+                name: name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        AppIndex::new(apps)
+    }
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(normalize("Counter-Strike: Source"), "counter strike source");
+        assert_eq!(normalize("  PORTAL   2 "), "portal 2");
+    }
+
+    #[test]
+    fn gets_exact() {
+        let index = index();
+        assert_eq!(index.get("counter-strike 2")[0].app_id, 730);
+        assert_eq!(index.get("COUNTER STRIKE source")[0].app_id, 240);
+        assert!(index.get("counter").is_empty());
+    }
+
+    #[test]
+    fn searches_fuzzy() {
+        let index = index();
+
+        let results = index.search("portal", 10);
+        assert_eq!(results[0].0.app_id, 400);
+        assert_eq!(results[0].1, 1.0);
+        assert_eq!(results[1].0.app_id, 620);
+        assert_eq!(results[1].1, 0.9);
+
+        let results = index.search("team fortess", 1);
+        assert_eq!(results[0].0.app_id, 440);
+
+        assert!(index.search("cyberpunk", 10).is_empty());
+        assert!(index.search("", 10).is_empty());
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let index = index();
+        let json = serde_json::to_string(&index).unwrap();
+        let decoded: AppIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), index.len());
+        assert_eq!(decoded.get("portal 2")[0].app_id, 620);
+    }
+}
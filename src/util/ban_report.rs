@@ -0,0 +1,121 @@
+//! Join bans with recently played games to find accounts that were banned while active.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{OwnedGames, PlayerBans};
+use crate::SteamId;
+
+/// Playtime in the last two weeks is all the API reports about recent activity
+pub const RECENT_PLAYTIME_DAYS: i32 = 14;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BannedWhileActive {
+    pub steam_id: SteamId,
+    pub app_id: u32,
+    pub days_since_last_ban: i32,
+    pub number_of_vac_bans: i32,
+    pub number_of_game_bans: i32,
+    /// Minutes played in `app_id` in the last two weeks
+    pub playtime_2weeks: u64,
+}
+
+/// Accounts with a VAC or game ban in the last `max_days_since_ban` days,
+/// that played `app_id` in the last two weeks.
+///
+/// `max_days_since_ban` should be at most [`RECENT_PLAYTIME_DAYS`], otherwise the
+/// playtime might be from after the ban. Accounts with private game details are skipped.
+/// The result is sorted by [`SteamId`].
+pub fn banned_while_active(
+    bans: &PlayerBans,
+    games: &HashMap<SteamId, OwnedGames>,
+    app_id: u32,
+    max_days_since_ban: i32,
+) -> Vec<BannedWhileActive> {
+    let mut report = bans
+        .iter()
+        .filter(|(_, ban)| ban.vac_banned || ban.number_of_game_bans > 0)
+        .filter(|(_, ban)| ban.days_since_last_ban <= max_days_since_ban)
+        .filter_map(|(steam_id, ban)| {
+            let game = games.get(steam_id)?.as_inner_ref()?.get(&app_id)?;
+            let playtime_2weeks = game.playtime_2weeks.filter(|&minutes| minutes > 0)?;
+            Some(BannedWhileActive {
+                steam_id: *steam_id,
+                app_id,
+                days_since_last_ban: ban.days_since_last_ban,
+                number_of_vac_bans: ban.number_of_vac_bans,
+                number_of_game_bans: ban.number_of_game_bans,
+                playtime_2weeks,
+            })
+        })
+        .collect::<Vec<_>>();
+    report.sort_unstable_by_key(|entry| entry.steam_id);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::{banned_while_active, RECENT_PLAYTIME_DAYS};
+    use crate::api::{OwnedGames, PlayerBans};
+    use crate::test_util::player_ban_json;
+    use crate::SteamId;
+
+    fn ban(id: u64, vac: bool, game_bans: i32, days: i32) -> serde_json::Value {
+        player_ban_json(json!({
+            "SteamId": id.to_string(),
+            "VACBanned": vac,
+            "NumberOfVACBans": i32::from(vac),
+            "DaysSinceLastBan": days,
+            "NumberOfGameBans": game_bans
+        }))
+    }
+
+    fn games(playtime_2weeks: Option<u64>) -> OwnedGames {
+        let json = json!({
+            "730": {
+                "appid": 730,
+                "name": "Counter-Strike 2",
+                "playtime_forever": 1000,
+                "playtime_2weeks": playtime_2weeks
+            }
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn flags_recent_bans() {
+        let bans = json!({
+            "76561198000000001": ban(76561198000000001, true, 0, 3),
+            "76561198000000002": ban(76561198000000002, false, 1, 10),
+            "76561198000000003": ban(76561198000000003, true, 0, 300),
+            "76561198000000004": ban(76561198000000004, false, 0, 0),
+            "76561198000000005": ban(76561198000000005, true, 0, 1),
+            "76561198000000006": ban(76561198000000006, true, 0, 1),
+        });
+        let bans: PlayerBans = serde_json::from_value(bans).unwrap();
+
+        let private: OwnedGames = serde_json::from_value(json!(null)).unwrap();
+        let games = HashMap::from([
+            (SteamId(76561198000000001), games(Some(300))),
+            (SteamId(76561198000000002), games(Some(20))),
+            (SteamId(76561198000000003), games(Some(300))),
+            (SteamId(76561198000000004), games(Some(300))),
+            (SteamId(76561198000000005), games(None)),
+            (SteamId(76561198000000006), private),
+        ]);
+
+        let report = banned_while_active(&bans, &games, 730, RECENT_PLAYTIME_DAYS);
+        let ids = report.iter().map(|e| e.steam_id).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [SteamId(76561198000000001), SteamId(76561198000000002)]
+        );
+        assert_eq!(report[0].playtime_2weeks, 300);
+        assert!(banned_while_active(&bans, &games, 440, RECENT_PLAYTIME_DAYS).is_empty());
+    }
+}
@@ -1,58 +1,203 @@
-pub trait BitChunks<T> {
-    const CHUNK_BITS: u32;
-    const MAX_CHUNKS: usize;
-    const CHUNK_MASK: T;
+//! Split integers into fixed width chunks, the building block of codes like
+//! friend codes, invite links or match sharing codes.
+//!
+//! ```
+//! use steam_api_concurrent::util::bit_chunks::{Chunks, ChunksU5};
+//!
+//! // least significant chunk first
+//! assert_eq!(ChunksU5::new(0b11_00001).collect::<Vec<_>>(), [0b00001, 0b11]);
+//! // or most significant first
+//! assert_eq!(ChunksU5::new(0b11_00001).rev().collect::<Vec<_>>(), [0b11, 0b00001]);
+//!
+//! let code = u128::MAX;
+//! assert_eq!(Chunks::<6, u128>::new(code).len(), Chunks::<6, u128>::MAX_CHUNKS);
+//! ```
+
+use std::ops::{BitAnd, Not, Shl, Shr, Sub};
+
+/// An unsigned integer that can be split into [`Chunks`]
+pub trait Word:
+    Copy
+    + Eq
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + Sub<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const BITS: u32;
+    const ZERO: Self;
+    const ONE: Self;
+    fn leading_zeros(self) -> u32;
 }
 
-macro_rules! impl_bit_chunks {
-    ($SelfT:ident, $ActualT:ty, $ItemT:ty, $BITS:expr) => {
-        pub struct $SelfT(pub $ActualT);
-        impl BitChunks<$ActualT> for $SelfT {
-            const CHUNK_BITS: u32 = $BITS;
-            const MAX_CHUNKS: usize = <$ActualT>::BITS.div_ceil(Self::CHUNK_BITS) as usize;
-            const CHUNK_MASK: $ActualT = (1 << Self::CHUNK_BITS) - 1;
-        }
-        impl Iterator for $SelfT {
-            type Item = $ItemT;
-            fn next(&mut self) -> Option<Self::Item> {
-                let out = match self.0 {
-                    0 => None,
-                    _ => Some((self.0 & Self::CHUNK_MASK) as Self::Item),
-                };
-                self.0 >>= Self::CHUNK_BITS;
-                out
-            }
-            fn size_hint(&self) -> (usize, Option<usize>) {
-                let bits_left = (<$ActualT>::BITS - self.0.leading_zeros()) as usize;
-                let len = bits_left.div_ceil(Self::CHUNK_BITS as usize);
-                (len, Some(len))
+macro_rules! impl_word {
+    ($($T:ty),+) => {$(
+        impl Word for $T {
+            const BITS: u32 = <$T>::BITS;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn leading_zeros(self) -> u32 {
+                <$T>::leading_zeros(self)
             }
         }
-        impl ExactSizeIterator for $SelfT {}
-    };
+    )+};
+}
+
+impl_word!(u32, u64, u128);
+
+/// Iterate over the `BITS` wide chunks of a value, least significant chunk first.
+///
+/// Leading chunks that are zero are skipped, so `0` has no chunks at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunks<const BITS: u32, T> {
+    value: T,
+    /// Chunks left in `value`
+    len: u32,
+}
+
+pub type ChunksU4 = Chunks<4, u64>;
+pub type ChunksU5 = Chunks<5, u64>;
+pub type ChunksU6 = Chunks<6, u64>;
+
+impl<const BITS: u32, T: Word> Chunks<BITS, T> {
+    const VALID_BITS: () = assert!(BITS > 0 && BITS <= T::BITS, "invalid chunk width");
+
+    pub const CHUNK_BITS: u32 = BITS;
+    /// The number of chunks of a value with no leading zeros
+    pub const MAX_CHUNKS: usize = T::BITS.div_ceil(BITS) as usize;
+
+    pub fn new(value: T) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_BITS;
+        let significant = T::BITS - value.leading_zeros();
+        Self {
+            value,
+            len: significant.div_ceil(BITS),
+        }
+    }
+
+    /// The lowest `BITS` bits set
+    pub fn chunk_mask() -> T {
+        if BITS == T::BITS {
+            !T::ZERO
+        } else {
+            (T::ONE << BITS) - T::ONE
+        }
+    }
+}
+
+impl<const BITS: u32, T: Word> Iterator for Chunks<BITS, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let out = self.value & Self::chunk_mask();
+        self.value = if BITS == T::BITS {
+            T::ZERO
+        } else {
+            self.value >> BITS
+        };
+        self.len -= 1;
+        Some(out)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl<const BITS: u32, T: Word> DoubleEndedIterator for Chunks<BITS, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let shift = self.len * BITS;
+        let mask = Self::chunk_mask() << shift;
+        let out = (self.value & mask) >> shift;
+        self.value = self.value & !mask;
+        Some(out)
+    }
 }
 
-impl_bit_chunks!(ChunksU4, u64, u8, 4);
-impl_bit_chunks!(ChunksU5, u64, u8, 5);
-impl_bit_chunks!(ChunksU6, u64, u8, 6);
+impl<const BITS: u32, T: Word> ExactSizeIterator for Chunks<BITS, T> {}
 
+#[cfg(test)]
 mod test {
-    macro_rules! test_bit_chunks {
-        ($func:ident, $SelfT:ident, $ItemT:ty, $ActualT:ty, $MAX_CHUNKS:expr, $value:expr, $($expec:expr),+) => {
-            #[test]
-            fn $func() {
-                use super::{BitChunks, $SelfT};
-                assert_eq!(<$SelfT>::MAX_CHUNKS, $MAX_CHUNKS);
-                let arr: &[$ItemT] = &[$($expec),+];
-                for (chunk, exp) in $SelfT($value).zip(arr.iter()) {
-                    assert_eq!(*exp, chunk);
+    use proptest::prelude::*;
+
+    use super::{Chunks, ChunksU4, ChunksU5, ChunksU6};
+
+    /// Straight forward implementation to compare the iterators against
+    fn reference_chunks(value: u128, bits: u32) -> Vec<u128> {
+        let significant = u128::BITS - value.leading_zeros();
+        let len = significant.div_ceil(bits);
+        let mask = if bits == u128::BITS {
+            u128::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        (0..len)
+            .map(|i| (value.checked_shr(i * bits).unwrap_or(0)) & mask)
+            .collect()
+    }
+
+    fn check<const BITS: u32>(value: u128) -> Result<(), TestCaseError> {
+        let expected = reference_chunks(value, BITS);
+        let chunks = Chunks::<BITS, u128>::new(value);
+        prop_assert_eq!(chunks.len(), expected.len());
+        let reversed = Chunks::<BITS, u128>::new(value).rev().collect::<Vec<_>>();
+        prop_assert_eq!(reversed, expected.iter().rev().copied().collect::<Vec<_>>());
+        prop_assert_eq!(chunks.collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn u64_matches_reference(value in any::<u64>()) {
+            let expected = |bits| reference_chunks(value as u128, bits);
+            prop_assert_eq!(ChunksU4::new(value).map(u128::from).collect::<Vec<_>>(), expected(4));
+            prop_assert_eq!(ChunksU5::new(value).map(u128::from).collect::<Vec<_>>(), expected(5));
+            prop_assert_eq!(ChunksU6::new(value).map(u128::from).collect::<Vec<_>>(), expected(6));
+        }
+
+        #[test]
+        fn u128_matches_reference(value in any::<u128>()) {
+            check::<1>(value)?;
+            check::<5>(value)?;
+            check::<7>(value)?;
+            check::<64>(value)?;
+            check::<128>(value)?;
+        }
+
+        #[test]
+        fn mixed_ends(value in any::<u32>()) {
+            let mut chunks = Chunks::<3, u32>::new(value);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            while let Some(chunk) = chunks.next() {
+                front.push(chunk);
+                if let Some(chunk) = chunks.next_back() {
+                    back.push(chunk);
                 }
-                assert_eq!($SelfT($value).skip(arr.len()).next(), None);
             }
-        };
+            back.reverse();
+            front.extend(back);
+            prop_assert_eq!(front, Chunks::<3, u32>::new(value).collect::<Vec<_>>());
+        }
     }
 
-    test_bit_chunks!(u4_works, ChunksU4, u8, u64, 16, 0xFFFF, 0x0F, 0x0F, 0x0F, 0x0F);
-    test_bit_chunks!(u5_works, ChunksU5, u8, u64, 13, 0xFFFF, 0x1F, 0x1F, 0x1F, 0x01);
-    test_bit_chunks!(u6_works, ChunksU6, u8, u64, 11, 0xFFFF, 0x3F, 0x3F, 0x0F);
+    #[test]
+    fn max_chunks() {
+        assert_eq!(ChunksU4::MAX_CHUNKS, 16);
+        assert_eq!(ChunksU5::MAX_CHUNKS, 13);
+        assert_eq!(ChunksU6::MAX_CHUNKS, 11);
+        assert_eq!(Chunks::<5, u128>::MAX_CHUNKS, 26);
+        assert_eq!(
+            ChunksU5::new(0xFFFF).collect::<Vec<_>>(),
+            [0x1F, 0x1F, 0x1F, 0x01]
+        );
+        assert_eq!(ChunksU5::new(0).next(), None);
+    }
 }
@@ -0,0 +1,220 @@
+//! Score how likely an account is a throwaway or bot account, from the signals the API gives.
+//!
+//! Every [`Signal`] that applies adds its weight to the score. With the default [`Weights`]
+//! the score is in `0.0..=1.0`, but the weights can be set to anything that suits the use.
+//! Signals are only checked if the data for them is given, e.g. a profile without
+//! a [`SteamLevel`] never gets [`Signal::LevelZero`].
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{FriendsList, PlayerBan, PlayerSummary, SteamLevel};
+use crate::ProfilePrivacy;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// The account was created less than [`Weights::new_account_days`] ago
+    NewAccount,
+    /// The account never set an avatar
+    DefaultAvatar,
+    /// The profile is private
+    PrivateProfile,
+    /// The friends list is private
+    PrivateFriends,
+    /// The account never leveled up
+    LevelZero,
+    VacBanned,
+    GameBanned,
+    CommunityBanned,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Weights {
+    pub new_account: f64,
+    /// Accounts younger than this are new
+    pub new_account_days: i64,
+    pub default_avatar: f64,
+    pub private_profile: f64,
+    pub private_friends: f64,
+    pub level_zero: f64,
+    pub vac_banned: f64,
+    pub game_banned: f64,
+    pub community_banned: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            new_account: 0.2,
+            new_account_days: 30,
+            default_avatar: 0.15,
+            private_profile: 0.15,
+            private_friends: 0.1,
+            level_zero: 0.15,
+            vac_banned: 0.1,
+            game_banned: 0.1,
+            community_banned: 0.05,
+        }
+    }
+}
+
+impl Weights {
+    pub const fn weight(&self, signal: Signal) -> f64 {
+        match signal {
+            Signal::NewAccount => self.new_account,
+            Signal::DefaultAvatar => self.default_avatar,
+            Signal::PrivateProfile => self.private_profile,
+            Signal::PrivateFriends => self.private_friends,
+            Signal::LevelZero => self.level_zero,
+            Signal::VacBanned => self.vac_banned,
+            Signal::GameBanned => self.game_banned,
+            Signal::CommunityBanned => self.community_banned,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The sum of the weights of `signals`
+    pub score: f64,
+    /// The signals that applied
+    pub signals: Vec<Signal>,
+}
+
+/// The signals that apply to an account at `now`
+///
+/// `friends` is the result of a [`Client::get_player_friends`](crate::Client::get_player_friends)
+/// attempt, like in [`ProfilePrivacy::classify`].
+pub fn signals(
+    summary: Option<&PlayerSummary>,
+    ban: Option<&PlayerBan>,
+    level: Option<SteamLevel>,
+    friends: Option<&FriendsList>,
+    new_account_days: i64,
+    now: DateTime<Utc>,
+) -> Vec<Signal> {
+    let mut signals = Vec::new();
+
+    if let Some(summary) = summary {
+        let created = summary.time_created().map(|created| created.to_utc());
+        if created.is_some_and(|created| now - created < Duration::days(new_account_days)) {
+            signals.push(Signal::NewAccount);
+        }
+        if summary.has_custom_avatar() == Some(false) {
+            signals.push(Signal::DefaultAvatar);
+        }
+    }
+    if summary.is_some()
+        && ProfilePrivacy::classify(summary, ban, friends) == ProfilePrivacy::Private
+    {
+        signals.push(Signal::PrivateProfile);
+    }
+    if friends.is_some_and(|friends| friends.as_inner_ref().is_none()) {
+        signals.push(Signal::PrivateFriends);
+    }
+    if level.and_then(SteamLevel::lvl) == Some(0) {
+        signals.push(Signal::LevelZero);
+    }
+    if let Some(ban) = ban {
+        if ban.vac_banned {
+            signals.push(Signal::VacBanned);
+        }
+        if ban.number_of_game_bans > 0 {
+            signals.push(Signal::GameBanned);
+        }
+        if ban.community_banned {
+            signals.push(Signal::CommunityBanned);
+        }
+    }
+    signals
+}
+
+/// Score an account with `weights`, see [`signals`]
+pub fn score(
+    weights: &Weights,
+    summary: Option<&PlayerSummary>,
+    ban: Option<&PlayerBan>,
+    level: Option<SteamLevel>,
+    friends: Option<&FriendsList>,
+) -> Score {
+    score_at(weights, summary, ban, level, friends, Utc::now())
+}
+
+/// Like [`score`], but with the account age taken at `now`
+pub fn score_at(
+    weights: &Weights,
+    summary: Option<&PlayerSummary>,
+    ban: Option<&PlayerBan>,
+    level: Option<SteamLevel>,
+    friends: Option<&FriendsList>,
+    now: DateTime<Utc>,
+) -> Score {
+    let signals = signals(summary, ban, level, friends, weights.new_account_days, now);
+    let score = signals.iter().map(|&signal| weights.weight(signal)).sum();
+    Score { score, signals }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::json;
+
+    use super::{score_at, Signal, Weights};
+    use crate::api::{FriendsList, PlayerBan, PlayerSummary, SteamLevel};
+    use crate::constants::DEFAULT_AVATAR_HASH;
+    use crate::test_util::{player_ban, player_summary};
+
+    fn summary(created: i64, avatar_hash: &str, visibility: u8) -> PlayerSummary {
+        player_summary(json!({
+            "communityvisibilitystate": visibility,
+            "avatarhash": avatar_hash,
+            "timecreated": created
+        }))
+    }
+
+    fn ban(vac: bool) -> PlayerBan {
+        player_ban(json!({ "VACBanned": vac, "NumberOfVACBans": i32::from(vac) }))
+    }
+
+    #[test]
+    fn scores_signals() {
+        let now = Utc.timestamp_opt(1697210511, 0).unwrap();
+        let week_ago = (now - Duration::days(7)).timestamp();
+        let weights = Weights::default();
+        let private_friends: FriendsList = serde_json::from_value(json!(null)).unwrap();
+
+        let fresh = summary(week_ago, DEFAULT_AVATAR_HASH, 1);
+        let score = score_at(
+            &weights,
+            Some(&fresh),
+            Some(&ban(true)),
+            Some(serde_json::from_value::<SteamLevel>(json!(0)).unwrap()),
+            Some(&private_friends),
+            now,
+        );
+        assert_eq!(
+            score.signals,
+            [
+                Signal::NewAccount,
+                Signal::DefaultAvatar,
+                Signal::PrivateProfile,
+                Signal::PrivateFriends,
+                Signal::LevelZero,
+                Signal::VacBanned,
+            ]
+        );
+        assert!((score.score - 0.85).abs() < 1e-9);
+
+        let old = summary(1065571200, "3cc9dd106fc8b72237056f37c4e9597d27637c6a", 3);
+        let score = score_at(&weights, Some(&old), Some(&ban(false)), None, None, now);
+        assert!(score.signals.is_empty());
+        assert_eq!(score.score, 0.0);
+
+        let weights = Weights {
+            default_avatar: 5.0,
+            ..Weights::default()
+        };
+        let score = score_at(&weights, Some(&fresh), None, None, None, now);
+        assert!((score.score - 5.35).abs() < 1e-9);
+    }
+}
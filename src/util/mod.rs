@@ -1,5 +1,18 @@
-#[cfg(feature = "friend_code")]
+pub mod app_index;
+
+pub mod ban_report;
+
+#[cfg(feature = "heuristics")]
+pub mod heuristics;
+
+#[cfg(feature = "codec")]
 pub mod bit_chunks;
 
+#[cfg(feature = "anonymize")]
+pub mod pseudonym;
+
+#[cfg(feature = "bincode")]
+pub mod snapshot;
+
 mod visibility;
 pub use visibility::Visibility;
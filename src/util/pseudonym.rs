@@ -0,0 +1,123 @@
+//! Replace [`SteamId`]s with pseudonyms before publishing a dataset.
+//!
+//! A pseudonym is the HMAC-SHA256 of the id under a secret key, so the same id gets the
+//! same pseudonym everywhere in an export and tables can still be joined on it. Without
+//! the key the pseudonyms can't be linked back to accounts, not even by hashing every id.
+//!
+//! ```
+//! use steam_api_concurrent::util::pseudonym::Pseudonymizer;
+//! use steam_api_concurrent::SteamId;
+//!
+//! let pseudonymizer = Pseudonymizer::new(b"a secret that is never published");
+//! let id = SteamId(76561197960287930);
+//! assert_eq!(pseudonymizer.pseudonym(id), pseudonymizer.pseudonym(id));
+//! assert_eq!(pseudonymizer.pseudonym(id).to_string().len(), 32);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use serde::{Serialize, Serializer};
+use sha2::Sha256;
+
+use crate::SteamId;
+
+/// The first 128 bits of the HMAC, displayed and serialized as lowercase hex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pseudonym([u8; 16]);
+
+impl Pseudonym {
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Pseudonym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl Serialize for Pseudonym {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Maps ids to pseudonyms with a secret key, see the [module docs](self)
+#[derive(Clone)]
+pub struct Pseudonymizer {
+    mac: Hmac<Sha256>,
+}
+
+impl fmt::Debug for Pseudonymizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // don't leak the key into logs
+        f.debug_struct("Pseudonymizer").finish_non_exhaustive()
+    }
+}
+
+impl Pseudonymizer {
+    /// Use a key of at least 32 random bytes and keep it for the whole dataset,
+    /// a new key gives every id a new pseudonym
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(key).expect("hmac takes keys of any length"),
+        }
+    }
+
+    pub fn pseudonym(&self, id: SteamId) -> Pseudonym {
+        let mut mac = self.mac.clone();
+        mac.update(&id.0.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        Pseudonym(bytes)
+    }
+
+    /// The pseudonyms of `ids`, to replace every column of an export with the same mapping
+    pub fn pseudonyms(
+        &self,
+        ids: impl IntoIterator<Item = SteamId>,
+    ) -> HashMap<SteamId, Pseudonym> {
+        ids.into_iter().map(|id| (id, self.pseudonym(id))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pseudonymizer;
+    use crate::SteamId;
+
+    #[test]
+    fn is_stable_per_key() {
+        let fst = Pseudonymizer::new(b"key");
+        let snd = Pseudonymizer::new(b"other key");
+        let id = SteamId(76561197960287930);
+
+        assert_eq!(fst.pseudonym(id), Pseudonymizer::new(b"key").pseudonym(id));
+        assert_ne!(fst.pseudonym(id), snd.pseudonym(id));
+        assert_ne!(fst.pseudonym(id), fst.pseudonym(SteamId(76561197960287931)));
+        // HMAC-SHA256 of the id as big endian bytes
+        assert_eq!(
+            fst.pseudonym(id).to_string(),
+            "eda8482f091dc2827f164ef3da58b88c"
+        );
+    }
+
+    #[test]
+    fn serializes_as_hex() {
+        let pseudonymizer = Pseudonymizer::new(b"key");
+        let pseudonyms = pseudonymizer.pseudonyms([SteamId(1), SteamId(2), SteamId(1)]);
+        assert_eq!(pseudonyms.len(), 2);
+
+        let json = serde_json::to_string(&pseudonyms[&SteamId(1)]).unwrap();
+        assert_eq!(json, format!("\"{}\"", pseudonyms[&SteamId(1)]));
+        assert!(format!("{:?}", pseudonymizer).starts_with("Pseudonymizer"));
+    }
+}
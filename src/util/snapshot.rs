@@ -0,0 +1,221 @@
+//! Compact binary encoding for snapshots and IPC between workers.
+//!
+//! Uses [`bincode`] under the hood. The models serialize their raw Steam representation
+//! (timestamps as integers, enums as their API values) when the format is not human readable,
+//! so everything that can be parsed from the API can also be round-tripped through here.
+//!
+//! [`SnapshotStore`] keeps encoded snapshots on disk, partitioned by namespace and day,
+//! e.g. `bans/2024-06-01/watchlist.bin`.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    Bincode(#[from] bincode::Error),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// Namespaces and names end up in paths, so they can't contain separators or be empty
+    #[error("invalid snapshot name: {0:?}")]
+    InvalidName(String),
+}
+type Result<T> = std::result::Result<T, SnapshotError>;
+
+/// Encode `value` into its compact binary representation
+pub fn to_bytes<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(bincode::serialize(value)?)
+}
+
+/// Decode a value previously encoded with [`to_bytes`]
+pub fn from_bytes<T>(bytes: &[u8]) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    Ok(bincode::deserialize(bytes)?)
+}
+
+/// How partitions are named, e.g. `2024-06-01`
+const PARTITION_FORMAT: &str = "%Y-%m-%d";
+
+/// Snapshots on disk under `root/{namespace}/{day}/{name}.bin`.
+///
+/// A tracker saves its state once per day and loads the state as of any day it has,
+/// [`SnapshotStore::retain`] deletes the days that are no longer needed.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+fn check_name(name: &str) -> Result<&str> {
+    let valid = !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']);
+    if valid {
+        Ok(name)
+    } else {
+        Err(SnapshotError::InvalidName(name.to_string()))
+    }
+}
+
+impl SnapshotStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The file the snapshot `name` of `day` is kept in
+    pub fn path(&self, namespace: &str, day: NaiveDate, name: &str) -> Result<PathBuf> {
+        Ok(self
+            .root
+            .join(check_name(namespace)?)
+            .join(day.format(PARTITION_FORMAT).to_string())
+            .join(format!("{}.bin", check_name(name)?)))
+    }
+
+    /// Save `value` as the snapshot `name` of `day`, replacing one saved earlier that day
+    pub fn save<T>(&self, namespace: &str, day: NaiveDate, name: &str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let path = self.path(namespace, day, name)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // write to a temporary file first, so a crash never leaves a truncated snapshot
+        let tmp = path.with_extension("bin.tmp");
+        fs::write(&tmp, to_bytes(value)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// The days with partitions in `namespace`, oldest first
+    pub fn days(&self, namespace: &str) -> Result<Vec<NaiveDate>> {
+        let dir = self.root.join(check_name(namespace)?);
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut days = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name();
+            // other files in the namespace aren't partitions
+            if let Some(day) = name
+                .to_str()
+                .and_then(|name| NaiveDate::parse_from_str(name, PARTITION_FORMAT).ok())
+            {
+                days.push(day);
+            }
+        }
+        days.sort_unstable();
+        Ok(days)
+    }
+
+    /// The snapshot `name` as it was on `day`, i.e. from the latest partition up to `day` that
+    /// has it, together with the day it was saved. [`None`], if there's no such snapshot.
+    pub fn load_as_of<T>(
+        &self,
+        namespace: &str,
+        day: NaiveDate,
+        name: &str,
+    ) -> Result<Option<(NaiveDate, T)>>
+    where
+        T: DeserializeOwned,
+    {
+        for saved in self.days(namespace)?.into_iter().rev() {
+            if saved > day {
+                continue;
+            }
+            match fs::read(self.path(namespace, saved, name)?) {
+                Ok(bytes) => return Ok(Some((saved, from_bytes(&bytes)?))),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Delete the partitions of `namespace` older than `keep_days` days before `today`,
+    /// returns the deleted days
+    pub fn retain(
+        &self,
+        namespace: &str,
+        today: NaiveDate,
+        keep_days: u32,
+    ) -> Result<Vec<NaiveDate>> {
+        let oldest = today - chrono::Days::new(u64::from(keep_days));
+        let mut deleted = Vec::new();
+        for day in self.days(namespace)? {
+            if day >= oldest {
+                break;
+            }
+            let dir = self
+                .root
+                .join(namespace)
+                .join(day.format(PARTITION_FORMAT).to_string());
+            fs::remove_dir_all(dir)?;
+            deleted.push(day);
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::NaiveDate;
+
+    use super::{SnapshotError, SnapshotStore};
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+    }
+
+    #[test]
+    fn partitions_by_day() {
+        let dir = std::env::temp_dir().join(format!("steam_api_snapshots_{}", std::process::id()));
+        let store = SnapshotStore::new(&dir);
+
+        store
+            .save("bans", day(1), "watchlist", &vec![1_u64])
+            .unwrap();
+        store
+            .save("bans", day(3), "watchlist", &vec![1_u64, 2])
+            .unwrap();
+        store.save("bans", day(4), "other", &vec![3_u64]).unwrap();
+        assert!(dir.join("bans/2024-06-03/watchlist.bin").is_file());
+        assert_eq!(store.days("bans").unwrap(), [day(1), day(3), day(4)]);
+        assert!(store.days("summaries").unwrap().is_empty());
+
+        let load = |at| {
+            store
+                .load_as_of::<Vec<u64>>("bans", at, "watchlist")
+                .unwrap()
+        };
+        assert_eq!(load(day(2)), Some((day(1), vec![1])));
+        assert_eq!(load(day(5)), Some((day(3), vec![1, 2])));
+        assert_eq!(load(NaiveDate::from_ymd_opt(2024, 5, 31).unwrap()), None);
+
+        assert_eq!(store.retain("bans", day(5), 2).unwrap(), [day(1)]);
+        assert_eq!(store.days("bans").unwrap(), [day(3), day(4)]);
+
+        assert!(matches!(
+            store.save("../bans", day(1), "watchlist", &0_u64),
+            Err(SnapshotError::InvalidName(_))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
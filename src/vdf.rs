@@ -0,0 +1,663 @@
+//! Valve's KeyValues text format, also called VDF, and the `format=xml` output of the api.
+//!
+//! Both are parsed into [`KeyValues`], a tree of string values that keeps the order
+//! and duplicates of keys, since Steam repeats them for lists, e.g. `<player>` in xml.
+//!
+//! The tree implements [`Serialize`], and [`from_str`] deserializes VDF text into any
+//! [`Deserialize`](serde::Deserialize) type. Every value is a string in VDF, so numbers and
+//! bools are parsed from them when the type asks for one. [`to_string`] writes a tree back.
+//!
+//! ```
+//! use steam_api_concurrent::vdf;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Response {
+//!     player_level: u32,
+//! }
+//!
+//! let text = r#""response" { "player_level" "15" }"#;
+//! let response: std::collections::HashMap<String, Response> = vdf::from_str(text).unwrap();
+//! assert_eq!(response["response"].player_level, 15);
+//!
+//! let tree = vdf::parse(text).unwrap();
+//! assert_eq!(tree.get_path(&["response", "player_level"]).and_then(|v| v.as_str()), Some("15"));
+//! ```
+
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::SerializeMap;
+use serde::{forward_to_deserialize_any, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum VdfError {
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+
+    /// A token that isn't valid at its position, e.g. a `}` without an open object
+    #[error("unexpected '{found}' on line {line}")]
+    Unexpected { line: usize, found: char },
+
+    #[error("invalid xml ({0})")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("invalid text encoding ({0})")]
+    Encoding(#[from] quick_xml::encoding::EncodingError),
+
+    #[error("invalid json ({0})")]
+    Json(#[from] serde_json::Error),
+
+    /// The tree doesn't match the type it's deserialized into
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl serde::de::Error for VdfError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        VdfError::Custom(msg.to_string())
+    }
+}
+type Result<T> = std::result::Result<T, VdfError>;
+
+/// A value in a [`KeyValues`] tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Object(KeyValues),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(str) => Some(str),
+            Value::Object(_) => None,
+        }
+    }
+
+    pub const fn as_object(&self) -> Option<&KeyValues> {
+        match self {
+            Value::String(_) => None,
+            Value::Object(object) => Some(object),
+        }
+    }
+
+    /// The first value under `key` if this is an object
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+}
+
+/// Keys and their values in the order they appeared, keys may repeat
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyValues(Vec<(String, Value)>);
+
+impl KeyValues {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn push(&mut self, key: impl Into<String>, value: Value) {
+        self.0.push((key.into(), value));
+    }
+
+    /// The first value under `key`, which is compared case-insensitively like Valve does
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.get_all(key).next()
+    }
+
+    /// Every value under `key`, e.g. the elements of a list in xml
+    pub fn get_all<'a, 'k>(&'a self, key: &'k str) -> impl Iterator<Item = &'a Value> + 'k
+    where
+        'a: 'k,
+    {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Follow `path` through nested objects, taking the first value for each key
+    pub fn get_path(&self, path: &[&str]) -> Option<&Value> {
+        let (last, parents) = path.split_last()?;
+        let mut object = self;
+        for key in parents {
+            object = object.get(key)?.as_object()?;
+        }
+        object.get(last)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::String(str) => serializer.serialize_str(str),
+            Value::Object(object) => object.serialize(serializer),
+        }
+    }
+}
+
+/// Serialized as a map, repeated keys are kept, which not every format allows
+impl Serialize for KeyValues {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, VdfError> for Value {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl Value {
+    fn parse<T: std::str::FromStr>(self, expected: &str) -> Result<T> {
+        match self {
+            Value::String(str) => str
+                .trim()
+                .parse()
+                .map_err(|_| VdfError::Custom(format!("expected {}, got '{}'", expected, str))),
+            Value::Object(_) => Err(VdfError::Custom(format!(
+                "expected {}, got an object",
+                expected
+            ))),
+        }
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visitor.$visit(self.parse(stringify!($method))?)
+            }
+        )*
+    };
+}
+
+/// Strings are parsed for numbers, objects with keys `0`, `1`, ... can be sequences
+impl<'de> Deserializer<'de> for Value {
+    type Error = VdfError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Value::String(str) => visitor.visit_string(str),
+            Value::Object(object) => visitor.visit_map(MapDeserializer::new(object.0.into_iter())),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.as_str().map(str::trim) {
+            Some("1" | "true") => visitor.visit_bool(true),
+            Some("0" | "false" | "") => visitor.visit_bool(false),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self {
+            Value::Object(object) => {
+                let values = object.0.into_iter().map(|(_, value)| value);
+                visitor.visit_seq(SeqDeserializer::new(values))
+            }
+            value @ Value::String(_) => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self {
+            Value::String(str) => visitor.visit_enum(str.into_deserializer()),
+            value @ Value::Object(_) => value.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct
+        tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Parse KeyValues text into `T`, see the [module docs](self)
+pub fn from_str<T: DeserializeOwned>(text: &str) -> Result<T> {
+    T::deserialize(Value::Object(parse(text)?))
+}
+
+fn write_escaped(out: &mut String, str: &str) {
+    out.push('"');
+    for c in str.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_object(out: &mut String, object: &KeyValues, depth: usize) {
+    for (key, value) in object.iter() {
+        out.extend(std::iter::repeat_n('\t', depth));
+        write_escaped(out, key);
+        match value {
+            Value::String(str) => {
+                out.push('\t');
+                write_escaped(out, str);
+                out.push('\n');
+            }
+            Value::Object(object) => {
+                out.push('\n');
+                out.extend(std::iter::repeat_n('\t', depth));
+                out.push_str("{\n");
+                write_object(out, object, depth + 1);
+                out.extend(std::iter::repeat_n('\t', depth));
+                out.push_str("}\n");
+            }
+        }
+    }
+}
+
+/// Write `tree` as KeyValues text with tabs, which [`parse`] reads back into the same tree
+pub fn to_string(tree: &KeyValues) -> String {
+    let mut out = String::new();
+    write_object(&mut out, tree, 0);
+    out
+}
+
+impl fmt::Display for KeyValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_string(self))
+    }
+}
+
+/// The tokens of the text format
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    /// Skip whitespace, `// comments` and conditionals like `[$WIN32]`
+    fn skip_ignored(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '/' && self.chars.clone().nth(1) == Some('/') {
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.bump();
+                }
+            } else if c == '[' {
+                while self.bump().is_some_and(|c| c != ']') {}
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        let mut str = String::new();
+        loop {
+            match self.bump().ok_or(VdfError::UnexpectedEnd)? {
+                '"' => return Ok(str),
+                '\\' => match self.bump().ok_or(VdfError::UnexpectedEnd)? {
+                    'n' => str.push('\n'),
+                    't' => str.push('\t'),
+                    c => str.push(c),
+                },
+                c => str.push(c),
+            }
+        }
+    }
+
+    fn unquoted(&mut self, first: char) -> String {
+        let mut str = String::from(first);
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                break;
+            }
+            str.push(c);
+            self.bump();
+        }
+        str
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>> {
+        self.skip_ignored();
+        let Some(c) = self.bump() else {
+            return Ok(None);
+        };
+        Ok(Some(match c {
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '"' => Token::Str(self.quoted()?),
+            c => Token::Str(self.unquoted(c)),
+        }))
+    }
+}
+
+/// Parse the pairs of an object until its closing brace, or the end for the root
+fn parse_object(lexer: &mut Lexer<'_>, root: bool) -> Result<KeyValues> {
+    let mut object = KeyValues::new();
+    loop {
+        let key = match lexer.next_token()? {
+            Some(Token::Str(key)) => key,
+            Some(Token::Close) if !root => return Ok(object),
+            None if root => return Ok(object),
+            None => return Err(VdfError::UnexpectedEnd),
+            Some(token) => {
+                let found = if token == Token::Open { '{' } else { '}' };
+                return Err(VdfError::Unexpected {
+                    line: lexer.line,
+                    found,
+                });
+            }
+        };
+        let value = match lexer.next_token()?.ok_or(VdfError::UnexpectedEnd)? {
+            Token::Str(value) => Value::String(value),
+            Token::Open => Value::Object(parse_object(lexer, false)?),
+            Token::Close => {
+                return Err(VdfError::Unexpected {
+                    line: lexer.line,
+                    found: '}',
+                })
+            }
+        };
+        object.push(key, value);
+    }
+}
+
+/// Parse KeyValues text, e.g. the output of an api endpoint with `format=vdf`
+pub fn parse(text: &str) -> Result<KeyValues> {
+    parse_object(&mut Lexer::new(text), true)
+}
+
+/// Parse the `format=xml` output of an api endpoint, elements become keys and
+/// elements without children become strings
+pub fn from_xml(xml: &str) -> Result<KeyValues> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    // the objects of the open elements with their names, the root has no name
+    let mut stack = vec![(String::new(), KeyValues::new(), String::new())];
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => {
+                let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+                stack.push((name, KeyValues::new(), String::new()));
+            }
+            Event::Empty(empty) => {
+                let name = String::from_utf8_lossy(empty.local_name().as_ref()).into_owned();
+                if let Some((_, parent, _)) = stack.last_mut() {
+                    parent.push(name, Value::String(String::new()));
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, _, buf)) = stack.last_mut() {
+                    buf.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(cdata) => {
+                if let Some((_, _, buf)) = stack.last_mut() {
+                    buf.push_str(&cdata.decode()?);
+                }
+            }
+            Event::End(_) if stack.len() > 1 => {
+                let (name, object, text) = stack.pop().ok_or(VdfError::UnexpectedEnd)?;
+                let value = if object.is_empty() {
+                    Value::String(text)
+                } else {
+                    Value::Object(object)
+                };
+                if let Some((_, parent, _)) = stack.last_mut() {
+                    parent.push(name, value);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if stack.len() != 1 {
+        return Err(VdfError::UnexpectedEnd);
+    }
+    Ok(stack.pop().map(|(_, root, _)| root).unwrap_or_default())
+}
+
+/// Convert json into the same tree, array elements are keyed by their index like in VDF
+pub fn from_json(json: &str) -> Result<KeyValues> {
+    fn convert(value: serde_json::Value) -> Value {
+        match value {
+            serde_json::Value::Object(map) => Value::Object(KeyValues(
+                map.into_iter().map(|(k, v)| (k, convert(v))).collect(),
+            )),
+            serde_json::Value::Array(values) => Value::Object(KeyValues(
+                values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, v)| (i.to_string(), convert(v)))
+                    .collect(),
+            )),
+            serde_json::Value::String(str) => Value::String(str),
+            serde_json::Value::Null => Value::String(String::new()),
+            other => Value::String(other.to_string()),
+        }
+    }
+
+    match convert(serde_json::from_str(json)?) {
+        Value::Object(object) => Ok(object),
+        value => {
+            let mut object = KeyValues::new();
+            object.push("", value);
+            Ok(object)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Deserialize;
+
+    use super::{from_json, from_str, from_xml, parse, to_string, Value, VdfError};
+
+    const VDF: &str = r#"
+        "response"
+        {
+            // a comment
+            "players"
+            {
+                "0"
+                {
+                    "steamid"   "76561197960287930"
+                    "name"      "say \"hi\""
+                }
+                "1" { steamid 76561197960287931 }
+            }
+            "count" "2" [$WIN32]
+        }
+    "#;
+
+    #[test]
+    fn parses_vdf() {
+        let tree = parse(VDF).unwrap();
+        let players = tree.get_path(&["response", "players"]).unwrap();
+        assert_eq!(players.as_object().unwrap().len(), 2);
+        assert_eq!(
+            players
+                .get("0")
+                .and_then(|p| p.get("name"))
+                .and_then(Value::as_str),
+            Some(r#"say "hi""#)
+        );
+        assert_eq!(
+            players
+                .get("1")
+                .and_then(|p| p.get("SteamID"))
+                .and_then(Value::as_str),
+            Some("76561197960287931")
+        );
+        assert_eq!(
+            tree.get_path(&["response", "count"])
+                .and_then(Value::as_str),
+            Some("2")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_vdf() {
+        assert!(matches!(parse(r#""a" {"#), Err(VdfError::UnexpectedEnd)));
+        assert!(matches!(
+            parse("\"a\"\n}"),
+            Err(VdfError::Unexpected {
+                line: 2,
+                found: '}'
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_xml_and_json() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <response><players><player><steamid>1</steamid></player><player><steamid>2</steamid></player></players></response>"#;
+        let tree = from_xml(xml).unwrap();
+        let players = tree.get_path(&["response", "players"]).unwrap();
+        let ids = players
+            .as_object()
+            .unwrap()
+            .get_all("player")
+            .filter_map(|p| p.get("steamid")?.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["1", "2"]);
+
+        let json = r#"{"response":{"players":[{"steamid":"1"},{"steamid":"2"}],"count":2}}"#;
+        let tree = from_json(json).unwrap();
+        assert_eq!(
+            tree.get_path(&["response", "players", "1", "steamid"])
+                .and_then(Value::as_str),
+            Some("2")
+        );
+        assert_eq!(
+            tree.get_path(&["response", "count"])
+                .and_then(Value::as_str),
+            Some("2")
+        );
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Player {
+        steamid: u64,
+        name: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Response {
+        players: Vec<Player>,
+        count: usize,
+    }
+
+    #[test]
+    fn deserializes() {
+        let response: BTreeMap<String, Response> = from_str(VDF).unwrap();
+        let response = &response["response"];
+        assert_eq!(response.count, 2);
+        assert_eq!(response.players[1].steamid, 76561197960287931);
+        assert_eq!(response.players[1].name, None);
+        assert_eq!(response.players[0].name.as_deref(), Some(r#"say "hi""#));
+
+        let err = from_str::<BTreeMap<String, u32>>(r#""a" "b""#).unwrap_err();
+        assert!(matches!(err, VdfError::Custom(_)));
+    }
+
+    #[test]
+    fn writes_and_serializes() {
+        let tree = parse(VDF).unwrap();
+        let text = to_string(&tree);
+        assert!(text.starts_with("\"response\"\n{\n\t\"players\""));
+        assert_eq!(parse(&text).unwrap(), tree);
+
+        let json = serde_json::to_value(&tree).unwrap();
+        assert_eq!(
+            json["response"]["players"]["0"]["steamid"],
+            "76561197960287930"
+        );
+    }
+}